@@ -0,0 +1,47 @@
+from pathlib import Path
+from statistics import median
+from tempfile import TemporaryDirectory
+from time import perf_counter
+from typing import Final
+
+from belgie import Runtime, Script
+
+MODULES: Final[int] = 200
+RUNS: Final[int] = 10
+
+
+def write_graph(root: Path) -> Path:
+    for index in range(MODULES):
+        (root / f"module{index}.ts").write_text(
+            f"export type Props{index} = {{ label: string }};\n"
+            f"export const render{index} = (props: Props{index}): string => `<div>${{props.label}}</div>`;\n",
+        )
+    main = root / "main.ts"
+    main.write_text(
+        "\n".join(f'import {{ render{index} }} from "./module{index}.ts";' for index in range(MODULES))
+        + f"\nexport default () => [{', '.join(f'render{index}' for index in range(MODULES))}].length;\n",
+    )
+    return main
+
+
+def load_ms(runtime: Runtime, script: Script) -> float:
+    started = perf_counter()
+    runtime(script)()
+    return (perf_counter() - started) * 1000
+
+
+def main() -> None:
+    with TemporaryDirectory() as directory:
+        script = Script.from_file(write_graph(Path(directory)))
+        first: list[float] = []
+        second: list[float] = []
+        with Runtime() as runtime:
+            for _ in range(RUNS):
+                runtime.clear_module_cache()
+                first.append(load_ms(runtime, script))
+                second.append(load_ms(runtime, script))
+    print(f"first={median(first):.1f}ms second={median(second):.1f}ms ({MODULES} modules)")  # noqa: T201
+
+
+if __name__ == "__main__":
+    main()
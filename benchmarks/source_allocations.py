@@ -0,0 +1,29 @@
+import resource
+from typing import Final
+
+from belgie import Runtime, Script
+
+SOURCE_BYTES: Final[int] = 16 * 1024 * 1024
+RUNNERS: Final[int] = 8
+SOURCE: Final[str] = f"const padding = `{'x' * SOURCE_BYTES}`;\nexport default () => padding.length;\n"
+
+
+def peak_rss_mb() -> float:
+    # Kilobytes on Linux.
+    return resource.getrusage(resource.RUSAGE_SELF).ru_maxrss / 1024
+
+
+def main() -> None:
+    script = Script(SOURCE)
+    before = peak_rss_mb()
+    with Runtime() as runtime:
+        runners = [runtime(script) for _ in range(RUNNERS)]
+        for runner in runners:
+            runner()
+        grown = peak_rss_mb() - before
+    source_mb = SOURCE_BYTES / 1024 / 1024
+    print(f"peak rss grew {grown / RUNNERS:.1f}MB per runner for a {source_mb:.0f}MB source")  # noqa: T201
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,36 @@
+from collections.abc import Callable
+from statistics import median
+from time import perf_counter
+from typing import Final
+
+from belgie import Runtime, RuntimeOptions
+
+COMPONENTS: Final[int] = 5_000
+RUNS: Final[int] = 10
+SETUP: Final[str] = "\n".join(
+    [
+        *(
+            f"function component{index}(props) {{ return `<div data-id={index}>${{props.label}}</div>`; }}"
+            for index in range(COMPONENTS)
+        ),
+        f"globalThis.components = [{', '.join(f'component{index}' for index in range(COMPONENTS))}];",
+    ],
+)
+
+
+def first_render_ms(create: Callable[[], Runtime]) -> float:
+    started = perf_counter()
+    with create() as runtime:
+        runtime.eval("components.at(-1)({ label: 'ready' })")
+    return (perf_counter() - started) * 1000
+
+
+def main() -> None:
+    snapshot = Runtime.create_snapshot(SETUP)
+    setup = median(first_render_ms(lambda: Runtime(options=RuntimeOptions(setup=SETUP))) for _ in range(RUNS))
+    snapshotted = median(first_render_ms(lambda: Runtime(snapshot=snapshot)) for _ in range(RUNS))
+    print(f"setup={setup:.1f}ms snapshot={snapshotted:.1f}ms ({len(snapshot)} snapshot bytes)")  # noqa: T201
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,37 @@
+from statistics import median
+from typing import Final
+
+from belgie import Runtime, Script
+
+SIZE: Final[int] = 200_000
+ITERATIONS: Final[int] = 25
+RUNS: Final[int] = 10
+SOURCE: Final[str] = """
+export default (size) => {
+  let total = 0;
+  for (let index = 0; index < size; index += 1) {
+    total += Math.sqrt(index) * (index % 7);
+  }
+  return total;
+};
+"""
+
+
+def run_ms(runtime: Runtime, *, warm: bool) -> float:
+    runner = runtime(Script(SOURCE))
+    runner.load()
+    if warm:
+        runner.warmup(SIZE, iterations=ITERATIONS)
+    _, metrics = runner.timed(SIZE)
+    return metrics["run_ms"]
+
+
+def main() -> None:
+    with Runtime() as runtime:
+        cold = median(run_ms(runtime, warm=False) for _ in range(RUNS))
+        warm = median(run_ms(runtime, warm=True) for _ in range(RUNS))
+    print(f"cold={cold:.2f}ms warm={warm:.2f}ms after {ITERATIONS} warmup calls")  # noqa: T201
+
+
+if __name__ == "__main__":
+    main()
@@ -4,6 +4,8 @@ import type { PluginOption } from "vite";
 
 import type { RenderContext } from "./build.js";
 
+export { renderToStaticMarkup, renderToString } from "./ssr.js";
+
 export interface RenderOptions {
   plugins?: PluginOption[];
   widget: ReactElement;
@@ -0,0 +1,22 @@
+import { createElement } from "react";
+import type { ComponentType } from "react";
+import {
+  renderToString as renderElementToString,
+  renderToStaticMarkup as renderElementToStaticMarkup,
+} from "react-dom/server";
+
+function createComponentElement<P extends object>(component: ComponentType<P>, props: P) {
+  if (typeof component !== "function" && (typeof component !== "object" || component === null)) {
+    throw new TypeError("@belgie/render: component must be a React component");
+  }
+  return createElement(component, props);
+}
+
+export function renderToString<P extends object>(component: ComponentType<P>, props: P): string {
+  return renderElementToString(createComponentElement(component, props));
+}
+
+/** Renders plain HTML without hydration markers, for emails and static pages React never hydrates. */
+export function renderToStaticMarkup<P extends object>(component: ComponentType<P>, props: P): string {
+  return renderElementToStaticMarkup(createComponentElement(component, props));
+}
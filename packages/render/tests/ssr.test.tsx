@@ -0,0 +1,40 @@
+import { renderToStaticMarkup, renderToString } from "../src/index.ts";
+
+function Greeting({ name }: { name: string }) {
+  return <p className="greeting">Hello, {name}!</p>;
+}
+
+function Broken(): never {
+  throw new Error("render failed");
+}
+
+describe("renderToString", () => {
+  it("renders a component with props to HTML", () => {
+    const html = renderToString(Greeting, { name: "belgie" });
+
+    expect(html).toMatch(/^<p class="greeting">/u);
+    expect(html).toContain("belgie");
+  });
+
+  it("propagates errors thrown during render", () => {
+    expect(() => renderToString(Broken, {})).toThrow("render failed");
+  });
+
+  it("rejects values that are not components", () => {
+    expect(() => renderToString(null as never, {})).toThrow(TypeError);
+  });
+});
+
+describe("renderToStaticMarkup", () => {
+  it("renders without the hydration markers renderToString adds", () => {
+    const hydratable = renderToString(Greeting, { name: "belgie" });
+    const markup = renderToStaticMarkup(Greeting, { name: "belgie" });
+
+    expect(hydratable).toContain("<!-- -->");
+    expect(markup).toBe('<p class="greeting">Hello, belgie!</p>');
+  });
+
+  it("rejects values that are not components", () => {
+    expect(() => renderToStaticMarkup(null as never, {})).toThrow(TypeError);
+  });
+});
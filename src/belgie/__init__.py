@@ -1,5 +1,6 @@
 from belgie._core import (
     Command,
+    CompressedModule,
     Environment,
     EnvironmentInstallResult,
     EnvironmentOptions,
@@ -9,7 +10,10 @@ from belgie._core import (
     RuntimeOptions,
     RuntimePermissions,
     Script,
+    configure,
+    version,
 )
+from belgie._renderer import Renderer
 
 type JsonPrimitive = None | bool | int | float | str
 type JsonInput = JsonPrimitive | list[JsonInput] | tuple[JsonInput, ...] | dict[str, JsonInput]
@@ -19,6 +23,7 @@ type JsonArray = list[JsonOutput]
 
 __all__: tuple[str, ...] = (
     "Command",
+    "CompressedModule",
     "Environment",
     "EnvironmentInstallResult",
     "EnvironmentOptions",
@@ -29,8 +34,11 @@ __all__: tuple[str, ...] = (
     "JsonObject",
     "JsonOutput",
     "JsonPrimitive",
+    "Renderer",
     "Runtime",
     "RuntimeOptions",
     "RuntimePermissions",
     "Script",
+    "configure",
+    "version",
 )
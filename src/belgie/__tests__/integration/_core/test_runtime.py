@@ -2,7 +2,10 @@ from __future__ import annotations
 
 import asyncio
 import json
+import select
 import sys
+import threading
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
 from json import loads
 from os import environ
 from pathlib import Path
@@ -17,7 +20,7 @@ from belgie.__tests__.integration._core.conftest import (
     installed_environment,
     rollup_native_package,
 )
-from belgie.errors import BelgieJavaScriptError
+from belgie.errors import BelgieJavaScriptError, BelgieRuntimeError, BelgieTimeoutError
 
 
 def run_fresh_python(source: str) -> CompletedProcess[str]:
@@ -78,6 +81,28 @@ def test_executes_with_runtime_options(tmp_path: Path):
         assert runtime(Script("export default () => 'configured'"))() == "configured"
 
 
+def test_heap_limit_raises_instead_of_aborting():
+    source = """
+const retained = [];
+export default (mode) => {
+  if (mode === "release") {
+    retained.length = 0;
+    return "recovered";
+  }
+  while (true) {
+    retained.push(new Array(100_000).fill(retained.length));
+  }
+};
+"""
+
+    with Runtime(options=RuntimeOptions(max_old_generation_size_mb=64)) as runtime:
+        run = runtime(Script(source))
+        with pytest.raises(BelgieRuntimeError, match="heap limit"):
+            run("allocate")
+
+        assert run("release") == "recovered"
+
+
 def test_runtime_worker_seed_is_deterministic_with_environment(tmp_path: Path):
     project = tmp_path / "project"
     project.mkdir()
@@ -165,6 +190,191 @@ def test_runtime_permissions_can_deny_and_allow_read_access(tmp_path: Path):
         assert runtime(Script(source))() == "secret"
 
 
+def test_runtime_env_vars_respect_env_permissions(tmp_path: Path):
+    project = tmp_path / "project"
+    project.mkdir()
+    source = "export default () => [Deno.env.get('NODE_ENV'), process.env.NODE_ENV];"
+
+    def options(permissions: RuntimePermissions) -> RuntimeOptions:
+        return RuntimeOptions(env_vars={"NODE_ENV": "production"}, permissions=permissions)
+
+    with (
+        Environment(path=project) as env,
+        Runtime(env=env, options=options(RuntimePermissions(allow_env=["NODE_ENV"]))) as runtime,
+    ):
+        assert runtime(Script(source))() == ["production", "production"]
+
+    with (
+        Environment(path=project) as env,
+        Runtime(env=env, options=options(RuntimePermissions(deny_env=["NODE_ENV"]))) as runtime,
+        pytest.raises(BelgieJavaScriptError, match="env|NotCapable"),
+    ):
+        runtime(Script(source))()
+
+
+def test_runtime_permissions_restrict_fetch_to_allowed_hosts(tmp_path: Path):
+    class Handler(BaseHTTPRequestHandler):
+        def do_GET(self) -> None:
+            body = b'{"message": "hello"}'
+            self.send_response(200)
+            self.send_header("content-type", "application/json")
+            self.send_header("content-length", str(len(body)))
+            self.end_headers()
+            self.wfile.write(body)
+
+        def log_message(self, *_args: object) -> None:
+            pass
+
+    project = tmp_path / "project"
+    project.mkdir()
+    server = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+    thread = threading.Thread(target=server.serve_forever, daemon=True)
+    thread.start()
+    origin = f"127.0.0.1:{server.server_address[1]}"
+    source = "export default async (url) => (await fetch(url)).json();"
+
+    try:
+        with (
+            Environment(path=project) as env,
+            Runtime(
+                env=env,
+                options=RuntimeOptions(permissions=RuntimePermissions(allow_net=[origin])),
+            ) as runtime,
+        ):
+            run = runtime(Script(source))
+            assert run(f"http://{origin}/") == {"message": "hello"}
+            with pytest.raises(BelgieJavaScriptError, match="net|NotCapable"):
+                run("http://localhost:1/")
+    finally:
+        server.shutdown()
+        server.server_close()
+        thread.join()
+
+
+def test_sequential_fetches_reuse_one_connection(tmp_path: Path):
+    connections: set[tuple[str, int]] = set()
+
+    class Handler(BaseHTTPRequestHandler):
+        protocol_version = "HTTP/1.1"
+
+        def do_GET(self) -> None:
+            connections.add(self.client_address)
+            body = json.dumps({"path": self.path}).encode()
+            self.send_response(200)
+            self.send_header("content-type", "application/json")
+            self.send_header("content-length", str(len(body)))
+            self.end_headers()
+            self.wfile.write(body)
+
+        def log_message(self, *_args: object) -> None:
+            pass
+
+    project = tmp_path / "project"
+    project.mkdir()
+    server = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+    thread = threading.Thread(target=server.serve_forever, daemon=True)
+    thread.start()
+    origin = f"127.0.0.1:{server.server_address[1]}"
+    source = """
+export default async (origin) => {
+  const paths = [];
+  for (let index = 0; index < 5; index += 1) {
+    paths.push((await (await fetch(`http://${origin}/${index}`)).json()).path);
+  }
+  return paths;
+};
+"""
+
+    try:
+        with (
+            Environment(path=project) as env,
+            Runtime(env=env, options=RuntimeOptions(permissions=RuntimePermissions(allow_net=[origin]))) as runtime,
+        ):
+            run = runtime(Script(source))
+            assert run(origin) == ["/0", "/1", "/2", "/3", "/4"]
+            assert run(origin) == ["/0", "/1", "/2", "/3", "/4"]
+    finally:
+        server.shutdown()
+        server.server_close()
+        thread.join()
+
+    assert len(connections) == 1
+
+
+def test_timed_out_calls_cancel_in_flight_fetches(tmp_path: Path):
+    disconnected = threading.Event()
+
+    class Handler(BaseHTTPRequestHandler):
+        def do_GET(self) -> None:
+            # Never answer; report when the client gives up on the request.
+            for _ in range(100):
+                readable, _, _ = select.select([self.connection], [], [], 0.05)
+                if readable and not self.connection.recv(1):
+                    disconnected.set()
+                    return
+
+        def log_message(self, *_args: object) -> None:
+            pass
+
+    project = tmp_path / "project"
+    project.mkdir()
+    server = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+    thread = threading.Thread(target=server.serve_forever, daemon=True)
+    thread.start()
+    origin = f"127.0.0.1:{server.server_address[1]}"
+    options = RuntimeOptions(permissions=RuntimePermissions(allow_net=[origin]), timeout=0.5)
+
+    try:
+        with Environment(path=project) as env, Runtime(env=env, options=options) as runtime:
+            run = runtime(Script("export default async (url) => (await fetch(url)).text();"))
+            with pytest.raises(BelgieTimeoutError):
+                run(f"http://{origin}/slow")
+
+            assert disconnected.wait(2)
+    finally:
+        server.shutdown()
+        server.server_close()
+        thread.join()
+
+
+def test_denied_operations_reject_inside_javascript(tmp_path: Path):
+    project = tmp_path / "project"
+    project.mkdir()
+    source = """
+export default async () => {
+  const denied = async (operation) => {
+    try {
+      await operation();
+      return "allowed";
+    } catch (error) {
+      return error.name;
+    }
+  };
+  return {
+    net: await denied(() => fetch("http://blocked.example/")),
+    env: await denied(() => Deno.env.get("HOME")),
+    read: await denied(() => Deno.readTextFile("/etc/hostname")),
+  };
+};
+"""
+
+    with (
+        Environment(path=project) as env,
+        Runtime(
+            env=env,
+            options=RuntimeOptions(
+                permissions=RuntimePermissions(
+                    allow_net=["api.example.com"],
+                    allow_env=["BELGIE_ALLOWED"],
+                    allow_read=[str(project)],
+                    prompt=False,
+                ),
+            ),
+        ) as runtime,
+    ):
+        assert runtime(Script(source))() == {"net": "NotCapable", "env": "NotCapable", "read": "NotCapable"}
+
+
 def test_package_worker_applies_memory_options_with_cli_snapshot(tmp_path: Path):
     project = tmp_path / "project"
     project.mkdir()
@@ -298,6 +508,102 @@ print(json.dumps({
     }
 
 
+def test_configure_applies_v8_flags_before_the_first_runtime() -> None:
+    result = run_fresh_python(
+        """
+import belgie
+from belgie.errors import BelgieRuntimeError
+
+belgie.configure(v8_flags=["--jitless"])
+with belgie.Runtime() as runtime:
+    print(runtime(belgie.Script("export default () => [1, 2, 3].map((x) => x * 2);"))())
+try:
+    belgie.configure(v8_flags=["--jitless"])
+except BelgieRuntimeError as error:
+    print(error)
+""".strip(),
+    )
+
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.splitlines() == [
+        "[2, 4, 6]",
+        "V8 flags must be configured once, before the first Runtime starts",
+    ]
+
+
+def test_version_does_not_start_v8_before_configure() -> None:
+    result = run_fresh_python(
+        """
+import belgie
+
+print(sorted(belgie.version()))
+belgie.configure(v8_flags=["--jitless"])
+print("configured")
+""".strip(),
+    )
+
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.splitlines() == ["['belgie', 'deno', 'deno_core', 'v8']", "configured"]
+
+
+def test_configure_rejects_unrecognized_v8_flags() -> None:
+    result = run_fresh_python(
+        """
+import belgie
+
+try:
+    belgie.configure(v8_flags=["--not-a-real-v8-flag"])
+except ValueError as error:
+    print(error)
+""".strip(),
+    )
+
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.strip() == "Unrecognized V8 flags: --not-a-real-v8-flag"
+
+
+def test_configure_rejects_flags_that_v8_would_abort_or_exit_on() -> None:
+    result = run_fresh_python(
+        """
+import belgie
+
+for flags in (["--help"], ["jitless"], ["--stack-size=large"]):
+    try:
+        belgie.configure(v8_flags=flags)
+    except ValueError as error:
+        print(error)
+with belgie.Runtime() as runtime:
+    print(runtime(belgie.Script("export default () => 'alive'"))())
+""".strip(),
+    )
+
+    assert result.returncode == 0, result.stderr
+    lines = result.stdout.splitlines()
+    assert lines[:2] == ["V8 flag --help would exit the process", "V8 flags must start with '-', got 'jitless'"]
+    assert "--stack-size=large" in lines[2]
+    assert lines[3] == "alive"
+
+
+def test_configure_reports_conflicting_second_configuration() -> None:
+    result = run_fresh_python(
+        """
+import belgie
+from belgie.errors import BelgieRuntimeError
+
+belgie.configure(v8_flags=["--jitless"])
+try:
+    belgie.configure(v8_flags=["--no-opt"])
+except BelgieRuntimeError as error:
+    print(error)
+""".strip(),
+    )
+
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.strip() == (
+        "V8 flags must be configured once, before the first Runtime starts; V8 is already configured with --jitless"
+    )
+
+
 @pytest.mark.skipif(sys.platform == "win32", reason="uvloop is unavailable on Windows")
 def test_importing_belgie_before_uvloop_can_create_event_loop() -> None:
     result = run_fresh_python(
@@ -313,6 +619,29 @@ loop.close()
     assert result.returncode == 0, result.stderr
 
 
+@pytest.mark.skipif(sys.platform == "win32", reason="uvloop is unavailable on Windows")
+def test_async_runner_calls_complete_under_uvloop() -> None:
+    result = run_fresh_python(
+        """
+import uvloop
+
+from belgie import Runtime, Script
+
+
+async def main():
+    async with Runtime() as runtime:
+        run = runtime(Script("export default async (value) => value * 2;"))
+        print(await run(21), await run.ready())
+
+
+uvloop.run(main())
+""".strip(),
+    )
+
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.strip() == "42 None"
+
+
 async def test_package_script_loads_native_rollup_addon_before_command(
     isolated_project_cwd: Path,
 ) -> None:
@@ -1,25 +1,48 @@
 from __future__ import annotations
 
+import json
+import sys
+
 import pytest
 
 from belgie import _core, errors as public_errors
 from belgie.__tests__.unit._core.conftest import run_source
-from belgie._core import BelgieError, BelgieJavaScriptError, BelgieModuleError, BelgieRuntimeError, Runtime, Script
+from belgie._core import (
+    BelgieClosedError,
+    BelgieError,
+    BelgieJavaScriptError,
+    BelgieModuleError,
+    BelgieResultTooLargeError,
+    BelgieRuntimeError,
+    BelgieSyntaxError,
+    BelgieTimeoutError,
+    Runtime,
+    RuntimeOptions,
+    Script,
+)
 
 
 def test_exception_hierarchy_is_exported_from_core() -> None:
     assert issubclass(BelgieRuntimeError, BelgieError)
     assert issubclass(BelgieModuleError, BelgieError)
     assert issubclass(BelgieJavaScriptError, BelgieError)
+    assert issubclass(BelgieTimeoutError, BelgieError)
+    assert issubclass(BelgieSyntaxError, BelgieModuleError)
+    assert issubclass(BelgieClosedError, BelgieRuntimeError)
+    assert issubclass(BelgieResultTooLargeError, BelgieRuntimeError)
 
 
 @pytest.mark.parametrize(
     "error_type",
     [
+        BelgieClosedError,
         BelgieError,
         BelgieJavaScriptError,
         BelgieModuleError,
+        BelgieResultTooLargeError,
         BelgieRuntimeError,
+        BelgieSyntaxError,
+        BelgieTimeoutError,
     ],
 )
 def test_core_exception_classes_use_public_error_module(error_type: type[BelgieError]) -> None:
@@ -31,6 +54,10 @@ def test_core_errors_are_identical_to_public_errors() -> None:
     assert _core.BelgieRuntimeError is public_errors.BelgieRuntimeError
     assert _core.BelgieModuleError is public_errors.BelgieModuleError
     assert _core.BelgieJavaScriptError is public_errors.BelgieJavaScriptError
+    assert _core.BelgieTimeoutError is public_errors.BelgieTimeoutError
+    assert _core.BelgieSyntaxError is public_errors.BelgieSyntaxError
+    assert _core.BelgieClosedError is public_errors.BelgieClosedError
+    assert _core.BelgieResultTooLargeError is public_errors.BelgieResultTooLargeError
 
 
 def test_missing_run_export_raises_module_error() -> None:
@@ -38,6 +65,11 @@ def test_missing_run_export_raises_module_error() -> None:
         run_source("export const answer = 42;")
 
 
+def test_non_callable_default_export_is_not_returned_as_a_value() -> None:
+    with pytest.raises(BelgieModuleError, match="callable run function"):
+        run_source("export default { value: 42 };")
+
+
 def test_non_function_run_export_raises_module_error() -> None:
     with pytest.raises(BelgieModuleError, match="not callable"):
         run_source("export const run = 42;")
@@ -52,6 +84,52 @@ def test_module_load_failure_raises_module_error() -> None:
     assert "missing.js" in str(exc_info.value)
 
 
+def test_runner_load_surfaces_compile_errors_before_the_first_call() -> None:
+    with Runtime() as runtime:
+        run = runtime(Script("export default function run( { return 1; }"))
+
+        with pytest.raises(BelgieSyntaxError):
+            run.load()
+
+
+def test_runtime_check_reports_syntax_errors_without_running_the_script() -> None:
+    missing_brace = "globalThis.ran = true;\nexport default function run() {\n  return 1;\n"
+    missing_expression = "export default () => {\n  const value = ;\n  return value;\n};"
+
+    with pytest.raises(BelgieSyntaxError) as missing_brace_info:
+        Runtime.check(missing_brace)
+    with pytest.raises(BelgieSyntaxError, match="line 2") as missing_expression_info:
+        Runtime.check(missing_expression)
+
+    assert (missing_brace_info.value.line or 0) >= 3
+    assert missing_expression_info.value.line == 2
+    assert missing_expression_info.value.column is not None
+    assert missing_expression_info.value.source_line == "  const value = ;"
+    assert Runtime.check("export default () => <main />;") is None
+
+
+def test_unparsable_module_raises_syntax_error() -> None:
+    script = Script(
+        'import { x } from "./broken.js"; export default () => x;',
+        modules={"./broken.js": "export const x = ;"},
+    )
+
+    with Runtime() as runtime, pytest.raises(BelgieSyntaxError):
+        runtime(script)()
+
+
+def test_runner_load_compiles_once_for_repeated_calls() -> None:
+    source = "globalThis.loads = (globalThis.loads ?? 0) + 1; export default (value) => [value, globalThis.loads];"
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+        run.load()
+        run.load()
+
+        assert run(1) == [1, 1]
+        assert run(2) == [2, 1]
+
+
 def test_javascript_throw_raises_javascript_error() -> None:
     source = "export default function run() { throw new TypeError('vanilla js failed'); }"
 
@@ -59,15 +137,176 @@ def test_javascript_throw_raises_javascript_error() -> None:
         run_source(source)
 
 
+def test_javascript_error_exposes_structured_details() -> None:
+    source = """export default function run() {
+  throw new RangeError("out of range");
+}
+"""
+
+    with pytest.raises(BelgieJavaScriptError) as exc_info:
+        run_source(source)
+
+    error = exc_info.value
+    assert error.name == "RangeError"
+    assert error.message == "out of range"
+    assert error.stack is not None
+    assert "out of range" in error.stack
+    assert error.filename is not None
+    assert error.filename.endswith("__deno_python_inline__.ts")
+    assert error.line == 2
+    assert error.column is not None
+
+
+def test_javascript_error_to_json_reports_every_stack_frame() -> None:
+    source = """function inner() {
+  throw new Error("deep failure");
+}
+function middle() {
+  inner();
+}
+export default function run() {
+  middle();
+}
+"""
+
+    with pytest.raises(BelgieJavaScriptError) as exc_info:
+        run_source(source)
+
+    document = exc_info.value.to_json()
+    assert json.loads(json.dumps(document)) == document
+    assert (document["name"], document["message"]) == ("Error", "deep failure")
+    assert "deep failure" in (document["stack"] or "")
+    frames = document["frames"]
+    assert [frame["function"] for frame in frames[:3]] == ["inner", "middle", "run"]
+    assert [frame["line"] for frame in frames[:3]] == [2, 5, 8]
+    assert all(set(frame) == {"file", "line", "column", "function"} for frame in frames)
+    assert all(frame["file"].endswith("__deno_python_inline__.ts") for frame in frames[:3])
+    assert exc_info.value.frames == frames
+    document["frames"].clear()
+    assert len(exc_info.value.to_json()["frames"]) == len(frames)
+
+
+def test_typescript_errors_report_original_source_lines() -> None:
+    source = """interface Input {
+  value: number;
+}
+
+export default function run(input: Input): number {
+  throw new Error(`bad value ${input.value}`);
+}
+"""
+
+    pattern = r"bad value 1[\s\S]*__deno_python_inline__\.ts:6:"
+    with pytest.raises(BelgieJavaScriptError, match=pattern) as exc_info:
+        run_source(source, {"value": 1})
+
+    frame = exc_info.value.frames[0]
+    assert frame["file"] is not None
+    assert frame["file"].endswith("__deno_python_inline__.ts")
+    assert (frame["function"], frame["line"], frame["column"]) == ("run", 6, 9)
+    assert (exc_info.value.line, exc_info.value.column) == (6, 9)
+
+
+def test_typescript_file_errors_map_to_original_lines(write_script) -> None:
+    write_script(
+        'type Input = { value: number };\ninterface Output { text: string }\nexport const fail = (input: Input): Output => { throw new Error("bad " + input.value); };\n',
+        "lib/fail.ts",
+    )
+    path = write_script(
+        'import { fail } from "./lib/fail.ts";\nexport default (input: { value: number }) => fail(input);\n',
+        "main.ts",
+    )
+
+    with Runtime() as runtime, pytest.raises(BelgieJavaScriptError) as exc_info:
+        runtime(Script.from_file(path))({"value": 1})
+
+    error = exc_info.value
+    assert error.filename is not None
+    assert error.filename.endswith("lib/fail.ts")
+    assert error.line == 3
+    assert error.stack is not None
+    assert "fail.ts:3:" in error.stack
+
+
 def test_closed_runner_raises_runtime_error() -> None:
     with Runtime() as runtime:
         run = runtime(Script("export default function run() { return 'ok'; }"))
         assert run() == "ok"
 
-    with pytest.raises(BelgieRuntimeError, match="closed"):
+    with pytest.raises(BelgieClosedError, match="closed"):
         run()
 
 
+def test_closed_runtime_raises_closed_error_when_binding() -> None:
+    with Runtime() as runtime:
+        pass
+
+    with pytest.raises(BelgieClosedError, match="closed"):
+        runtime(Script("export default () => 1;"))
+
+
+def test_runaway_script_raises_timeout_error_and_runner_recovers() -> None:
+    source = """
+let calls = 0;
+export default function run() {
+  calls += 1;
+  if (calls === 1) {
+    while (true) {}
+  }
+  return calls;
+}
+"""
+
+    with Runtime(options=RuntimeOptions(timeout=0.2)) as runtime:
+        run = runtime(Script(source))
+        with pytest.raises(BelgieTimeoutError, match="timed out"):
+            run()
+        assert run() == 2
+
+
+def test_pending_promise_raises_timeout_error() -> None:
+    with (
+        Runtime(options=RuntimeOptions(timeout=0.2)) as runtime,
+        pytest.raises(BelgieTimeoutError, match="timed out"),
+    ):
+        runtime(Script("export default () => new Promise(() => {});"))()
+
+
+@pytest.mark.skipif(sys.platform != "linux", reason="cpu_budget needs a per-thread CPU clock")
+def test_cpu_budget_stops_spinning_calls_but_not_waiting_ones() -> None:
+    source = """
+export default async function run(spin) {
+  if (spin) {
+    while (true) {}
+  }
+  await new Promise((resolve) => setTimeout(resolve, 500));
+  return "waited";
+}
+"""
+
+    with Runtime(options=RuntimeOptions(cpu_budget=0.2)) as runtime:
+        run = runtime(Script(source))
+        with pytest.raises(BelgieTimeoutError, match="CPU budget of 0.2 seconds"):
+            run(True)
+        assert run(False) == "waited"
+
+
+def test_oversized_result_raises_and_runner_recovers() -> None:
+    with Runtime(options=RuntimeOptions(max_result_bytes=1024 * 1024)) as runtime:
+        run = runtime(Script('export default (size) => "x".repeat(size);'))
+        with pytest.raises(BelgieResultTooLargeError, match="max_result_bytes"):
+            run(10 * 1024 * 1024)
+        assert run(3) == "xxx"
+
+
+def test_binary_results_count_their_byte_length_against_max_result_bytes() -> None:
+    with Runtime(options=RuntimeOptions(max_result_bytes=1024)) as runtime:
+        run = runtime(Script("export default (size) => new Uint8Array(size);"))
+        assert run(1000) == bytes(1000)
+        with pytest.raises(BelgieResultTooLargeError, match="max_result_bytes"):
+            run(2000)
+
+
 def test_javascript_bigint_return_raises_type_error() -> None:
     with pytest.raises(TypeError, match="BigInt"):
         run_source("export default function run() { return 42n; }")
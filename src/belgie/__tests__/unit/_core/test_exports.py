@@ -71,6 +71,7 @@ def test_runtime_exports_are_available_from_core_module() -> None:
         "BelgieJavaScriptError",
         "BelgieModuleError",
         "BelgieRuntimeError",
+        "BelgieTimeoutError",
         "PackageInstallResult",
         "PackageUpdateChange",
         "PackageUpdateResult",
@@ -96,6 +97,14 @@ def test_option_types_are_exported_from_top_level_belgie() -> None:
     assert isinstance(RuntimePermissions.none(), RuntimePermissions)
 
 
+def test_version_reports_embedded_engine_versions() -> None:
+    versions = belgie.version()
+
+    assert belgie.version is _core.version
+    assert set(versions) == {"belgie", "deno", "deno_core", "v8"}
+    assert all(isinstance(value, str) and value[0].isdigit() for value in versions.values())
+
+
 def test_no_deno_public_error_names_are_exported() -> None:
     assert not hasattr(belgie, "DenoError")
     assert not hasattr(belgie, "DenoRuntimeError")
@@ -1,7 +1,11 @@
 from __future__ import annotations
 
+from datetime import datetime, timedelta, timezone
+from typing import Any, cast
+
 import pytest
 
+from belgie import Runtime, RuntimeOptions, Script
 from belgie.__tests__.unit._core.conftest import run_source
 
 
@@ -24,6 +28,121 @@ def test_round_trips_json_values() -> None:
     }
 
 
+def test_round_trips_bytes_as_uint8_arrays() -> None:
+    source = """
+export default (data, nested) => ({
+  isUint8Array: data instanceof Uint8Array,
+  data,
+  nested: nested.payload,
+  buffer: new Uint8Array([9, 8]).buffer,
+});
+"""
+
+    assert run_source(source, b"\x00\x01\x02", {"payload": bytearray(b"\xff")}) == {
+        "isUint8Array": True,
+        "data": b"\x00\x01\x02",
+        "nested": b"\xff",
+        "buffer": b"\x09\x08",
+    }
+
+
+def test_converts_dates_sets_and_maps_to_python_values() -> None:
+    source = """
+export default (when, naive) => ({
+  isDate: when instanceof Date && naive instanceof Date,
+  when,
+  naive,
+  later: new Date(when.getTime() + 1_500),
+  set: new Set([1, "two", 1]),
+  map: new Map([["a", 1], [2, [undefined]]]),
+  nested: [new Set(), new Map()],
+});
+"""
+    when = datetime(2024, 5, 17, 12, 30, 45, 123_000, tzinfo=timezone(timedelta(hours=2)))
+
+    result = run_source(source, when, datetime(2024, 5, 17, 10, 30, 45, 123_000))
+
+    assert result == {
+        "isDate": True,
+        "when": when,
+        "naive": when,
+        "later": when + timedelta(seconds=1.5),
+        "set": {1, "two"},
+        "map": {"a": 1, 2: [None]},
+        "nested": [set(), {}],
+    }
+    assert result["when"].tzinfo is timezone.utc
+
+
+def test_text_encoder_and_decoder_round_trip_multi_byte_text() -> None:
+    source = """
+export default (data) => ({
+  encoded: new TextEncoder().encode("h\u00e9llo"),
+  decoded: new TextDecoder().decode(data),
+});
+"""
+
+    assert run_source(source, "h\u00e9llo \U0001f600".encode()) == {
+        "encoded": "h\u00e9llo".encode(),
+        "decoded": "h\u00e9llo \U0001f600",
+    }
+
+
+def test_structured_clone_copies_nested_maps_independently() -> None:
+    source = """
+export default () => {
+  const original = { routes: new Map([["home", { hits: 1 }]]), tags: ["a"] };
+  const copy = structuredClone(original);
+  copy.routes.get("home").hits = 2;
+  copy.tags.push("b");
+  return { original, copy, isMap: copy.routes instanceof Map };
+};
+"""
+
+    assert run_source(source) == {
+        "original": {"routes": {"home": {"hits": 1}}, "tags": ["a"]},
+        "copy": {"routes": {"home": {"hits": 2}}, "tags": ["a", "b"]},
+        "isMap": True,
+    }
+
+
+def test_returns_native_python_values_without_json_strings() -> None:
+    result = run_source("export default () => [1, 'a', { b: true, c: 1.5 }];")
+
+    assert result == [1, "a", {"b": True, "c": 1.5}]
+    assert isinstance(result, list)
+    assert isinstance(result[2], dict)
+
+
+def test_returns_nested_render_outputs_as_dicts() -> None:
+    source = """
+export default async () => {
+  const preload = { href: "/app.js", as: "script" };
+  return {
+    html: "<div/>",
+    head: { title: "<title>Home</title>", tags: ["<meta charset=utf-8>"] },
+    hints: [preload, { ...preload, href: "/app.css", as: "style" }],
+    again: preload,
+  };
+};
+"""
+
+    result = run_source(source)
+
+    assert result == {
+        "html": "<div/>",
+        "head": {"title": "<title>Home</title>", "tags": ["<meta charset=utf-8>"]},
+        "hints": [{"href": "/app.js", "as": "script"}, {"href": "/app.css", "as": "style"}],
+        "again": {"href": "/app.js", "as": "script"},
+    }
+    assert result["again"] is not result["hints"][0]
+
+
+@pytest.mark.parametrize("expression", ["undefined", "null"])
+def test_converts_top_level_undefined_and_null_returns_to_none(expression: str) -> None:
+    assert run_source(f"export default function run() {{ return {expression}; }}") is None
+
+
 def test_converts_undefined_return_values_to_none_or_omits_object_fields() -> None:
     source = """
 export default function run() {
@@ -39,7 +158,8 @@ export default function run() {
     [
         ({1: "not a string key"}, TypeError, "JSON object keys must be strings"),
         ({"value": {1, 2, 3}}, TypeError, "Only JSON-serializable"),
-        ({"value": b"bytes"}, TypeError, "Only JSON-serializable"),
+        ({"\u0000belgie:bytes": "00"}, ValueError, "reserved for binary data"),
+        ({"\u0000belgie:date": 0}, ValueError, "reserved for dates"),
         ({"value": object()}, TypeError, "Only JSON-serializable"),
         ({"value": float("nan")}, ValueError, "finite"),
         ({"value": float("inf")}, ValueError, "finite"),
@@ -62,12 +182,11 @@ def test_rejects_non_json_python_inputs(
         ("42n", TypeError, "BigInt"),
         ("Symbol('x')", TypeError, "Symbol"),
         ("Number.POSITIVE_INFINITY", ValueError, "finite"),
+        ("[1, Number.NaN]", ValueError, r"finite"),
         ("function named() {}", ValueError, "function"),
-        ("new Date()", ValueError, "Date"),
-        ("new Map()", ValueError, "Map"),
-        ("new Set()", ValueError, "Set"),
         ("new RegExp('x')", ValueError, "RegExp"),
-        ("new Uint8Array([1])", ValueError, "binary data"),
+        ("new Date(Number.NaN)", ValueError, "Invalid Date"),
+        ("new Int16Array([1])", ValueError, "binary data"),
         ("new (class Custom {})()", ValueError, "Only plain JavaScript objects"),
     ],
 )
@@ -92,3 +211,45 @@ def test_rejects_non_json_javascript_return_values(
 def test_rejects_javascript_cycles(source: str) -> None:
     with pytest.raises(ValueError, match="cycle"):
         run_source(source)
+
+
+def test_reports_the_path_a_javascript_cycle_refers_back_to() -> None:
+    source = "export default function run() { const child = {}; child.parent = { child }; return { child }; }"
+
+    with pytest.raises(ValueError, match=r"cycle at \$\.child\.parent\.child .*refers back to \$\.child$"):
+        run_source(source)
+
+
+UNSERIALIZABLE_SOURCE = """
+export default function run() {
+  const value = { format: function format() {}, tag: Symbol("tag"), big: 42n, ratio: 0 / 0 };
+  value.self = value;
+  return value;
+}
+"""
+
+
+@pytest.mark.parametrize(
+    ("policy", "expected"),
+    [
+        ("null", {"format": None, "tag": None, "big": None, "ratio": None, "self": None}),
+        (
+            "stringify",
+            {"format": "[Function format]", "tag": "Symbol(tag)", "big": "42", "ratio": "NaN", "self": "[Circular $]"},
+        ),
+    ],
+)
+def test_on_unserializable_replaces_values_without_a_json_form(policy: str, expected: object) -> None:
+    options = RuntimeOptions(on_unserializable=cast("Any", policy))
+
+    with Runtime(options=options) as runtime:
+        assert runtime(Script(UNSERIALIZABLE_SOURCE))() == expected
+
+    assert f'on_unserializable="{policy}"' in repr(options)
+
+
+def test_on_unserializable_defaults_to_an_error_and_rejects_unknown_policies() -> None:
+    with pytest.raises(TypeError, match=r"Symbol at \$\.tag"):
+        run_source(UNSERIALIZABLE_SOURCE.replace("format: function format() {}, ", ""))
+    with pytest.raises(ValueError, match="on_unserializable"):
+        RuntimeOptions(on_unserializable=cast("Any", "drop"))
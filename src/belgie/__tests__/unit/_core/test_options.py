@@ -1,10 +1,12 @@
 from __future__ import annotations
 
+import os
+import sys
 from typing import Any, cast
 
 import pytest
 
-from belgie import EnvironmentOptions, Runtime, RuntimeOptions, RuntimePermissions, _core
+from belgie import EnvironmentOptions, Runtime, RuntimeOptions, RuntimePermissions, Script, _core
 
 
 def test_runtime_options_accepts_default_and_explicit_memory_limits() -> None:
@@ -35,6 +37,150 @@ def test_runtime_options_reject_non_positive_memory_limits(kwargs: dict[str, int
         RuntimeOptions(**cast("Any", kwargs))
 
 
+def test_runtime_options_repr_hides_setup_code() -> None:
+    assert "setup=None" in repr(RuntimeOptions())
+    assert "setup=<code>" in repr(RuntimeOptions(setup="globalThis.ready = true;"))
+
+
+def test_runtime_options_fixed_time_requires_deterministic_mode() -> None:
+    assert "deterministic=true, fixed_time_ms=Some(0)" in repr(RuntimeOptions(deterministic=True))
+    with pytest.raises(ValueError, match="deterministic=True"):
+        RuntimeOptions(fixed_time_ms=0)
+    with pytest.raises(ValueError, match="epoch"):
+        RuntimeOptions(deterministic=True, fixed_time_ms=10**16)
+
+
+def test_runtime_options_accept_timeout_without_environment() -> None:
+    options = RuntimeOptions(timeout=1.5)
+
+    assert "timeout=Some(1.5)" in repr(options)
+    Runtime(options=options)
+
+
+@pytest.mark.parametrize("timeout", [0, -1.0, float("nan"), float("inf")])
+def test_runtime_options_reject_non_positive_timeouts(timeout: float) -> None:
+    with pytest.raises(ValueError, match="timeout"):
+        RuntimeOptions(timeout=timeout)
+
+
+@pytest.mark.skipif(sys.platform != "linux", reason="cpu_budget needs a per-thread CPU clock")
+def test_runtime_options_validate_cpu_budget() -> None:
+    assert "cpu_budget=None" in repr(RuntimeOptions())
+    assert "cpu_budget=Some(0.5)" in repr(RuntimeOptions(cpu_budget=0.5))
+    with pytest.raises(ValueError, match="cpu_budget"):
+        RuntimeOptions(cpu_budget=0)
+
+
+@pytest.mark.skipif(sys.platform != "linux", reason="cpu_affinity pins threads with sched_setaffinity")
+def test_runtime_options_pin_runner_threads_to_cores() -> None:
+    cores = sorted(os.sched_getaffinity(0))[:2]
+    source = "export default () => host.affinity();"
+
+    options = RuntimeOptions(cpu_affinity=cores)
+
+    # Every runtime starts from the first core, even when runtimes share the options.
+    for _ in range(2):
+        with Runtime(options=options) as runtime:
+            runtime.register("affinity", lambda: sorted(os.sched_getaffinity(0)))
+            pinned = [runtime(Script(source))() for _ in range(3)]
+
+        assert pinned == [[cores[index % len(cores)]] for index in range(3)]
+    assert f"cpu_affinity=Some({cores})" in repr(RuntimeOptions(cpu_affinity=cores))
+    assert f"cpu_affinity=Some([{cores[0]}])" in repr(RuntimeOptions(cpu_affinity=cores[0]))
+    assert "cpu_affinity=None" in repr(RuntimeOptions())
+    with pytest.raises(ValueError, match="not available"):
+        RuntimeOptions(cpu_affinity=max(os.sched_getaffinity(0)) + 1)
+    with pytest.raises(ValueError, match="at least one core"):
+        RuntimeOptions(cpu_affinity=[])
+
+
+def test_runtime_options_accept_a_positive_result_limit() -> None:
+    assert "max_result_bytes=None" in repr(RuntimeOptions())
+    assert "max_result_bytes=Some(1024)" in repr(RuntimeOptions(max_result_bytes=1024))
+    with pytest.raises(ValueError, match="max_result_bytes"):
+        RuntimeOptions(max_result_bytes=0)
+
+
+def test_runtime_options_expose_env_vars_to_scripts() -> None:
+    options = RuntimeOptions(env_vars={"NODE_ENV": "production", "API_TOKEN": "secret"})
+    source = """
+export default () => {
+  Deno.env.set("CACHE", "warm");
+  return [Deno.env.get("NODE_ENV"), Deno.env.get("HOME"), Deno.env.toObject()];
+};
+"""
+
+    with Runtime(options=options) as runtime:
+        node_env, home, variables = runtime(Script(source))()
+
+    assert (node_env, home) == ("production", None)
+    assert variables == {"API_TOKEN": "secret", "CACHE": "warm", "NODE_ENV": "production"}
+    assert "env_vars=Some([\"API_TOKEN\", \"NODE_ENV\"])" in repr(options)
+    assert "secret" not in repr(options)
+    with pytest.raises(ValueError, match="env_vars"):
+        RuntimeOptions(env_vars={"A=B": "1"})
+
+
+def test_runtime_options_node_compat_provides_a_process_global() -> None:
+    options = RuntimeOptions(node_compat=True, env_vars={"NODE_ENV": "production"})
+    source = """
+export default async () => {
+  const order = [];
+  await new Promise((resolve) => {
+    process.nextTick((label) => {
+      order.push(label);
+      resolve();
+    }, "tick");
+    order.push("sync");
+  });
+  return [process.env.NODE_ENV, order, typeof process.platform, process.version.startsWith("v")];
+};
+"""
+
+    with Runtime(options=options) as runtime:
+        run = runtime(Script(source))
+        assert run() == ["production", ["sync", "tick"], "string", True]
+        assert "process" not in run.globals()
+
+    assert "node_compat=true" in repr(options)
+    assert "node_compat=false" in repr(RuntimeOptions())
+
+
+def test_runtime_options_default_and_configured_queue_size() -> None:
+    assert "queue_size=64" in repr(RuntimeOptions())
+    assert "queue_size=2" in repr(RuntimeOptions(queue_size=2))
+
+
+@pytest.mark.parametrize("queue_size", [0, -1])
+def test_runtime_options_reject_non_positive_queue_sizes(queue_size: int) -> None:
+    with pytest.raises(ValueError, match="queue_size"):
+        RuntimeOptions(queue_size=queue_size)
+
+
+def test_runtime_options_inspector_is_off_by_default() -> None:
+    assert "inspect=None, inspect_brk=False" in repr(RuntimeOptions())
+    options = RuntimeOptions(inspect="127.0.0.1:9229", inspect_brk=True)
+    assert 'inspect=Some("127.0.0.1:9229"), inspect_brk=True' in repr(options)
+
+
+def test_runtime_options_select_builtin_extensions() -> None:
+    default_extensions = '["text_encoding", "crypto", "performance", "url", "base64", "structured_clone", "abort"]'
+    assert f"extensions={default_extensions}" in repr(RuntimeOptions())
+    options = RuntimeOptions(extensions=["url", "crypto", "url"])
+    assert 'extensions=["url", "crypto"]' in repr(options)
+    assert "extensions=[]" in repr(RuntimeOptions(extensions=[]))
+
+    with pytest.raises(ValueError, match="extensions must be drawn from"):
+        RuntimeOptions(extensions=["fetch"])
+
+
+def test_runtime_options_reject_invalid_inspector_addresses() -> None:
+    with pytest.raises(ValueError, match="inspect"):
+        RuntimeOptions(inspect="localhost")
+    with pytest.raises(ValueError, match="inspect_brk requires inspect"):
+        RuntimeOptions(inspect_brk=True)
+
+
 def test_runtime_options_reject_positional_memory_limits() -> None:
     options_type = cast("Any", RuntimeOptions)
 
@@ -1,7 +1,14 @@
 from __future__ import annotations
 
 import asyncio
+import gc
 import inspect
+import json
+import queue
+import re
+import threading
+import time
+from pathlib import Path
 from typing import Any, cast
 
 import pytest
@@ -106,16 +113,1148 @@ def test_script_runner_closes_with_runtime() -> None:
         run()
 
 
+def test_sync_runner_releases_the_gil_while_javascript_runs() -> None:
+    entered = threading.Event()
+    helper_ran = threading.Event()
+
+    def helper() -> None:
+        entered.wait(timeout=10)
+        helper_ran.set()
+
+    with Runtime() as runtime:
+        runtime.register("enter", entered.set)
+        runtime.register("helper_ran", lambda: helper_ran.wait(timeout=10))
+        run = runtime(Script("export default () => { host.enter(); return host.helper_ran() ? 'done' : 'starved'; };"))
+        thread = threading.Thread(target=helper)
+        thread.start()
+        try:
+            result = run()
+        finally:
+            entered.set()
+            thread.join()
+
+    assert result == "done"
+
+
+def test_timers_and_microtasks_fire_while_calls_are_pending() -> None:
+    source = """
+export default async () => {
+  const order = [];
+  queueMicrotask(() => order.push("microtask"));
+  await new Promise((resolve) => setTimeout(() => resolve(order.push("timeout")), 10));
+  let ticks = 0;
+  await new Promise((resolve) => {
+    const interval = setInterval(() => {
+      if (++ticks === 3) {
+        clearInterval(interval);
+        resolve();
+      }
+    }, 1);
+  });
+  return { order, ticks };
+};
+"""
+
+    assert run_source(source) == {"order": ["microtask", "timeout"], "ticks": 3}
+
+
+def test_async_work_in_one_call_overlaps_on_the_worker_thread() -> None:
+    source = """
+export default async () => {
+  const fired = [];
+  let open;
+  const gate = new Promise((resolve) => (open = resolve));
+  await Promise.all(
+    [0, 1, 2, 3].map(async (index) => {
+      await new Promise((resolve) => setTimeout(resolve, 1));
+      fired.push(index);
+      if (fired.length === 4) open();
+      await gate;
+    }),
+  );
+  return fired.length;
+};
+"""
+
+    assert run_source(source) == 4
+
+
+def test_runtime_close_cancels_pending_timers() -> None:
+    source = """
+export default () => {
+  setInterval(() => {}, 1);
+  setTimeout(() => {}, 60_000);
+  return "scheduled";
+};
+"""
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+        assert run() == "scheduled"
+
+        started = time.monotonic()
+        runtime.close()
+
+    assert time.monotonic() - started < 5
+
+
+def test_captured_console_output_is_drained_in_order() -> None:
+    source = """
+export default function run() {
+  console.log("a", 1, {});
+  console.error("boom");
+  console.info("done");
+  return "ok";
+}
+"""
+
+    with Runtime(options=RuntimeOptions(capture_console=True)) as runtime:
+        run = runtime(Script(source))
+        assert run() == "ok"
+
+        assert run.drain_logs() == [
+            {"level": "log", "message": "a 1 {}"},
+            {"level": "error", "message": "boom"},
+            {"level": "info", "message": "done"},
+        ]
+        assert run.drain_logs() == []
+
+
+def test_console_output_is_not_captured_by_default() -> None:
+    with Runtime() as runtime:
+        run = runtime(Script("export default () => { console.log('visible'); return 1; };"))
+        assert run() == 1
+
+        assert run.drain_logs() == []
+
+
+async def test_async_runner_drains_captured_console_output() -> None:
+    async with Runtime(options=RuntimeOptions(capture_console=True)) as runtime:
+        run = runtime(Script("export default async () => { console.warn('careful'); };"))
+        assert await run() is None
+
+        assert await run.drain_logs() == [{"level": "warn", "message": "careful"}]
+
+
+def test_request_ids_tag_console_messages_and_javascript_errors() -> None:
+    source = """
+export default (fail) => {
+  console.log("handling");
+  if (fail) {
+    throw new Error("render failed");
+  }
+};
+"""
+
+    with Runtime(options=RuntimeOptions(capture_console=True)) as runtime:
+        run = runtime(Script(source))
+        tagged = run.with_request_id("req-1")
+        assert tagged.request_id == "req-1"
+        assert run.request_id is None
+
+        tagged(fail=False)
+        run(fail=False)
+        with pytest.raises(_core.BelgieJavaScriptError, match="render failed") as raised:
+            run.with_request_id("req-2")(fail=True)
+
+        assert raised.value.request_id == "req-2"
+        assert run.drain_logs() == [
+            {"level": "log", "message": "handling", "request_id": "req-1"},
+            {"level": "log", "message": "handling"},
+            {"level": "log", "message": "handling", "request_id": "req-2"},
+        ]
+        with pytest.raises(_core.BelgieJavaScriptError) as untagged:
+            run(fail=True)
+        assert untagged.value.request_id is None
+
+
+def test_unhandled_rejections_are_collected_but_handled_ones_are_not() -> None:
+    source = """
+export default async (mode) => {
+  if (mode === "unhandled") {
+    Promise.reject(new Error("forgotten"));
+  } else {
+    const promise = Promise.reject(new Error("handled"));
+    await Promise.resolve();
+    promise.catch(() => {});
+  }
+  await new Promise((resolve) => setTimeout(resolve, 10));
+  return mode;
+};
+"""
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+        assert run("handled") == "handled"
+        assert run.drain_unhandled_rejections() == []
+
+        assert run("unhandled") == "unhandled"
+        rejections = run.drain_unhandled_rejections()
+
+        assert len(rejections) == 1
+        assert "forgotten" in rejections[0]
+        assert run.drain_unhandled_rejections() == []
+
+
+async def test_async_runner_drains_unhandled_rejections() -> None:
+    source = """
+export default async () => {
+  Promise.reject("not an error");
+  await new Promise((resolve) => setTimeout(resolve, 10));
+};
+"""
+
+    async with Runtime() as runtime:
+        run = runtime(Script(source))
+        assert await run() is None
+
+        rejections = await run.drain_unhandled_rejections()
+
+        assert len(rejections) == 1
+        assert "not an error" in rejections[0]
+
+
+def test_timed_calls_report_compile_run_and_event_loop_phases() -> None:
+    source = """
+export default async (delay) => {
+  await new Promise((resolve) => setTimeout(resolve, delay));
+  return delay;
+};
+"""
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+        first, first_timings = run.timed(0)
+        result, timings = run.timed(30)
+
+    assert (first, result) == (0, 30)
+    assert set(timings) == {"compile_ms", "run_ms", "event_loop_ms"}
+    assert first_timings["compile_ms"] > 0
+    assert timings["compile_ms"] == 0
+    assert timings["event_loop_ms"] >= 25
+    assert all(value >= 0 for value in timings.values())
+
+
+async def test_async_runner_timed_calls_return_result_and_timings() -> None:
+    async with Runtime() as runtime:
+        run = runtime(Script("export default (value) => value * 2;"))
+
+        result, timings = await run.timed(21)
+
+    assert result == 42
+    assert timings["run_ms"] >= 0
+
+
+def test_diagnose_returns_warnings_alongside_the_rendered_html() -> None:
+    source = """
+export default (title) => {
+  host.warn({ code: "deprecated-prop", message: "title is deprecated" });
+  host.warn({ code: "missing-alt", message: "img has no alt text" });
+  return `<h1>${title}</h1><img src="/logo.png">`;
+};
+"""
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+        html, diagnostics = run.diagnose("Hello")
+        plain = run("Again")
+        _, later = run.diagnose("Later")
+
+    assert html == '<h1>Hello</h1><img src="/logo.png">'
+    assert diagnostics == [
+        {"code": "deprecated-prop", "message": "title is deprecated"},
+        {"code": "missing-alt", "message": "img has no alt text"},
+    ]
+    assert plain == '<h1>Again</h1><img src="/logo.png">'
+    assert len(later) == 2
+
+
+async def test_async_runner_diagnose_returns_result_and_diagnostics() -> None:
+    async with Runtime() as runtime:
+        run = runtime(Script('export default (value) => { host.warn("slow path"); return value * 2; };'))
+
+        result, diagnostics = await run.diagnose(21)
+
+    assert result == 42
+    assert diagnostics == ["slow path"]
+
+
+def test_msgpack_calls_return_encoded_results() -> None:
+    source = """
+export default (count) => ({
+  items: [count, "two", true],
+  nested: { bytes: new Uint8Array([1, 2]), missing: null },
+});
+"""
+    expected = b"\x82\xa5items\x93\x01\xa3two\xc3\xa6nested\x82\xa5bytes\xc4\x02\x01\x02\xa7missing\xc0"
+
+    with Runtime() as runtime:
+        assert runtime(Script(source)).msgpack(1) == expected
+
+
+async def test_async_msgpack_calls_return_bytes() -> None:
+    async with Runtime() as runtime:
+        run = runtime(Script("export default (value) => [value, -value];"))
+
+        assert await run.msgpack(3) == b"\x92\x03\xfd"
+
+
+def test_batches_run_calls_in_order_and_isolate_failures() -> None:
+    source = """
+const seen = [];
+export default (value) => {
+  if (value === "fail") {
+    throw new Error(`cannot handle ${value}`);
+  }
+  seen.push(value);
+  return [...seen];
+};
+"""
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+
+        first, second, third = run.batch([("a",), ("fail",), ("b",)])
+
+    assert first == ["a"]
+    assert isinstance(second, _core.BelgieJavaScriptError)
+    assert "cannot handle fail" in str(second)
+    assert third == ["a", "b"]
+
+
+async def test_async_batches_return_per_call_results() -> None:
+    async with Runtime() as runtime:
+        run = runtime(Script("export default async (left, right) => left + right;"))
+
+        assert await run.batch([(1, 2), (3, 4)]) == [3, 7]
+
+
+def test_warmup_repeats_calls_on_the_runner() -> None:
+    source = """
+let calls = 0;
+export default (size) => {
+  calls += 1;
+  let total = 0;
+  for (let index = 0; index < size; index += 1) {
+    total += Math.sqrt(index) * (index % 7);
+  }
+  return { calls, total };
+};
+"""
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+
+        assert run.warmup(1_000, iterations=25) is None
+        result = run(1_000)
+
+        with pytest.raises(ValueError, match="at least 1"):
+            run.warmup(1, iterations=0)
+
+    assert result["calls"] == 26
+
+
+async def test_async_warmup_stops_at_the_first_failing_call() -> None:
+    source = """
+let calls = 0;
+export default () => {
+  calls += 1;
+  if (calls === 3) {
+    throw new Error("third call failed");
+  }
+  return calls;
+};
+"""
+
+    async with Runtime() as runtime:
+        run = runtime(Script(source))
+
+        with pytest.raises(_core.BelgieJavaScriptError, match="third call failed"):
+            await run.warmup(iterations=10)
+
+        assert await run() == 4
+
+
+def test_batches_require_tuples_of_arguments() -> None:
+    with Runtime() as runtime:
+        run = runtime(Script("export default (value) => value;"))
+
+        with pytest.raises(TypeError, match="Batch call 0"):
+            run.batch(cast("Any", [1]))
+
+
+def test_streams_yield_emitted_chunks_before_the_call_returns() -> None:
+    source = """
+export default async (count) => {
+  for (let index = 0; index < count; index += 1) {
+    host.emit({ index });
+    await new Promise((resolve) => setTimeout(resolve, 1));
+  }
+  return "done";
+};
+"""
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+
+        assert list(run.stream(3)) == [{"index": 0}, {"index": 1}, {"index": 2}]
+        with pytest.raises(_core.BelgieJavaScriptError, match="streaming"):
+            run(1)
+
+
+def test_stream_errors_raise_after_emitted_chunks() -> None:
+    source = """
+export default () => {
+  host.emit("partial");
+  throw new Error("stream failed");
+};
+"""
+
+    with Runtime() as runtime:
+        stream = runtime(Script(source)).stream()
+
+        assert next(stream) == "partial"
+        with pytest.raises(_core.BelgieJavaScriptError, match="stream failed"):
+            next(stream)
+
+
+def test_stream_timeouts_deliver_the_chunks_emitted_before_the_deadline() -> None:
+    source = """
+export default async () => {
+  host.emit("<head/>");
+  host.emit("<header/>");
+  await new Promise(() => {});
+};
+"""
+    chunks: list[object] = []
+
+    with Runtime(options=RuntimeOptions(timeout=0.2)) as runtime:
+        run = runtime(Script(source))
+        with pytest.raises(_core.BelgieTimeoutError):
+            chunks.extend(run.stream())
+
+        assert chunks == ["<head/>", "<header/>"]
+        assert run.is_alive()
+
+
+def test_closing_a_stream_early_stops_the_call_and_keeps_the_runner() -> None:
+    source = """
+let emitted = 0;
+export default async (mode) => {
+  if (mode === "count") {
+    return emitted;
+  }
+  while (true) {
+    host.emit(emitted++);
+    await new Promise((resolve) => setTimeout(resolve, 5));
+  }
+};
+"""
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+        stream = run.stream()
+        assert next(stream) == 0
+        stream.close()
+
+        assert run("count") >= 1
+        assert run.is_alive()
+
+
+async def test_async_streams_yield_emitted_chunks() -> None:
+    source = """
+export default async (prefix) => {
+  for (const suffix of ["a", "b"]) {
+    await Promise.resolve();
+    host.emit(prefix + suffix);
+  }
+};
+"""
+
+    async with Runtime() as runtime:
+        run = runtime(Script(source))
+
+        chunks = [chunk async for chunk in run.stream("x-")]
+
+    assert chunks == ["x-a", "x-b"]
+
+
+def test_runtime_iterate_streams_async_generator_values() -> None:
+    source = """
+(async function* () {
+  yield { page: 1 };
+  await new Promise((resolve) => setTimeout(resolve, 1));
+  yield { page: 2 };
+})()
+"""
+
+    with Runtime() as runtime:
+        assert list(runtime.iterate(source)) == [{"page": 1}, {"page": 2}]
+        assert list(runtime.iterate("[1, 2, 3].values()")) == [1, 2, 3]
+
+        stream = runtime.iterate('(async function* () { yield "first"; throw new Error("page failed"); })()')
+        assert next(stream) == "first"
+        with pytest.raises(_core.BelgieJavaScriptError, match="page failed"):
+            next(stream)
+
+        with pytest.raises(_core.BelgieJavaScriptError, match="async iterator"):
+            list(runtime.iterate("42"))
+
+
+def test_runtime_iterate_streams_from_the_runner_eval_reuses() -> None:
+    with Runtime() as runtime:
+        assert list(runtime.iterate("(globalThis.streamed = 'yes', [1, 2].values())")) == [1, 2]
+        assert runtime.eval("streamed") == "yes"
+
+        stream = runtime.iterate("[3, 4].values()")
+        assert next(stream) == 3
+        assert runtime.eval("typeof globalThis.streamed") == "undefined"
+        assert list(stream) == [4]
+
+
+def worker_threads() -> int:
+    tasks = Path("/proc/self/task").iterdir()
+    return sum(1 for task in tasks if re.fullmatch(r"belgie-\d+", (task / "comm").read_text().strip()))
+
+
+def wait_for_worker_threads(count: int) -> None:
+    deadline = time.monotonic() + 10
+    while worker_threads() != count and time.monotonic() < deadline:
+        time.sleep(0.05)
+    assert worker_threads() == count
+
+
+@pytest.mark.skipif(not Path("/proc/self/task").is_dir(), reason="requires /proc thread listing")
+def test_unconsumed_streams_hand_back_their_runners() -> None:
+    with Runtime() as runtime:
+        assert runtime.eval("1") == 1
+        baseline = worker_threads()
+
+        stream = runtime.iterate("[1, 2].values()")
+        assert next(stream) == 1
+        del stream
+        gc.collect()
+        assert runtime.eval("2") == 2
+        assert worker_threads() == baseline
+
+        stream = runtime.iterate("(import.meta, [1, 2].values())")
+        assert next(stream) == 1
+        del stream
+        gc.collect()
+        wait_for_worker_threads(baseline)
+
+
+async def test_async_runtime_iterate_pulls_values_until_done() -> None:
+    async with Runtime() as runtime:
+        stream = runtime.iterate("(async function* () { for (let n = 0; n < 3; n += 1) yield n * 2; })()")
+
+        assert [value async for value in stream] == [0, 2, 4]
+        with pytest.raises(StopAsyncIteration):
+            await stream.__anext__()
+
+
+def test_host_emit_cannot_be_registered() -> None:
+    with Runtime() as runtime, pytest.raises(ValueError, match="reserved"):
+        runtime.register("emit", lambda chunk: chunk)
+
+
+def test_heap_stats_grow_after_allocation_without_disturbing_state() -> None:
+    source = """
+const retained = [];
+export default (count) => {
+  for (let index = 0; index < count; index += 1) {
+    retained.push({ index, label: `item-${index}` });
+  }
+  return retained.length;
+};
+"""
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+        assert run(0) == 0
+        before = run.heap_stats()
+
+        assert run(200_000) == 200_000
+        after = run.heap_stats()
+
+        assert set(after) == {"used_heap_size", "total_heap_size", "heap_size_limit", "external_memory"}
+        assert after["used_heap_size"] > before["used_heap_size"]
+        assert after["heap_size_limit"] >= after["total_heap_size"] > 0
+        assert run(0) == 200_000
+
+
+def test_runner_globals_list_what_setup_and_scripts_define() -> None:
+    source = """
+globalThis.foo = 1;
+export default () => {
+  globalThis.later = "set by a call";
+};
+"""
+
+    with Runtime(options=RuntimeOptions(setup="globalThis.helpers = { format: String };")) as runtime:
+        run = runtime(Script(source))
+
+        assert run.globals() == {"foo": "number", "helpers": "object"}
+        run()
+        assert run.globals()["later"] == "string"
+
+        builtins = run.globals(include_builtins=True)
+        assert builtins["foo"] == "number"
+        assert builtins["JSON"] == "object"
+        assert builtins["host"] == "object"
+
+
+async def test_async_runner_reports_heap_stats() -> None:
+    async with Runtime() as runtime:
+        run = runtime(Script("export default () => 1;"))
+        assert await run() == 1
+
+        stats = await run.heap_stats()
+
+    assert stats["used_heap_size"] > 0
+
+
+def test_runner_records_a_cpu_profile_devtools_can_load() -> None:
+    source = """
+export default (count) => {
+  let total = 0;
+  for (let index = 0; index < count; index += 1) {
+    total += Math.sqrt(index);
+  }
+  return total > 0;
+};
+"""
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+        run.start_profiling()
+        assert run(2_000_000) is True
+        profile = json.loads(run.stop_profiling())
+
+        assert {"nodes", "startTime", "endTime", "samples", "timeDeltas"} <= set(profile)
+        assert profile["endTime"] >= profile["startTime"]
+        assert any(node["callFrame"]["functionName"] == "(root)" for node in profile["nodes"])
+        with pytest.raises(_core.BelgieRuntimeError, match="not running"):
+            run.stop_profiling()
+
+
+async def test_async_runner_takes_a_heap_snapshot() -> None:
+    async with Runtime() as runtime:
+        run = runtime(Script("globalThis.retained = { marker: 'belgie-heap-marker' }; export default () => 1;"))
+        assert await run() == 1
+
+        snapshot = json.loads(await run.heap_snapshot())
+
+    assert {"snapshot", "nodes", "edges", "strings"} <= set(snapshot)
+    assert "belgie-heap-marker" in snapshot["strings"]
+
+
+def test_runner_state_restores_into_a_new_runtime() -> None:
+    source = """
+globalThis.visits ??= 0;
+export default (label) => {
+  globalThis.visits += 1;
+  globalThis.seen = new Set([...(globalThis.seen ?? []), label]);
+  globalThis.format = (value) => String(value);
+  return globalThis.visits;
+};
+"""
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+        assert run("a") == 1
+        assert run("b") == 2
+        state = run.capture_state()
+
+    assert isinstance(state, bytes)
+    with Runtime(restore=state) as runtime:
+        run = runtime(Script(source))
+        assert run.globals() == {"visits": "number", "seen": "object"}
+        assert run("c") == 3
+        assert runtime(Script("export default () => [...globalThis.seen];"))() == ["a", "b"]
+
+    with pytest.raises(ValueError, match="capture_state"):
+        Runtime(restore=b"not captured state")
+
+
+def test_runtime_starts_runners_from_a_created_snapshot() -> None:
+    snapshot = Runtime.create_snapshot("globalThis.framework = { render: (name) => `<p>${name}</p>` };")
+
+    assert isinstance(snapshot, bytes)
+    with Runtime(snapshot=snapshot) as runtime:
+        assert runtime.eval("framework.render('snap')") == "<p>snap</p>"
+        run = runtime(Script("export default () => [framework.render('run'), typeof TextEncoder];"))
+        assert run() == ["<p>run</p>", "function"]
+
+    with pytest.raises(_core.BelgieJavaScriptError, match="broken setup"):
+        Runtime.create_snapshot("throw new Error('broken setup');")
+    with pytest.raises(ValueError, match="create_snapshot"):
+        Runtime(snapshot=b"not a snapshot")
+    with pytest.raises(ValueError, match="checksum"):
+        Runtime(snapshot=snapshot[:-1] + bytes([snapshot[-1] ^ 1]))
+    with pytest.raises(ValueError, match="env"):
+        Runtime(env=Environment(), snapshot=snapshot)
+
+
+async def test_async_runner_capture_names_the_global_it_cannot_convert() -> None:
+    async with Runtime() as runtime:
+        run = runtime(Script("globalThis.handlers = { onClick() {} }; export default () => 1;"))
+
+        with pytest.raises(ValueError, match="Could not capture global handlers"):
+            await run.capture_state()
+
+
+def test_runner_reset_clears_globals_and_module_state() -> None:
+    source = """
+let count = 0;
+export default () => {
+  const before = typeof globalThis.x;
+  globalThis.x = 1;
+  return { before, count: ++count };
+};
+"""
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+        assert run() == {"before": "undefined", "count": 1}
+        assert run() == {"before": "number", "count": 2}
+
+        run.reset()
+
+        assert run() == {"before": "undefined", "count": 1}
+
+
+async def test_async_runner_reset_reloads_the_module() -> None:
+    async with Runtime() as runtime:
+        run = runtime(Script("let count = 0; export default () => ++count;"))
+        assert await run() == 1
+
+        await run.reset()
+
+        assert await run() == 1
+
+
+@pytest.mark.parametrize(
+    ("expression", "expected"),
+    [
+        ("{a: 1}", {"a": 1}),
+        ("  {a: 1, b: [2]};  ", {"a": 1, "b": [2]}),
+        ("{}", {}),
+        ("({html: '<div/>', head: '<title/>'})", {"html": "<div/>", "head": "<title/>"}),
+        ("1 + 2 // trailing comment", 3),
+        ("Promise.resolve('later')", "later"),
+        ("[1, 2].map((value) => value * 2)", [2, 4]),
+    ],
+)
+def test_runtime_eval_returns_expression_values(expression: str, expected: object) -> None:
+    with Runtime() as runtime:
+        assert runtime.eval(expression) == expected
+
+
+def test_runtime_eval_rejects_blocks_that_are_not_expressions() -> None:
+    with Runtime() as runtime, pytest.raises(_core.BelgieSyntaxError):
+        runtime.eval("{ const a = 1; a }")
+
+
+def test_runtime_eval_binds_this_to_the_given_receiver() -> None:
+    with Runtime() as runtime:
+        assert runtime.eval("this.x * 2", this={"x": 21}) == 42
+        assert runtime.eval("Array.isArray(this) && this.length", this=[1, 2]) == 2
+        assert runtime.eval("typeof this") == "undefined"
+
+
+def test_scripts_run_in_strict_mode_so_assignments_do_not_leak_globals() -> None:
+    setup = "leaked = 'setup is a classic script';"
+
+    with Runtime(options=RuntimeOptions(setup=setup)) as runtime:
+        run = runtime(Script("export default () => { x = 1; };"))
+        with pytest.raises(_core.BelgieJavaScriptError, match="ReferenceError: x is not defined"):
+            run()
+        assert "x" not in run.globals()
+        assert run.globals()["leaked"] == "string"
+        with pytest.raises(_core.BelgieJavaScriptError, match="ReferenceError"):
+            runtime.eval("(() => { y = 2; })()")
+
+
+def test_runtime_eval_reuses_one_runner_until_a_host_function_is_registered() -> None:
+    setup = "globalThis.setups = (globalThis.setups ?? 0) + 1;"
+
+    with Runtime(options=RuntimeOptions(setup=setup)) as runtime:
+        assert runtime.eval("(globalThis.seen = 'first', setups)") == 1
+        assert runtime.eval("[setups, seen, typeof this]") == [1, "first", "undefined"]
+        assert runtime.eval("setups + (this.x as number)", this={"x": 1}) == 2
+
+        runtime.register("double", lambda value: value * 2)
+
+        assert runtime.eval("[setups, typeof globalThis.seen, host.double(2)]") == [1, "undefined", 4]
+
+
+@pytest.mark.skipif(not Path("/proc/self/task").is_dir(), reason="requires /proc thread listing")
+async def test_cancelled_async_eval_hands_back_its_runner() -> None:
+    async with Runtime() as runtime:
+        assert await runtime.eval("1") == 1
+        baseline = worker_threads()
+
+        for expression in (
+            "new Promise((resolve) => setTimeout(resolve, 200))",
+            "(import.meta, new Promise((resolve) => setTimeout(resolve, 60_000)))",
+        ):
+            task = asyncio.create_task(runtime.eval(expression))
+            await asyncio.sleep(0)
+            task.cancel()
+            with pytest.raises(asyncio.CancelledError):
+                await task
+            wait_for_worker_threads(baseline)
+
+        runtime.eval("(import.meta, 1)").close()
+        gc.collect()
+        wait_for_worker_threads(baseline)
+
+        assert await runtime.eval("2") == 2
+        assert worker_threads() == baseline
+
+
+def test_runtime_eval_isolate_runs_in_a_fresh_context() -> None:
+    with Runtime(options=RuntimeOptions(setup="globalThis.configured = true;")) as runtime:
+        assert runtime.eval("(globalThis.shared = 1, Array.prototype.extra = 'leaked', 0)") == 0
+
+        assert runtime.eval(
+            "[typeof configured, typeof shared, typeof [].extra, typeof host, (globalThis.own = 2)]",
+            isolate=True,
+        ) == ["undefined", "undefined", "undefined", "undefined", 2]
+        assert runtime.eval("this.x + 1", this={"x": 41}, isolate=True) == 42
+        with pytest.raises(_core.BelgieJavaScriptError, match="inside"):
+            runtime.eval("(() => { throw new RangeError('inside'); })()", isolate=True)
+
+        assert runtime.eval("[configured, shared, typeof globalThis.own]") == [True, 1, "undefined"]
+
+
+async def test_async_runtime_eval_isolate_runs_in_a_fresh_context() -> None:
+    async with Runtime() as runtime:
+        await runtime.eval("globalThis.user = 'alice'")
+
+        assert await runtime.eval("typeof user", isolate=True) == "undefined"
+        assert await runtime.eval("Promise.resolve(user)") == "alice"
+
+
+async def test_async_runtime_eval_reads_shared_state() -> None:
+    async with Runtime() as runtime:
+        runtime.state["name"] = "belgie"
+
+        assert await runtime.eval("({greeting: `hi ${state.name}`})") == {"greeting": "hi belgie"}
+
+
+def test_runtime_define_returns_a_reusable_function_runner() -> None:
+    with Runtime() as runtime:
+        render = runtime.define(
+            """(() => {
+  let renders = 0;
+  return ({ user }) => `<p>${user}</p><!-- ${++renders} -->`;
+})()""",
+        )
+
+        assert isinstance(render, SyncRunner)
+        assert render({"user": "alice"}) == "<p>alice</p><!-- 1 -->"
+        assert render({"user": "bob"}) == "<p>bob</p><!-- 2 -->"
+
+
+async def test_async_runtime_define_returns_an_async_runner() -> None:
+    async with Runtime() as runtime:
+        add = runtime.define("async (left, right) => left + right;")
+
+        assert isinstance(add, AsyncRunner)
+        assert [await add(1, 2), await add(3, 4)] == [3, 7]
+        with pytest.raises(_core.BelgieModuleError):
+            await runtime.define("42")()
+
+
+def test_deterministic_runtimes_with_the_same_seed_agree() -> None:
+    source = """
+export default () => ({
+  random: Array.from({ length: 5 }, () => Math.random()),
+  now: Date.now(),
+  date: new Date().toISOString(),
+  explicit: new Date(86_400_000).getTime(),
+  isDate: new Date() instanceof Date,
+});
+"""
+
+    def sample(seed: int) -> dict[str, Any]:
+        options = RuntimeOptions(deterministic=True, seed=seed, fixed_time_ms=1_700_000_000_000)
+        with Runtime(options=options) as runtime:
+            return runtime(Script(source))()
+
+    first = sample(42)
+
+    assert first == sample(42)
+    assert first["random"] != sample(7)["random"]
+    assert all(0 <= value < 1 for value in first["random"])
+    assert first["now"] == 1_700_000_000_000
+    assert first["date"] == "2023-11-14T22:13:20.000Z"
+    assert first["explicit"] == 86_400_000
+    assert first["isDate"] is True
+
+
+def test_deterministic_clock_and_generator_cannot_be_replaced() -> None:
+    source = """
+const attempts = [
+  () => { Math.random = () => 0; },
+  () => { Date = class {}; },
+  () => { Date.now = () => 0; },
+  () => Object.defineProperty(Math, "random", { value: () => 0 }),
+  () => delete globalThis.Date,
+];
+export default () => ({
+  errors: attempts.map((attempt) => { try { attempt(); return null; } catch (error) { return error.name; } }),
+  now: Date.now(),
+  constructed: new Date().getTime(),
+  random: Math.random() !== 0,
+});
+"""
+    options = RuntimeOptions(deterministic=True, fixed_time_ms=1_700_000_000_000)
+
+    with Runtime(options=options) as runtime:
+        assert runtime(Script(source))() == {
+            "errors": ["TypeError"] * 5,
+            "now": 1_700_000_000_000,
+            "constructed": 1_700_000_000_000,
+            "random": True,
+        }
+
+
+def test_runtime_setup_runs_before_each_runner_loads_its_script() -> None:
+    setup = "globalThis.greet = (name) => `hello ${name}`; globalThis.setups = (globalThis.setups ?? 0) + 1;"
+
+    with Runtime(options=RuntimeOptions(setup=setup)) as runtime:
+        run = runtime(Script("export default (name) => [greet(name), setups];"))
+        assert run("ada") == ["hello ada", 1]
+
+        run.reset()
+
+        assert run("grace") == ["hello grace", 1]
+
+
+def test_runtime_setup_errors_surface_on_first_use() -> None:
+    with Runtime(options=RuntimeOptions(setup="throw new Error('setup failed');")) as runtime:
+        run = runtime(Script("export default () => 1;"))
+
+        with pytest.raises(_core.BelgieJavaScriptError, match="setup failed"):
+            run.load()
+        with pytest.raises(_core.BelgieJavaScriptError, match="setup failed"):
+            run()
+        assert not run.is_alive()
+
+
+def test_ready_surfaces_setup_errors_before_the_module_loads() -> None:
+    with Runtime(options=RuntimeOptions(setup="throw new Error('setup failed');")) as runtime:
+        with pytest.raises(_core.BelgieJavaScriptError, match="setup failed"):
+            runtime.ready()
+        run = runtime(Script("export default () => missing(;"))
+
+        with pytest.raises(_core.BelgieJavaScriptError, match="setup failed"):
+            run.ready()
+        assert not run.is_alive()
+
+    with Runtime(options=RuntimeOptions(setup="globalThis.ready = true;")) as runtime:
+        assert runtime.ready() is None
+        run = runtime(Script("export default () => missing(;"))
+
+        assert run.ready() is None
+        assert run.is_alive()
+
+
+def test_runtime_ready_runs_setup_on_the_runner_eval_reuses() -> None:
+    setup = "globalThis.setups = (globalThis.setups ?? 0) + 1;"
+
+    with Runtime(options=RuntimeOptions(setup=setup)) as runtime:
+        assert runtime.ready() is None
+        assert runtime.ready() is None
+        assert runtime.eval("setups") == 1
+
+
+async def test_async_ready_raises_setup_errors() -> None:
+    async with Runtime(options=RuntimeOptions(setup="throw new Error('setup failed');")) as runtime:
+        with pytest.raises(_core.BelgieJavaScriptError, match="setup failed"):
+            await runtime.ready()
+        run = runtime(Script("export default () => 1;"))
+
+        with pytest.raises(_core.BelgieJavaScriptError, match="setup failed"):
+            await run.ready()
+        assert not run.is_alive()
+
+
+@pytest.mark.skipif(not Path("/proc/self/task").is_dir(), reason="requires /proc thread listing")
+async def test_dropped_async_ready_hands_back_the_scratch_runner() -> None:
+    baseline = worker_threads()
+    setup = "globalThis.setups = (globalThis.setups ?? 0) + 1;"
+
+    async with Runtime(options=RuntimeOptions(setup=setup)) as runtime:
+        runtime.ready().close()
+        gc.collect()
+        task = asyncio.create_task(runtime.ready())
+        await asyncio.sleep(0)
+        task.cancel()
+        await asyncio.gather(task, return_exceptions=True)
+
+        assert await runtime.eval("setups") == 1
+        assert worker_threads() == baseline + 1
+
+
+def test_runner_evaluate_returns_the_default_export() -> None:
+    with Runtime() as runtime:
+        run = runtime(Script('export default {rendered: "<div/>"};'))
+
+        assert run.evaluate() == {"rendered": "<div/>"}
+
+
+def test_runner_evaluate_returns_none_without_a_default_export() -> None:
+    with Runtime() as runtime:
+        assert runtime(Script("export const answer = 42;")).evaluate() is None
+
+
+def test_runner_evaluate_shares_module_state_with_calls() -> None:
+    source = (
+        "globalThis.loads = (globalThis.loads ?? 0) + 1;"
+        " export const run = () => globalThis.loads;"
+        " export default { loads: globalThis.loads };"
+    )
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+
+        assert run.evaluate() == {"loads": 1}
+        assert run() == 1
+        assert run.evaluate() == {"loads": 1}
+
+
+async def test_async_runner_evaluates_the_default_export() -> None:
+    async with Runtime() as runtime:
+        run = runtime(Script("export default [1, 2, 3];"))
+
+        assert await run.evaluate() == [1, 2, 3]
+
+
 def test_multiple_bindings_are_independent_and_preserve_state() -> None:
     source = "let count = 0; export default () => ++count;"
 
     with Runtime() as runtime:
-        first = runtime(Script(source))
-        second = runtime(Script(source))
+        first = runtime(Script(source))
+        second = runtime(Script(source))
+
+        assert first() == 1
+        assert first() == 2
+        assert second() == 1
+
+
+@pytest.mark.skipif(not Path("/proc/self/task").is_dir(), reason="requires /proc thread listing")
+def test_runtimes_created_in_a_loop_do_not_strand_worker_threads() -> None:
+    def thread_count() -> int:
+        return len(list(Path("/proc/self/task").iterdir()))
+
+    def run_once() -> None:
+        runtime = Runtime()
+        with runtime as run:
+            assert run(Script("export default () => 1;"))() == 1
+        del runtime
+        gc.collect()
+
+    run_once()
+    baseline = thread_count()
+    for _ in range(10):
+        run_once()
+
+    assert thread_count() <= baseline + 2
+
+
+@pytest.mark.skipif(not Path("/proc/self/task").is_dir(), reason="requires /proc thread listing")
+def test_timed_calls_share_one_watchdog_thread_per_runner() -> None:
+    def watchdog_threads() -> list[str]:
+        tasks = Path("/proc/self/task").iterdir()
+        return [task.name for task in tasks if (task / "comm").read_text().strip() == "belgie-watchdog"]
+
+    with Runtime(options=RuntimeOptions(timeout=5)) as runtime:
+        runtime.register("watchdog_threads", watchdog_threads)
+        run = runtime(Script("export default () => host.watchdog_threads();"))
+        seen = [run() for _ in range(5)]
+        seen.extend(run.batch([(), ()]))
+        run.warmup(iterations=3)
+        seen.append(run())
+
+    assert len(seen[0]) == 1
+    assert all(threads == seen[0] for threads in seen)
+
+
+@pytest.mark.skipif(not Path("/proc/self/task").is_dir(), reason="requires /proc thread listing")
+def test_cpu_budget_calls_reuse_the_watchdog_thread() -> None:
+    def thread_count() -> int:
+        return len(list(Path("/proc/self/task").iterdir()))
+
+    with Runtime(options=RuntimeOptions(timeout=5, cpu_budget=5)) as runtime:
+        runtime.register("thread_count", thread_count)
+        run = runtime(Script("export default () => host.thread_count();"))
+        seen = [run() for _ in range(5)]
+
+    assert all(count == seen[0] for count in seen)
+
+
+def test_runners_accept_concurrent_calls_from_multiple_threads() -> None:
+    source = "let count = 0; export default (label) => ({ label, count: ++count });"
+
+    with Runtime() as runtime:
+        run = runtime(Script(source))
+        barrier = threading.Barrier(2)
+        results: list[dict[str, Any]] = []
 
-        assert first() == 1
-        assert first() == 2
-        assert second() == 1
+        def call(label: str) -> None:
+            barrier.wait()
+            results.extend(run(f"{label}-{index}") for index in range(5))
+
+        threads = [threading.Thread(target=call, args=(label,)) for label in ("a", "b")]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+
+    assert sorted(result["count"] for result in results) == list(range(1, 11))
+    for label in ("a", "b"):
+        labelled = [result for result in results if result["label"].startswith(label)]
+        assert [result["label"] for result in labelled] == [f"{label}-{index}" for index in range(5)]
+        assert [result["count"] for result in labelled] == sorted(result["count"] for result in labelled)
+
+
+def test_sync_calls_wait_for_queue_space() -> None:
+    release = threading.Event()
+    source = "export default (label) => host.hold(label);"
+
+    with Runtime(options=RuntimeOptions(queue_size=1)) as runtime:
+        runtime.register("hold", lambda label: release.wait(5) and label)
+        run = runtime(Script(source))
+        results: list[str] = []
+        threads = [threading.Thread(target=lambda label=label: results.append(run(label))) for label in "abcd"]
+        for thread in threads:
+            thread.start()
+        time.sleep(0.1)
+
+        assert results == []
+        release.set()
+        for thread in threads:
+            thread.join()
+
+    assert sorted(results) == ["a", "b", "c", "d"]
+
+
+async def test_async_calls_wait_for_queue_space() -> None:
+    source = """
+    export default async (value) => {
+      await new Promise((resolve) => setTimeout(resolve, 1));
+      return value;
+    };
+    """
+
+    async with Runtime(options=RuntimeOptions(queue_size=1)) as runtime:
+        run = runtime(Script(source))
+
+        assert await asyncio.gather(*(run(index) for index in range(20))) == list(range(20))
 
 
 def test_runtime_from_folder_resolves_inline_relative_imports(tmp_path) -> None:
@@ -128,6 +1267,26 @@ def test_runtime_from_folder_resolves_inline_relative_imports(tmp_path) -> None:
     assert sorted(path.name for path in tmp_path.iterdir()) == ["value.ts"]
 
 
+def test_module_root_loads_files_inside_it_and_rejects_escapes(tmp_path) -> None:
+    root = tmp_path / "app"
+    (root / "components").mkdir(parents=True)
+    (root / "components" / "App.tsx").write_text(
+        "export default (name: string): string => `<p>${name}</p>`;\n",
+        encoding="utf-8",
+    )
+    (tmp_path / "secret.ts").write_text("export default 'secret';\n", encoding="utf-8")
+    options = RuntimeOptions(module_root=root)
+
+    assert f'module_root=Some("{root.resolve()}")' in repr(options)
+    with Runtime.from_folder(root, options=options) as runtime:
+        app = runtime(Script('import App from "./components/App.tsx"; export default App;'))
+        escape = runtime(Script('import secret from "../secret.ts"; export default () => secret;'))
+
+        assert app("belgie") == "<p>belgie</p>"
+        with pytest.raises(_core.BelgieError, match="outside the module root"):
+            escape()
+
+
 async def test_async_runtime_from_folder(tmp_path) -> None:
     async with Runtime.from_folder(tmp_path) as runtime:
         assert isinstance(runtime, AsyncRuntime)
@@ -158,6 +1317,107 @@ def test_command_requires_package_dependencies_not_just_worker_options(tmp_path)
             command("--version")
 
 
+def test_scripts_use_web_crypto_random_values() -> None:
+    source = """
+    export default () => {
+      const array = new Uint8Array(32);
+      return { uuid: crypto.randomUUID(), filled: crypto.getRandomValues(array) === array, array };
+    };
+    """
+
+    result = run_source(source)
+
+    assert re.fullmatch(r"[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}", result["uuid"])
+    assert result["filled"] is True
+    assert len(result["array"]) == 32
+    assert result["array"] != bytes(32)
+
+
+def test_seeded_runtimes_generate_deterministic_random_values(tmp_path) -> None:
+    project = tmp_path / "project"
+    project.mkdir()
+    script = Script("export default () => [crypto.randomUUID(), Math.random()];")
+
+    def generate() -> list[Any]:
+        with Environment(path=project) as env, Runtime(env=env, options=RuntimeOptions(seed=7)) as runtime:
+            return runtime(script)()
+
+    assert generate() == generate()
+
+
+def test_scripts_read_a_monotonic_performance_clock() -> None:
+    source = """
+    export default () => {
+      const first = performance.now();
+      const second = performance.now();
+      return [second >= first, typeof performance.timeOrigin, Date.now() > 0];
+    };
+    """
+
+    assert run_source(source) == [True, "number", True]
+
+
+def test_scripts_parse_urls_with_queries_and_fragments() -> None:
+    source = """
+    export default () => {
+      const url = new URL("/search?z=1&tag=a+b&tag=c#results", "https://example.com/app/");
+      url.searchParams.set("page", "2");
+      return {
+        href: url.href,
+        pathname: url.pathname,
+        hash: url.hash,
+        z: url.searchParams.get("z"),
+        tags: url.searchParams.getAll("tag"),
+        invalid: URL.canParse("not a url"),
+      };
+    };
+    """
+
+    assert run_source(source) == {
+        "href": "https://example.com/search?z=1&tag=a+b&tag=c&page=2#results",
+        "pathname": "/search",
+        "hash": "#results",
+        "z": "1",
+        "tags": ["a b", "c"],
+        "invalid": False,
+    }
+
+
+def test_scripts_instantiate_webassembly_modules_from_bytes() -> None:
+    # (module (func (export "add") (param i32 i32) (result i32) local.get 0 local.get 1 i32.add))
+    add_wasm = bytes.fromhex(
+        "0061736d0100000001070160027f7f017f030201000707010361646400000a09010700200020016a0b",
+    )
+    source = """
+    const compiled = await WebAssembly.compile(wasm);
+    const { instance } = await WebAssembly.instantiate(wasm);
+    export default (a, b) => ({
+      exports: WebAssembly.Module.exports(compiled).map((entry) => entry.name),
+      sum: instance.exports.add(a, b),
+    });
+    """
+
+    with Runtime() as runtime:
+        assert runtime(Script(source, globals={"wasm": add_wasm}))(2, 40) == {"exports": ["add"], "sum": 42}
+
+
+def test_intl_formats_with_full_icu_locale_data() -> None:
+    source = """
+export default (value) => ({
+  number: new Intl.NumberFormat("de-DE").format(value),
+  date: new Intl.DateTimeFormat("fr-FR", { dateStyle: "long", timeZone: "UTC" }).format(Date.UTC(2024, 0, 15)),
+  supported: Intl.NumberFormat.supportedLocalesOf(["de-DE", "ja-JP", "ar-EG"]),
+});
+"""
+
+    with Runtime() as runtime:
+        assert runtime(Script(source))(1234.5) == {
+            "number": "1.234,5",
+            "date": "15 janvier 2024",
+            "supported": ["de-DE", "ja-JP", "ar-EG"],
+        }
+
+
 def test_closed_runtime_rejects_new_bindings() -> None:
     with Runtime() as runtime:
         pass
@@ -166,6 +1426,267 @@ def test_closed_runtime_rejects_new_bindings() -> None:
         runtime(Script("export default () => 42;"))
 
 
+def test_sync_runtime_close_is_idempotent_and_closes_runners() -> None:
+    with Runtime() as runtime:
+        run = runtime(Script("export default () => 42;"))
+        assert run() == 42
+
+        runtime.close()
+        runtime.close()
+
+        with pytest.raises(_core.BelgieRuntimeError, match="closed"):
+            run()
+        with pytest.raises(_core.BelgieRuntimeError, match="closed"):
+            runtime(Script("export default () => 42;"))
+
+
+def test_is_alive_reports_false_after_close() -> None:
+    with Runtime() as runtime:
+        run = runtime(Script("export default () => 42;"))
+        assert runtime.is_alive()
+        assert run.is_alive()
+
+        runtime.close()
+
+        assert not runtime.is_alive()
+        assert not run.is_alive()
+
+
+def test_scripts_call_registered_host_functions() -> None:
+    users = {5: {"id": 5, "name": "Ada"}}
+
+    with Runtime() as runtime:
+        runtime.register("get_user", lambda user_id: users[user_id])
+        run = runtime(Script("export default async (id) => ({ user: await host.get_user(id) });"))
+
+        assert run(5) == {"user": {"id": 5, "name": "Ada"}}
+
+
+def test_op_metrics_count_each_host_function_call() -> None:
+    source = """
+export default (count) => {
+  let total = 0;
+  for (let index = 0; index < count; index += 1) {
+    total += host.double(index);
+  }
+  return total;
+};
+"""
+    runtime = Runtime(options=RuntimeOptions(op_metrics=True))
+
+    with runtime as session:
+        session.register("double", lambda value: value * 2)
+        assert session(Script(source))(7) == 42
+        metrics = session.op_metrics()
+
+    assert metrics["host.double"]["calls"] == 7
+    assert metrics["host.double"]["total_ms"] >= 0
+    assert runtime.op_metrics()["host.double"]["calls"] == 7
+
+
+def test_op_metrics_are_disabled_by_default() -> None:
+    with Runtime() as runtime, pytest.raises(_core.BelgieRuntimeError, match="op_metrics=True"):
+        runtime.op_metrics()
+
+
+@pytest.mark.skipif(not Path("/proc/self/task").is_dir(), reason="thread names are read from /proc")
+def test_runtime_name_labels_worker_threads() -> None:
+    def thread_name() -> str:
+        return Path(f"/proc/self/task/{threading.get_native_id()}/comm").read_text().strip()
+
+    with Runtime(name="ssr") as runtime:
+        runtime.register("thread_name", thread_name)
+
+        assert runtime(Script("export default () => host.thread_name();"))().startswith("ssr-")
+
+    with pytest.raises(ValueError, match="name must be"):
+        Runtime(name="")
+
+
+def test_host_function_errors_are_catchable_in_javascript() -> None:
+    def fail() -> None:
+        message = "lookup failed"
+        raise LookupError(message)
+
+    source = """
+    export default () => {
+      try {
+        host.fail();
+      } catch (error) {
+        return error.message;
+      }
+    };
+    """
+
+    with Runtime() as runtime:
+        runtime.register("fail", fail)
+
+        assert "lookup failed" in runtime(Script(source))()
+        with pytest.raises(_core.BelgieJavaScriptError, match="host.fail failed"):
+            runtime(Script("export default () => host.fail();"))()
+
+
+def test_native_backtraces_identify_failing_host_functions() -> None:
+    def fail() -> None:
+        message = "lookup failed"
+        raise LookupError(message)
+
+    script = Script("export default () => host.fail();")
+
+    with Runtime(options=RuntimeOptions(native_backtraces=True)) as runtime:
+        runtime.register("fail", fail)
+        with pytest.raises(_core.BelgieJavaScriptError) as exc_info:
+            runtime(script)()
+
+    assert exc_info.value.message is not None
+    assert exc_info.value.message.startswith("host.fail failed: LookupError: lookup failed")
+    assert "Native backtrace (host.fail):" in exc_info.value.message
+    assert "native_backtraces=true" in repr(RuntimeOptions(native_backtraces=True))
+
+    with Runtime() as runtime:
+        runtime.register("fail", fail)
+        with pytest.raises(_core.BelgieJavaScriptError) as exc_info:
+            runtime(script)()
+
+    assert "Native backtrace" not in str(exc_info.value)
+
+
+def test_register_rejects_invalid_host_functions() -> None:
+    with Runtime() as runtime:
+        with pytest.raises(TypeError, match="callable"):
+            runtime.register("value", cast("Any", 42))
+        with pytest.raises(ValueError, match="identifier"):
+            runtime.register("not-valid", lambda: None)
+
+
+def test_runtime_state_is_shared_between_python_and_javascript() -> None:
+    source = """
+    export default () => {
+      const seen = state.user;
+      state.user = "bob";
+      state.visits = (state.visits ?? 0) + 1;
+      return { seen, keys: Object.keys(state), has: "user" in state };
+    };
+    """
+
+    with Runtime() as runtime:
+        runtime.state["user"] = "alice"
+        run = runtime(Script(source))
+
+        assert run() == {"seen": "alice", "keys": ["user", "visits"], "has": True}
+        assert runtime.state["user"] == "bob"
+        assert runtime.state["visits"] == 1
+
+        del runtime.state["user"]
+
+        assert run()["seen"] is None
+        assert list(runtime.state) == ["user", "visits"]
+        assert len(runtime.state) == 2
+
+
+def test_runtime_state_outlives_sessions_and_is_shared_by_runners() -> None:
+    runtime = Runtime()
+    runtime.state["count"] = 0
+    source = "export default () => (state.count += 1);"
+
+    with runtime as session:
+        assert session(Script(source))() == 1
+        assert session(Script(source))() == 2
+    with runtime as session:
+        assert session(Script(source))() == 3
+
+    assert runtime.state.get("count") == 3
+    assert "missing" not in runtime.state
+    assert runtime.state.get("missing", "default") == "default"
+    with pytest.raises(KeyError):
+        runtime.state["missing"]
+
+
+def test_runtime_state_values_must_be_json_serializable() -> None:
+    source = """
+    export default () => {
+      try {
+        state.callback = () => 1;
+      } catch (error) {
+        return [error.name, "callback" in state];
+      }
+    };
+    """
+
+    with Runtime() as runtime:
+        with pytest.raises(TypeError, match="JSON-serializable"):
+            runtime.state["value"] = object()
+
+        assert runtime(Script(source))() == ["TypeError", False]
+
+
+def test_local_storage_writes_through_to_the_python_mapping() -> None:
+    source = """
+    export default () => {
+      const theme = localStorage.getItem("theme");
+      localStorage.setItem("k", "v");
+      localStorage.setItem("count", 1);
+      localStorage.removeItem("theme");
+      return [theme, localStorage.getItem("missing"), localStorage.length, localStorage.key(0)];
+    };
+    """
+    storage = {"theme": "dark"}
+
+    with Runtime(storage=storage) as runtime:
+        assert runtime(Script(source))() == ["dark", None, 2, "k"]
+
+    assert storage == {"k": "v", "count": "1"}
+
+
+def test_local_storage_rejects_non_string_values() -> None:
+    with pytest.raises(TypeError, match="mutable mapping"):
+        Runtime(storage=cast("Any", [("k", "v")]))
+
+    with Runtime(storage=cast("Any", {"k": 1})) as runtime:
+        run = runtime(Script('export default () => localStorage.getItem("k");'))
+        with pytest.raises(_core.BelgieJavaScriptError, match="values must be strings"):
+            run()
+
+    with Runtime(storage=cast("Any", {1: "v"})) as runtime:
+        run = runtime(Script("export default () => localStorage.length;"))
+        with pytest.raises(_core.BelgieJavaScriptError, match="keys must be strings"):
+            run()
+
+
+def test_session_storage_is_kept_per_runner_and_never_reaches_python() -> None:
+    source = """
+    export default (value) => {
+      const previous = sessionStorage.getItem("k");
+      sessionStorage.setItem("k", value);
+      sessionStorage.setItem("count", 1);
+      return [previous, sessionStorage.getItem("count"), sessionStorage.length, sessionStorage.key(0)];
+    };
+    """
+    storage: dict[str, str] = {}
+
+    with Runtime(storage=storage) as runtime:
+        run = runtime(Script(source))
+        assert run("a") == [None, "1", 2, "k"]
+        assert run("b") == ["a", "1", 2, "k"]
+        assert runtime(Script(source))("c") == [None, "1", 2, "k"]
+
+        run.reset()
+
+        assert run("d") == [None, "1", 2, "k"]
+
+    assert storage == {}
+
+
+async def test_async_runtime_state_is_shared_with_scripts() -> None:
+    async with Runtime() as runtime:
+        runtime.state["items"] = [1, 2]
+        run = runtime(Script("export default () => { state.items = [...state.items, 3]; };"))
+
+        await run()
+
+        assert runtime.state["items"] == [1, 2, 3]
+
+
 @pytest.mark.parametrize(
     "source",
     [
@@ -178,6 +1699,28 @@ def test_executes_common_export_shapes(source: str) -> None:
     assert run_source(source, {"value": 41}) == {"value": 42}
 
 
+def test_sync_runners_wait_for_top_level_await_before_calling() -> None:
+    source = """
+    const base = await new Promise((resolve) => setTimeout(() => resolve(40), 1));
+    export default async (delta) => base + (await Promise.resolve(delta));
+    """
+
+    assert run_source(source, 2) == 42
+
+
+def test_resolves_returned_promises_before_returning() -> None:
+    source = "export default () => Promise.resolve(41).then((value) => value + 1);"
+
+    assert run_source(source) == 42
+
+
+def test_rejected_promise_raises_javascript_error_with_reason() -> None:
+    source = "export default () => Promise.reject(new Error('rejected boom'));"
+
+    with pytest.raises(_core.BelgieJavaScriptError, match="rejected boom"):
+        run_source(source)
+
+
 def test_executes_typescript_annotations_in_inline_source() -> None:
     source = """
 export default function run(input: { first: number; second: number }): number {
@@ -342,6 +1885,21 @@ def test_executes_script_loaded_from_file_with_relative_import(write_script) ->
         assert runtime(Script.from_file(path))({"value": 21}) == 42
 
 
+def test_runtime_module_cache_reuses_imports_until_cleared(write_script) -> None:
+    write_script("export const value: number = 1;\n", "lib/value.ts")
+    path = write_script('import { value } from "./lib/value.ts"; export default () => value;\n', "main.ts")
+    script = Script.from_file(path)
+
+    with Runtime() as runtime:
+        assert runtime(script)() == 1
+        write_script("export const value: number = 2;\n", "lib/value.ts")
+        assert runtime(script)() == 1
+
+        runtime.clear_module_cache()
+
+        assert runtime(script)() == 2
+
+
 async def test_cancelled_async_enter_can_be_retried() -> None:
     runtime = Runtime()
 
@@ -357,6 +1915,26 @@ async def test_cancelled_async_enter_can_be_retried() -> None:
         assert await active(Script("export default async () => 'ok';"))() == "ok"
 
 
+def test_async_runner_coroutine_created_before_loop_runs_in_asyncio_run() -> None:
+    runtime = Runtime()
+
+    async def enter() -> AsyncRuntime:
+        return await runtime.__aenter__()
+
+    async def exit_runtime() -> None:
+        await runtime.__aexit__(None, None, None)
+
+    active = asyncio.run(enter())
+    try:
+        run = active(Script("let calls = 0; export default (value) => [value + 1, ++calls];"))
+        coroutine = run(41)
+
+        assert asyncio.run(run(0)) == [1, 1]
+        assert asyncio.run(coroutine) == [42, 2]
+    finally:
+        asyncio.run(exit_runtime())
+
+
 async def test_async_runner_returns_awaitable_and_awaits_export() -> None:
     source = """
 const resolved = await Promise.resolve(41);
@@ -407,6 +1985,84 @@ async def test_async_closed_runner_raises_runtime_error() -> None:
         await run()
 
 
+async def test_async_runtime_close_is_idempotent() -> None:
+    async with Runtime() as runtime:
+        run = runtime(Script("export default async () => 'ok';"))
+        assert await run() == "ok"
+
+        await runtime.close()
+        await runtime.close()
+
+        with pytest.raises(_core.BelgieRuntimeError, match="closed"):
+            await run()
+
+
+async def test_async_is_alive_reports_false_after_close() -> None:
+    async with Runtime() as runtime:
+        run = runtime(Script("export default async () => 'ok';"))
+        assert runtime.is_alive()
+        assert run.is_alive()
+
+    assert not runtime.is_alive()
+    assert not run.is_alive()
+
+
+async def test_async_runtime_host_functions_can_be_coroutines() -> None:
+    async def double(value: int) -> int:
+        await asyncio.sleep(0)
+        return value * 2
+
+    async with Runtime() as runtime:
+        runtime.register("double", double)
+        run = runtime(Script("export default (value) => host.double(value);"))
+
+        assert await run(21) == 42
+
+
+async def test_async_host_coroutines_can_await_calls_on_the_same_runtime() -> None:
+    async with Runtime() as runtime:
+        inner = runtime(Script("export default (value) => value * 2;"))
+
+        async def double(value: int) -> int:
+            return await inner(value)
+
+        runtime.register("double", double)
+        outer = runtime(Script("export default async (value) => (await host.double(value)) + 1;"))
+
+        assert await outer(20) == 41
+
+
+def test_host_coroutines_leave_the_runner_serving_its_event_loop() -> None:
+    async def slow() -> str:
+        await asyncio.sleep(0.2)
+        return "done"
+
+    async def fail() -> None:
+        message = "lookup failed"
+        raise LookupError(message)
+
+    source = """
+    export default async () => {
+      let ticks = 0;
+      const timer = setInterval(() => ticks++, 10);
+      const value = await host.slow();
+      clearInterval(timer);
+      const error = await host.fail().catch((error) => error.message);
+      return { value, ticked: ticks > 0, error };
+    };
+    """
+
+    with Runtime() as runtime:
+        runtime.register("slow", slow)
+        runtime.register("fail", fail)
+
+        assert runtime(Script(source))() == {
+            "value": "done",
+            "ticked": True,
+            "error": "host.fail failed: LookupError: lookup failed",
+        }
+
+
 async def test_async_script_invocation_can_be_cancelled() -> None:
     source = "export default () => { while (true) {} };"
 
@@ -418,8 +2074,135 @@ async def test_async_script_invocation_can_be_cancelled() -> None:
             await task
 
 
+async def test_cancelled_async_invocation_leaves_runner_usable() -> None:
+    source = "export default (spin) => { if (spin) { while (true) {} } return 'idle'; };"
+
+    async with Runtime() as runtime:
+        run = runtime(Script(source))
+        task = asyncio.create_task(run(True))
+        await asyncio.sleep(0.05)
+        task.cancel()
+        with pytest.raises(asyncio.CancelledError):
+            await task
+
+        assert await asyncio.wait_for(run(False), timeout=5) == "idle"
+        assert run.is_alive()
+
+
+def test_interrupt_stops_a_running_call_from_another_thread() -> None:
+    source = """
+export default (spin) => {
+  while (spin) {}
+  return "idle";
+};
+"""
+    runtime = Runtime()
+    errors: list[BaseException] = []
+
+    with runtime as active:
+        run = active(Script(source))
+        assert not runtime.interrupt()
+
+        def spin() -> None:
+            try:
+                run(True)
+            except _core.BelgieRuntimeError as error:
+                errors.append(error)
+
+        thread = threading.Thread(target=spin)
+        thread.start()
+        deadline = time.monotonic() + 5
+        while not active.interrupt() and time.monotonic() < deadline:
+            time.sleep(0.01)
+        thread.join(timeout=5)
+
+        assert not thread.is_alive()
+        assert len(errors) == 1
+        assert "interrupted by Runtime.interrupt()" in str(errors[0])
+        assert run(False) == "idle"
+
+
+async def test_async_close_answers_every_queued_call() -> None:
+    source = "export default () => new Promise((resolve) => setTimeout(resolve, 60_000));"
+
+    async with Runtime() as runtime:
+        run = runtime(Script(source))
+        tasks = [asyncio.create_task(run()) for _ in range(4)]
+        await asyncio.sleep(0.05)
+        await runtime.close()
+
+        results = await asyncio.wait_for(asyncio.gather(*tasks, return_exceptions=True), timeout=5)
+
+    assert all(isinstance(result, _core.BelgieRuntimeError) for result in results)
+    assert all("shutting down" in str(result) for result in results[1:])
+
+
+async def test_async_runner_pool_spreads_concurrent_calls_across_workers() -> None:
+    source = """
+    const worker = crypto.randomUUID();
+    export default async () => {
+      await new Promise((resolve) => setTimeout(resolve, 50));
+      return worker;
+    };
+    """
+
+    async with Runtime() as runtime:
+        pool = runtime.pool(Script(source), size=3)
+        workers = await asyncio.gather(*(pool() for _ in range(3)))
+
+        assert pool.size == 3
+        assert len(set(workers)) == 3
+        assert "AsyncRunnerPool(3 workers" in repr(pool)
+
+
 async def test_async_command_runner_repr_smoke() -> None:
     async with Runtime() as runtime:
         command = runtime(Command("vite"))
         assert isinstance(command, AsyncCommandRunner)
         assert "AsyncCommandRunner" in repr(command)
+
+
+def test_sync_runner_pool_serves_calls_from_threads() -> None:
+    with Runtime() as runtime:
+        pool = runtime.pool(Script("export default (value) => value * 2;"), size=2)
+        results: list[int] = []
+        threads = [threading.Thread(target=lambda value=value: results.append(pool(value))) for value in range(4)]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+
+        assert sorted(results) == [0, 2, 4, 6]
+        assert pool.is_alive()
+
+
+def test_runner_pool_rejects_empty_pools() -> None:
+    with Runtime() as runtime, pytest.raises(ValueError, match="at least 1"):
+        runtime.pool(Script("export default () => 1;"), size=0)
+
+
+def test_runtime_watch_reruns_a_file_script_after_each_change(tmp_path: Path) -> None:
+    script_path = tmp_path / "page.js"
+    script_path.write_text("export default (name) => `v1 ${name}`;", encoding="utf-8")
+    results: queue.Queue[object] = queue.Queue()
+
+    with Runtime() as runtime:
+        watcher = runtime.watch(Script.from_file(script_path), results.put, "belgie")
+        assert results.get(timeout=10) == "v1 belgie"
+
+        script_path.write_text("export default (name) => `v2 ${name}`;", encoding="utf-8")
+        assert results.get(timeout=10) == "v2 belgie"
+
+        script_path.write_text("export default () => { throw new Error('broken edit'); };", encoding="utf-8")
+        error = results.get(timeout=10)
+        assert isinstance(error, _core.BelgieJavaScriptError)
+        assert "broken edit" in str(error)
+        assert watcher.is_alive()
+
+    watcher.close()
+    assert not watcher.is_alive()
+
+
+def test_runtime_watch_requires_a_file_script() -> None:
+    with Runtime() as runtime, pytest.raises(_core.BelgieRuntimeError, match="Script.from_file"):
+        runtime.watch(Script("export default () => 1;"), print)
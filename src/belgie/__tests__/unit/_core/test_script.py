@@ -1,11 +1,12 @@
 from __future__ import annotations
 
+import gzip
 from pathlib import Path
 from typing import TYPE_CHECKING, Any, cast
 
 import pytest
 
-from belgie import Script
+from belgie import CompressedModule, Runtime, Script, _core
 from belgie.__tests__.unit._core.conftest import StringPath
 
 if TYPE_CHECKING:
@@ -43,10 +44,176 @@ def test_script_loads_source_from_str_and_pathlike_files(write_script: Callable[
 
 
 def test_script_reports_missing_script_files(tmp_path: Path) -> None:
-    with pytest.raises(FileNotFoundError):
+    with pytest.raises(FileNotFoundError, match="missing.ts"):
         Script.from_file(tmp_path / "missing.ts")
 
 
+@pytest.mark.parametrize(
+    ("filename", "source", "expected"),
+    [
+        ("main.js", "export default () => 'js';", "js"),
+        ("main.mjs", "export default () => 'mjs';", "mjs"),
+        ("main.ts", "export default (): string => 'ts';", "ts"),
+        ("main.tsx", "export default (): string => 'tsx';", "tsx"),
+    ],
+)
+def test_file_scripts_infer_language_from_extension(
+    write_script: Callable[[str, str], Path],
+    filename: str,
+    source: str,
+    expected: str,
+) -> None:
+    script = Script.from_file(write_script(source, filename))
+
+    with Runtime() as runtime:
+        assert runtime(script)() == expected
+
+
+def test_named_inline_scripts_report_their_name_in_stack_traces() -> None:
+    script = Script("export default () => { throw new Error('named failure'); };", name="widget")
+
+    assert repr(script).startswith('Script(inline script "widget"')
+    with Runtime() as runtime, pytest.raises(_core.BelgieJavaScriptError) as exc_info:
+        runtime(script)()
+
+    assert "widget.ts:1:" in str(exc_info.value.stack)
+    assert str(exc_info.value.filename).endswith("widget.ts")
+
+
+@pytest.mark.parametrize("name", ["", "..", "nested/widget", "nested\\widget"])
+def test_script_names_reject_paths(name: str) -> None:
+    with pytest.raises(ValueError, match="file name"):
+        Script("export default () => 1;", name=name)
+
+
 def test_script_rejects_non_string_inline_source() -> None:
     with pytest.raises(TypeError):
         Script(cast("Any", 42))
+
+
+def test_script_globals_are_installed_before_module_evaluation() -> None:
+    source = "const greeting = `${name}${count}`; export default () => ({ greeting, nested: config.items[1] });"
+    script = Script(source, globals={"name": "a", "count": 2, "config": {"items": [None, True, 1.5]}})
+
+    with Runtime() as runtime:
+        assert runtime(script)() == {"greeting": "a2", "nested": True}
+
+
+def test_script_globals_reject_non_json_values() -> None:
+    with pytest.raises(TypeError, match="Only JSON-serializable"):
+        Script("export default () => value;", globals={"value": object()})
+
+
+def test_script_globals_must_be_a_dict() -> None:
+    with pytest.raises(TypeError):
+        Script("export default () => 1;", globals=cast("Any", [("name", "x")]))
+
+
+def test_script_modules_resolve_relative_imports_from_memory() -> None:
+    script = Script(
+        'import { double } from "./lib/math.ts"; export default (value) => double(value);',
+        modules={
+            "./lib/math.ts": 'import { factor } from "../config.js"; export const double = (v: number) => v * factor;',
+            "./config.js": "export const factor = 2;",
+        },
+    )
+
+    with Runtime() as runtime:
+        assert runtime(script)(21) == 42
+
+
+def brotli_stored(data: bytes) -> bytes:
+    # Uncompressed brotli meta-blocks (RFC 7932, section 9.2).
+    output, value, width = bytearray(), 0, 0
+
+    def write(bits: int, count: int) -> None:
+        nonlocal value, width
+        value |= bits << width
+        width += count
+        while width >= 8:
+            output.append(value & 0xFF)
+            value, width = value >> 8, width - 8
+
+    def pad() -> None:
+        if width:
+            write(0, 8 - width)
+
+    write(0, 1)  # WBITS = 16
+    for start in range(0, len(data), 4096):
+        chunk = data[start : start + 4096]
+        write(0, 1)  # ISLAST
+        write(0, 2)  # MNIBBLES = 4
+        write(len(chunk) - 1, 16)
+        write(1, 1)  # ISUNCOMPRESSED
+        pad()
+        output.extend(chunk)
+    write(0b11, 2)  # ISLAST, ISLASTEMPTY
+    pad()
+    return bytes(output)
+
+
+def test_script_modules_accept_compressed_sources() -> None:
+    bundle = "export const render = (name) => `<h1>${name}</h1>`.repeat(2);\n" + "// padding\n" * 1000
+    entry = 'import { render } from "./bundle.js"; export default (name) => render(name);'
+    plain = Script(entry, modules={"./bundle.js": bundle})
+    compressed_modules = {
+        "gzip": CompressedModule(gzip.compress(bundle.encode()), "gzip"),
+        "br": CompressedModule(brotli_stored(bundle.encode()), "br"),
+    }
+
+    with Runtime() as runtime:
+        expected = runtime(plain)("Ada")
+        for encoding, module in compressed_modules.items():
+            assert module.encoding == encoding
+            assert runtime(Script(entry, modules={"./bundle.js": module}))("Ada") == expected
+
+
+def test_script_modules_reject_corrupt_or_unknown_compression() -> None:
+    with pytest.raises(ValueError, match="encoding"):
+        CompressedModule(b"", "zstd")
+
+    script = Script(
+        'import "./bundle.js"; export default () => 1;',
+        modules={"./bundle.js": CompressedModule(b"not gzip", "gzip")},
+    )
+    with Runtime() as runtime, pytest.raises(_core.BelgieModuleError, match="decompress"):
+        runtime(script)()
+
+
+def test_script_modules_report_missing_modules() -> None:
+    script = Script('import { x } from "./missing.js"; export default () => x;', modules={"./other.js": ""})
+
+    with Runtime() as runtime, pytest.raises(_core.BelgieModuleError, match="missing.js"):
+        runtime(script)()
+
+
+def test_script_modules_require_relative_specifiers() -> None:
+    with pytest.raises(ValueError, match="relative"):
+        Script("export default () => 1;", modules={"helpers.js": "export const x = 1;"})
+
+
+def test_script_imports_resolve_bare_specifiers_to_supplied_modules() -> None:
+    script = Script(
+        'import { createElement } from "react"; import { tag } from "ui/tag.js"; '
+        'export default () => createElement(tag("div"));',
+        modules={
+            "./vendor/react.js": "export const createElement = (type) => ({ type });",
+            "./vendor/ui/tag.js": "export const tag = (name) => name.toUpperCase();",
+        },
+        imports={"react": "./vendor/react.js", "ui/": "./vendor/ui/"},
+    )
+
+    with Runtime() as runtime:
+        assert runtime(script)() == {"type": "DIV"}
+
+
+def test_script_imports_reject_prefixes_without_trailing_slash_targets() -> None:
+    with pytest.raises(ValueError, match="ending in /"):
+        Script("export default () => 1;", imports={"ui/": "./vendor/ui"})
+
+
+def test_script_imports_reject_bare_targets() -> None:
+    script = Script('import React from "react"; export default () => React;', imports={"react": "preact"})
+
+    with Runtime() as runtime, pytest.raises(_core.BelgieModuleError, match="relative path or URL"):
+        runtime(script)()
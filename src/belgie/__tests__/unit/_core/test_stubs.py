@@ -0,0 +1,119 @@
+from __future__ import annotations
+
+import ast
+import copy
+import inspect
+from pathlib import Path
+from typing import Any
+
+import pytest
+
+from belgie import _core
+
+STUB = ast.parse(Path(_core.__file__).with_name("_core.pyi").read_text(encoding="utf-8"))
+
+type ParameterShape = tuple[str, str, bool]
+
+
+def stub_classes() -> list[tuple[str, type[Any], ast.ClassDef]]:
+    classes = []
+    for node in STUB.body:
+        runtime_class = getattr(_core, node.name, None) if isinstance(node, ast.ClassDef) else None
+        if isinstance(runtime_class, type) and not issubclass(runtime_class, BaseException):
+            classes.append((node.name, runtime_class, node))
+    return classes
+
+
+def stub_methods() -> list[tuple[str, type[Any], ast.FunctionDef | ast.AsyncFunctionDef]]:
+    methods = []
+    for class_name, runtime_class, node in stub_classes():
+        overloaded = {
+            item.name
+            for item in node.body
+            if isinstance(item, ast.FunctionDef) and has_decorator(item, "overload")
+        }
+        for item in node.body:
+            is_method = isinstance(item, (ast.FunctionDef, ast.AsyncFunctionDef))
+            if not is_method or item.name in overloaded or has_decorator(item, "property"):
+                continue
+            # Other dunders take positional protocol arguments whose names callers never see.
+            if item.name == "__init__" or not item.name.startswith("_"):
+                methods.append((f"{class_name}.{item.name}", runtime_class, item))
+    return methods
+
+
+def has_decorator(function: ast.FunctionDef | ast.AsyncFunctionDef, name: str) -> bool:
+    return any(isinstance(decorator, ast.Name) and decorator.id == name for decorator in function.decorator_list)
+
+
+def stub_parameters(function: ast.FunctionDef | ast.AsyncFunctionDef) -> list[ParameterShape]:
+    arguments = function.args
+    positional = [*arguments.posonlyargs, *arguments.args]
+    first_default = len(positional) - len(arguments.defaults)
+    shapes = [
+        (
+            argument.arg,
+            "POSITIONAL_ONLY" if index < len(arguments.posonlyargs) else "POSITIONAL_OR_KEYWORD",
+            index >= first_default,
+        )
+        for index, argument in enumerate(positional)
+    ]
+    if not has_decorator(function, "staticmethod"):
+        shapes = shapes[1:]
+    if arguments.vararg is not None:
+        shapes.append((arguments.vararg.arg, "VAR_POSITIONAL", False))
+    shapes.extend(
+        (argument.arg, "KEYWORD_ONLY", default is not None)
+        for argument, default in zip(arguments.kwonlyargs, arguments.kw_defaults, strict=True)
+    )
+    if arguments.kwarg is not None:
+        shapes.append((arguments.kwarg.arg, "VAR_KEYWORD", False))
+    return shapes
+
+
+def runtime_parameters(target: object) -> list[ParameterShape]:
+    parameters = list(inspect.signature(target).parameters.values())
+    if parameters and parameters[0].name == "self":
+        parameters = parameters[1:]
+    return [
+        (parameter.name, parameter.kind.name, parameter.default is not inspect.Parameter.empty)
+        for parameter in parameters
+    ]
+
+
+@pytest.mark.parametrize(
+    ("runtime_class", "function"),
+    [pytest.param(runtime_class, function, id=name) for name, runtime_class, function in stub_methods()],
+)
+def test_stub_signatures_match_native_signatures(
+    runtime_class: type[Any],
+    function: ast.FunctionDef | ast.AsyncFunctionDef,
+) -> None:
+    target = runtime_class if function.name == "__init__" else getattr(runtime_class, function.name)
+
+    assert runtime_parameters(target) == stub_parameters(function)
+
+
+@pytest.mark.parametrize(
+    ("runtime_class", "node"),
+    [pytest.param(runtime_class, node, id=name) for name, runtime_class, node in stub_classes()],
+)
+def test_stub_declares_every_public_native_member(runtime_class: type[Any], node: ast.ClassDef) -> None:
+    declared = {
+        item.name if isinstance(item, (ast.FunctionDef, ast.AsyncFunctionDef)) else ast.unparse(item.target)
+        for item in node.body
+        if isinstance(item, (ast.FunctionDef, ast.AsyncFunctionDef, ast.AnnAssign))
+    }
+    public = {name for name in vars(runtime_class) if not name.startswith("_")}
+
+    assert public <= declared
+
+
+@pytest.mark.parametrize("name", ["configure", "version"])
+def test_stub_signatures_match_native_functions(name: str) -> None:
+    function = copy.deepcopy(
+        next(node for node in STUB.body if isinstance(node, ast.FunctionDef) and node.name == name),
+    )
+    function.decorator_list.append(ast.Name("staticmethod"))
+
+    assert runtime_parameters(getattr(_core, name)) == stub_parameters(function)
@@ -0,0 +1,79 @@
+from __future__ import annotations
+
+import pytest
+
+from belgie import Renderer, Runtime, _core
+
+BUNDLE = """
+globalThis.bundleLoads = (globalThis.bundleLoads ?? 0) + 1;
+
+export function render(componentId, props) {
+  globalThis.requestUser ??= props.user;
+  return `<${componentId}>${globalThis.requestUser}:${globalThis.bundleLoads}</${componentId}>`;
+}
+"""
+
+
+def test_renderer_isolates_request_globals_and_evaluates_the_bundle_once() -> None:
+    renderer = Renderer(BUNDLE)
+
+    with Runtime() as runtime:
+        render = renderer.bind(runtime)
+
+        assert render("greeting", {"user": "ada"}) == "<greeting>ada:1</greeting>"
+        assert render("greeting", {"user": "grace"}) == "<greeting>grace:1</greeting>"
+
+
+def test_renderer_restores_redefined_global_descriptors() -> None:
+    bundle = """
+globalThis.flag = "setup";
+
+export function render(componentId) {
+  const before = Object.getOwnPropertyDescriptor(globalThis, "flag").enumerable;
+  Object.defineProperty(globalThis, "flag", { enumerable: false });
+  return `${componentId}:${before}`;
+}
+"""
+
+    with Runtime() as runtime:
+        render = Renderer(bundle).bind(runtime)
+
+        assert render("first") == "first:true"
+        assert render("second") == "second:true"
+
+
+def test_renderer_does_not_reset_module_state_prototypes_or_mutated_globals() -> None:
+    bundle = """
+let renders = 0;
+globalThis.cache = new Map();
+
+export function render(componentId) {
+  renders += 1;
+  globalThis.cache.set(componentId, renders);
+  Array.prototype.belgieSeen ??= componentId;
+  return `${renders}:${globalThis.cache.size}:${[].belgieSeen}`;
+}
+"""
+
+    with Runtime() as runtime:
+        render = Renderer(bundle).bind(runtime)
+
+        assert render("first") == "1:1:first"
+        assert render("second") == "2:2:first"
+
+
+async def test_async_renderer_awaits_async_render_functions() -> None:
+    renderer = Renderer("export default async (componentId, props) => `${componentId}:${props.count}`;")
+
+    async with Runtime() as runtime:
+        render = renderer.bind_async(runtime)
+
+        assert await render("counter", {"count": 2}) == "counter:2"
+
+
+def test_renderer_requires_a_render_export() -> None:
+    with Runtime() as runtime:
+        render = Renderer("export const value = 1;").bind(runtime)
+
+        with pytest.raises(_core.BelgieJavaScriptError, match="render\\(componentId, props\\)"):
+            render("missing", {})
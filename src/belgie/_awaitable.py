@@ -1,5 +1,34 @@
-from collections.abc import Awaitable
+import asyncio
+import threading
+from collections.abc import Awaitable, Callable
+from concurrent.futures import Future
 
+_host_loop: list[asyncio.AbstractEventLoop] = []
+_host_loop_lock = threading.Lock()
 
-async def as_coroutine[T](awaitable: Awaitable[T]) -> T:
+
+async def as_coroutine[T](start: Callable[[], Awaitable[T]]) -> T:
+    return await start()
+
+
+def run_host_awaitable[T](
+    awaitable: Awaitable[T],
+    loop: asyncio.AbstractEventLoop | None,
+    done: Callable[[Future[T]], object],
+) -> None:
+    # Schedules on `loop`, or a shared background loop, and returns without waiting.
+    future = asyncio.run_coroutine_threadsafe(_await(awaitable), loop or _background_loop())
+    future.add_done_callback(done)
+
+
+async def _await[T](awaitable: Awaitable[T]) -> T:
     return await awaitable
+
+
+def _background_loop() -> asyncio.AbstractEventLoop:
+    with _host_loop_lock:
+        if not _host_loop:
+            loop = asyncio.new_event_loop()
+            threading.Thread(target=loop.run_forever, name="belgie-host-loop", daemon=True).start()
+            _host_loop.append(loop)
+        return _host_loop[0]
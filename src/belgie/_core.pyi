@@ -1,8 +1,8 @@
-from collections.abc import Awaitable, Coroutine, Iterable, Mapping
+from collections.abc import Awaitable, Callable, Coroutine, Iterable, Iterator, Mapping, MutableMapping, Sequence
 from os import PathLike
 from pathlib import Path
 from types import TracebackType
-from typing import Any, Literal, Self, overload
+from typing import Any, Literal, NotRequired, Self, TypedDict, overload
 
 type JsonPrimitive = None | bool | int | float | str
 type JsonInput = JsonPrimitive | list[JsonInput] | tuple[JsonInput, ...] | dict[str, JsonInput]
@@ -11,17 +11,72 @@ type JsonObject = dict[str, JsonOutput]
 type JsonArray = list[JsonOutput]
 type CacheSetting = Literal["use", "reload", "only"]
 type JsonImportMode = Literal["with_attribute", "always"]
+type UnserializablePolicy = Literal["error", "null", "stringify"]
 type NodeModulesDirMode = Literal["auto", "manual", "none"]
 type NodeModulesLinkerMode = Literal["isolated", "hoisted"]
 type NpmCachingMode = Literal["eager", "lazy", "manual"]
 type WorkerLogLevel = Literal["error", "warn", "info", "debug"]
+type RuntimeExtension = Literal["text_encoding", "crypto", "performance", "url", "base64", "structured_clone", "abort"]
+type ModuleEncoding = Literal["gzip", "br"]
+type ConsoleLevel = Literal["debug", "info", "log", "warn", "error"]
+
+class JavaScriptStackFrame(TypedDict):
+    file: str | None
+    line: int | None
+    column: int | None
+    function: str | None
+
+class JavaScriptErrorDocument(TypedDict):
+    name: str | None
+    message: str | None
+    stack: str | None
+    frames: list[JavaScriptStackFrame]
+
+class ConsoleMessage(TypedDict):
+    level: ConsoleLevel
+    message: str
+    request_id: NotRequired[str]
+
+class HeapStats(TypedDict):
+    used_heap_size: int
+    total_heap_size: int
+    heap_size_limit: int
+    external_memory: int
+
+class CallTimings(TypedDict):
+    compile_ms: float
+    run_ms: float
+    event_loop_ms: float
+
+class OpMetric(TypedDict):
+    calls: int
+    total_ms: float
 
 class BelgieError(Exception): ...
 class BelgieRuntimeError(BelgieError): ...
 class BelgieModuleError(BelgieError): ...
-class BelgieJavaScriptError(BelgieError): ...
+class BelgieJavaScriptError(BelgieError):
+    name: str | None
+    message: str | None
+    stack: str | None
+    filename: str | None
+    line: int | None
+    column: int | None
+    source_line: str | None
+    request_id: str | None
+    frames: list[JavaScriptStackFrame]
+    def to_json(self) -> JavaScriptErrorDocument: ...
+class BelgieTimeoutError(BelgieError): ...
+class BelgieSyntaxError(BelgieModuleError):
+    line: int | None
+    column: int | None
+    source_line: str | None
+class BelgieClosedError(BelgieRuntimeError): ...
+class BelgieResultTooLargeError(BelgieRuntimeError): ...
 
 def _run_node_child(module: str | PathLike[str], argv: list[str]) -> int: ...
+def configure(*, v8_flags: Iterable[str] | None = None) -> None: ...
+def version() -> dict[str, str]: ...
 
 class EnvironmentInstallResult:
     @property
@@ -69,20 +124,120 @@ class AsyncEnvironment:
         lockfile_only: bool = False,
     ) -> Awaitable[EnvironmentUpdateResult]: ...
 
+class CompressedModule:
+    def __init__(self, data: bytes, encoding: ModuleEncoding) -> None: ...
+    @property
+    def encoding(self) -> ModuleEncoding: ...
+    def __len__(self) -> int: ...
+
 class Script[**P, R]:
-    def __init__(self, content: str) -> None: ...
+    def __init__(
+        self,
+        content: str,
+        *,
+        name: str | None = None,
+        globals: dict[str, JsonInput] | None = None,  # noqa: A002  # Mirrors the JavaScript globals it installs.
+        modules: dict[str, str | CompressedModule] | None = None,
+        imports: dict[str, str] | None = None,
+    ) -> None: ...
     @property
     def content(self) -> str: ...
     @property
     def filename(self) -> Path | None: ...
     @classmethod
-    def from_file(cls: type[Self], path: str | PathLike[str]) -> Self: ...
+    def from_file(
+        cls: type[Self],
+        path: str | PathLike[str],
+        *,
+        globals: dict[str, JsonInput] | None = None,  # noqa: A002  # Mirrors the JavaScript globals it installs.
+        modules: dict[str, str | CompressedModule] | None = None,
+        imports: dict[str, str] | None = None,
+    ) -> Self: ...
 
 class SyncRunner[**P, R]:
     def __call__(self, *args: P.args, **kwargs: P.kwargs) -> R: ...
+    def timed(self, *args: P.args, **kwargs: P.kwargs) -> tuple[R, CallTimings]: ...
+    def diagnose(self, *args: P.args, **kwargs: P.kwargs) -> tuple[R, list[Any]]: ...
+    def msgpack(self, *args: P.args, **kwargs: P.kwargs) -> bytes: ...
+    def batch(self, calls: Iterable[tuple[Any, ...]]) -> list[R | BaseException]: ...
+    def warmup(self, *args: Any, iterations: int = 100, **kwargs: Any) -> None: ...
+    def stream(self, *args: P.args, **kwargs: P.kwargs) -> SyncStream: ...
+    def ready(self) -> None: ...
+    def load(self) -> None: ...
+    def evaluate(self) -> Any: ...
+    def drain_logs(self) -> list[ConsoleMessage]: ...
+    def drain_unhandled_rejections(self) -> list[str]: ...
+    def globals(self, *, include_builtins: bool = False) -> dict[str, str]: ...
+    def heap_stats(self) -> HeapStats: ...
+    def start_profiling(self) -> None: ...
+    def stop_profiling(self) -> bytes: ...
+    def heap_snapshot(self) -> bytes: ...
+    def capture_state(self) -> bytes: ...
+    def reset(self) -> None: ...
+    def with_request_id(self, request_id: str) -> SyncRunner[P, R]: ...
+    @property
+    def request_id(self) -> str | None: ...
+    def is_alive(self) -> bool: ...
 
 class AsyncRunner[**P, R]:
     def __call__(self, *args: P.args, **kwargs: P.kwargs) -> Coroutine[Any, Any, R]: ...
+    def timed(self, *args: P.args, **kwargs: P.kwargs) -> Coroutine[Any, Any, tuple[R, CallTimings]]: ...
+    def diagnose(self, *args: P.args, **kwargs: P.kwargs) -> Coroutine[Any, Any, tuple[R, list[Any]]]: ...
+    def msgpack(self, *args: P.args, **kwargs: P.kwargs) -> Coroutine[Any, Any, bytes]: ...
+    def batch(self, calls: Iterable[tuple[Any, ...]]) -> Coroutine[Any, Any, list[R | BaseException]]: ...
+    def warmup(self, *args: Any, iterations: int = 100, **kwargs: Any) -> Coroutine[Any, Any, None]: ...
+    def stream(self, *args: P.args, **kwargs: P.kwargs) -> AsyncStream: ...
+    def ready(self) -> Coroutine[Any, Any, None]: ...
+    def load(self) -> Coroutine[Any, Any, None]: ...
+    def evaluate(self) -> Coroutine[Any, Any, Any]: ...
+    def drain_logs(self) -> Coroutine[Any, Any, list[ConsoleMessage]]: ...
+    def drain_unhandled_rejections(self) -> Coroutine[Any, Any, list[str]]: ...
+    def globals(self, *, include_builtins: bool = False) -> Coroutine[Any, Any, dict[str, str]]: ...
+    def heap_stats(self) -> Coroutine[Any, Any, HeapStats]: ...
+    def start_profiling(self) -> Coroutine[Any, Any, None]: ...
+    def stop_profiling(self) -> Coroutine[Any, Any, bytes]: ...
+    def heap_snapshot(self) -> Coroutine[Any, Any, bytes]: ...
+    def capture_state(self) -> Coroutine[Any, Any, bytes]: ...
+    def reset(self) -> Coroutine[Any, Any, None]: ...
+    def with_request_id(self, request_id: str) -> AsyncRunner[P, R]: ...
+    @property
+    def request_id(self) -> str | None: ...
+    def is_alive(self) -> bool: ...
+
+class SyncRunnerPool[**P, R]:
+    def __call__(self, *args: P.args, **kwargs: P.kwargs) -> R: ...
+    @property
+    def size(self) -> int: ...
+    def is_alive(self) -> bool: ...
+
+class AsyncRunnerPool[**P, R]:
+    def __call__(self, *args: P.args, **kwargs: P.kwargs) -> Coroutine[Any, Any, R]: ...
+    @property
+    def size(self) -> int: ...
+    def is_alive(self) -> bool: ...
+
+class SyncStream:
+    def __iter__(self) -> Self: ...
+    def __next__(self) -> Any: ...
+    def close(self) -> None: ...
+
+class AsyncStream:
+    def __aiter__(self) -> Self: ...
+    def __anext__(self) -> Coroutine[Any, Any, Any]: ...
+    def aclose(self) -> Coroutine[Any, Any, None]: ...
+
+class ScriptWatcher:
+    @property
+    def path(self) -> Path: ...
+    def is_alive(self) -> bool: ...
+    def close(self) -> None: ...
+    def __enter__(self) -> Self: ...
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc: BaseException | None,
+        traceback: TracebackType | None,
+    ) -> bool: ...
 
 class Command:
     def __init__(
@@ -100,17 +255,68 @@ class SyncCommandRunner:
 class AsyncCommandRunner:
     def __call__(self, *args: str) -> Coroutine[Any, Any, None]: ...
 
+class RuntimeState:
+    def __getitem__(self, key: str) -> JsonOutput: ...
+    def __setitem__(self, key: str, value: JsonInput) -> None: ...
+    def __delitem__(self, key: str) -> None: ...
+    def __contains__(self, key: object) -> bool: ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[str]: ...
+    def get(self, key: str, default: Any = None) -> Any: ...
+    def keys(self) -> list[str]: ...
+    def clear(self) -> None: ...
+
 class SyncRuntime:
     @overload
     def __call__[**P, R](self, target: Script[P, R]) -> SyncRunner[P, R]: ...
     @overload
     def __call__(self, target: Command) -> SyncCommandRunner: ...
+    @property
+    def state(self) -> RuntimeState: ...
+    def clear_module_cache(self) -> None: ...
+    def interrupt(self) -> bool: ...
+    def op_metrics(self) -> dict[str, OpMetric]: ...
+    def register(self, name: str, function: Callable[..., Any]) -> None: ...
+    def eval(self, expression: str, *, this: object = None, isolate: bool = False) -> Any: ...
+    def define(self, expression: str) -> SyncRunner[..., Any]: ...
+    def ready(self) -> None: ...
+    def iterate(self, expression: str) -> SyncStream: ...
+    def pool[**P, R](self, script: Script[P, R], *, size: int | None = None) -> SyncRunnerPool[P, R]: ...
+    def watch[**P, R](
+        self,
+        script: Script[P, R],
+        callback: Callable[[R | BaseException], object],
+        *args: P.args,
+        **kwargs: P.kwargs,
+    ) -> ScriptWatcher: ...
+    def is_alive(self) -> bool: ...
+    def close(self) -> None: ...
 
 class AsyncRuntime:
     @overload
     def __call__[**P, R](self, target: Script[P, R]) -> AsyncRunner[P, R]: ...
     @overload
     def __call__(self, target: Command) -> AsyncCommandRunner: ...
+    @property
+    def state(self) -> RuntimeState: ...
+    def clear_module_cache(self) -> None: ...
+    def interrupt(self) -> bool: ...
+    def op_metrics(self) -> dict[str, OpMetric]: ...
+    def register(self, name: str, function: Callable[..., Any]) -> None: ...
+    def eval(self, expression: str, *, this: object = None, isolate: bool = False) -> Coroutine[Any, Any, Any]: ...
+    def define(self, expression: str) -> AsyncRunner[..., Any]: ...
+    def ready(self) -> Coroutine[Any, Any, None]: ...
+    def iterate(self, expression: str) -> AsyncStream: ...
+    def pool[**P, R](self, script: Script[P, R], *, size: int | None = None) -> AsyncRunnerPool[P, R]: ...
+    def watch[**P, R](
+        self,
+        script: Script[P, R],
+        callback: Callable[[R | BaseException], object],
+        *args: P.args,
+        **kwargs: P.kwargs,
+    ) -> ScriptWatcher: ...
+    def is_alive(self) -> bool: ...
+    def close(self) -> Coroutine[Any, Any, None]: ...
 
 class RuntimePermissions:
     def __init__(
@@ -156,6 +362,24 @@ class RuntimeOptions:
         enable_raw_imports: bool = False,
         disable_offscreen_canvas: bool = False,
         trace_ops: Iterable[str] | None = None,
+        timeout: float | None = None,
+        capture_console: bool = False,
+        queue_size: int | None = None,
+        inspect: str | None = None,
+        inspect_brk: bool = False,
+        extensions: Iterable[RuntimeExtension] | None = None,
+        setup: str | None = None,
+        deterministic: bool = False,
+        fixed_time_ms: int | None = None,
+        max_result_bytes: int | None = None,
+        env_vars: Mapping[str, str] | None = None,
+        native_backtraces: bool = False,
+        cpu_budget: float | None = None,
+        module_root: str | PathLike[str] | None = None,
+        op_metrics: bool = False,
+        node_compat: bool = False,
+        on_unserializable: UnserializablePolicy = "error",
+        cpu_affinity: int | Sequence[int] | None = None,
     ) -> None: ...
 
 class EnvironmentOptions:
@@ -211,6 +435,10 @@ class Runtime:
         *,
         env: Environment | SyncEnvironment | AsyncEnvironment | None = None,
         options: RuntimeOptions | None = None,
+        storage: MutableMapping[str, str] | None = None,
+        name: str | None = None,
+        restore: bytes | None = None,
+        snapshot: bytes | None = None,
     ) -> None: ...
     @classmethod
     def from_folder(
@@ -218,7 +446,20 @@ class Runtime:
         path: str | PathLike[str],
         *,
         options: RuntimeOptions | None = None,
+        storage: MutableMapping[str, str] | None = None,
+        name: str | None = None,
+        restore: bytes | None = None,
+        snapshot: bytes | None = None,
     ) -> Self: ...
+    @classmethod
+    def create_snapshot(cls, setup_code: str) -> bytes: ...
+    @staticmethod
+    def check(code: str) -> None: ...
+    @property
+    def state(self) -> RuntimeState: ...
+    def clear_module_cache(self) -> None: ...
+    def interrupt(self) -> bool: ...
+    def op_metrics(self) -> dict[str, OpMetric]: ...
     def __enter__(self) -> SyncRuntime: ...
     def __exit__(
         self,
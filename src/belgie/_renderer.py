@@ -0,0 +1,72 @@
+from __future__ import annotations
+
+from typing import TYPE_CHECKING, Final
+
+from belgie._core import Script
+
+if TYPE_CHECKING:
+    from belgie._core import AsyncRunner, AsyncRuntime, CompressedModule, SyncRunner, SyncRuntime
+
+BUNDLE_SPECIFIER: Final[str] = "./__belgie_render_bundle__.js"
+# The own properties of `globalThis` are captured once the bundle has evaluated and put back after each render. Only
+# those properties are reset: module state, prototypes, and objects mutated in place carry over between renders.
+RENDER_ENTRY_SOURCE: Final[str] = f"""
+import * as bundle from "{BUNDLE_SPECIFIER}";
+
+const renderComponent = bundle.render ?? bundle.default;
+
+const baseline = new Map(
+  Reflect.ownKeys(globalThis).map((key) => [key, Reflect.getOwnPropertyDescriptor(globalThis, key)]),
+);
+const sameDescriptor = (left, right) =>
+  left !== undefined &&
+  left.value === right.value &&
+  left.get === right.get &&
+  left.set === right.set &&
+  left.writable === right.writable &&
+  left.enumerable === right.enumerable &&
+  left.configurable === right.configurable;
+
+const restoreGlobals = () => {{
+  for (const key of Reflect.ownKeys(globalThis)) {{
+    if (!baseline.has(key)) {{
+      Reflect.deleteProperty(globalThis, key);
+    }}
+  }}
+  for (const [key, descriptor] of baseline) {{
+    if (!sameDescriptor(Reflect.getOwnPropertyDescriptor(globalThis, key), descriptor)) {{
+      Reflect.defineProperty(globalThis, key, descriptor);
+    }}
+  }}
+}};
+
+export default async function render(componentId, props = {{}}) {{
+  if (typeof renderComponent !== "function") {{
+    throw new TypeError("Renderer bundles must export a render(componentId, props) function");
+  }}
+  try {{
+    return await renderComponent(componentId, props);
+  }} finally {{
+    restoreGlobals();
+  }}
+}}
+"""
+
+
+# Each bound runner evaluates the bundle once and reuses it for every render.
+class Renderer:
+    def __init__(self, bundle: str | CompressedModule, *, name: str | None = None) -> None:
+        self._script: Script = Script(RENDER_ENTRY_SOURCE, name=name, modules={BUNDLE_SPECIFIER: bundle})
+
+    @property
+    def script(self) -> Script:
+        return self._script
+
+    def bind(self, runtime: SyncRuntime) -> SyncRunner:
+        return runtime(self._script)
+
+    def bind_async(self, runtime: AsyncRuntime) -> AsyncRunner:
+        return runtime(self._script)
+
+    def __repr__(self) -> str:
+        return f"Renderer({self._script!r})"
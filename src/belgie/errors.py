@@ -1,13 +1,21 @@
 from belgie._core import (
+    BelgieClosedError,
     BelgieError,
     BelgieJavaScriptError,
     BelgieModuleError,
+    BelgieResultTooLargeError,
     BelgieRuntimeError,
+    BelgieSyntaxError,
+    BelgieTimeoutError,
 )
 
 __all__: tuple[str, ...] = (
+    "BelgieClosedError",
     "BelgieError",
     "BelgieJavaScriptError",
     "BelgieModuleError",
+    "BelgieResultTooLargeError",
     "BelgieRuntimeError",
+    "BelgieSyntaxError",
+    "BelgieTimeoutError",
 )
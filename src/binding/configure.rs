@@ -0,0 +1,13 @@
+use pyo3::prelude::*;
+
+use crate::embed::init;
+use crate::utils::py_error;
+
+#[pyfunction]
+#[pyo3(signature = (*, v8_flags = None))]
+pub(crate) fn configure(v8_flags: Option<Vec<String>>) -> PyResult<()> {
+    match v8_flags {
+        Some(flags) => init::configure_v8_flags(flags).map_err(py_error::from_binding_error),
+        None => Ok(()),
+    }
+}
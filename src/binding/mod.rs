@@ -1,15 +1,21 @@
 pub(crate) mod blocking;
 pub(crate) mod child_process;
 pub(crate) mod command;
+pub(crate) mod configure;
 pub(crate) mod environment;
 pub(crate) mod normalize;
 pub(crate) mod packages;
 pub(crate) mod runner;
 pub(crate) mod runtime;
 pub(crate) mod script;
+pub(crate) mod state;
+pub(crate) mod stream;
+pub(crate) mod version;
+pub(crate) mod watch;
 
 pub(crate) use child_process::run_node_child;
 pub(crate) use command::PyCommand;
+pub(crate) use configure::configure;
 pub(crate) use environment::{
     PyAsyncEnvironment, PyEnvironment, PyEnvironmentOptions, PySyncEnvironment,
 };
@@ -17,8 +23,12 @@ pub(crate) use packages::{
     PyEnvironmentInstallResult, PyEnvironmentUpdateChange, PyEnvironmentUpdateResult,
 };
 pub(crate) use runner::{
-    PyAsyncCommandRunner, PyAsyncRunner, PyAsyncRuntime, PySyncCommandRunner, PySyncRunner,
-    PySyncRuntime,
+    PyAsyncCommandRunner, PyAsyncRunner, PyAsyncRunnerPool, PyAsyncRuntime, PySyncCommandRunner,
+    PySyncRunner, PySyncRunnerPool, PySyncRuntime,
 };
 pub(crate) use runtime::{PyRuntime, PyRuntimeOptions, PyRuntimePermissions};
-pub(crate) use script::PyScript;
+pub(crate) use script::{PyCompressedModule, PyScript};
+pub(crate) use state::PyRuntimeState;
+pub(crate) use stream::{PyAsyncStream, PySyncStream};
+pub(crate) use version::version;
+pub(crate) use watch::PyScriptWatcher;
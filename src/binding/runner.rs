@@ -2,20 +2,29 @@ use std::sync::Arc;
 
 use pyo3::{
     Borrowed, Bound, FromPyObject, PyAny, PyErr, PyResult, Python,
-    exceptions::PyTypeError,
+    exceptions::{PyRuntimeError, PyTypeError, PyValueError},
     prelude::*,
-    types::{PyAnyMethods, PyDict, PyTuple},
+    types::{PyAnyMethods, PyCFunction, PyDict, PyTuple},
 };
 
 use crate::{
-    binding::{PyCommand, PyScript},
+    binding::{
+        PyAsyncStream, PyCommand, PyRuntimeState, PyScript, PyScriptWatcher, PySyncStream, watch,
+    },
     command::CommandSource,
-    runtime::{DenoExecutionHandle, RuntimeSession, executor},
-    script::ScriptSource,
+    options::ScriptOptions,
+    runtime::{
+        BorrowedRunner, DenoExecutionHandle, EMIT_FUNCTION, HostFunction, RunnerPool,
+        RuntimeSession, WARN_FUNCTION, executor,
+    },
+    script::{ScriptSource, transpile_eval_source},
     types::runner::RunnerArguments,
     utils::{cancel_guard::CancelGuard, py_error},
 };
 
+/// Enough calls for V8 to optimize a typical render function.
+const DEFAULT_WARMUP_ITERATIONS: usize = 100;
+
 #[pyclass(name = "SyncRuntime", module = "belgie._core")]
 #[derive(Debug)]
 pub struct PySyncRuntime {
@@ -33,6 +42,8 @@ pub struct PyAsyncRuntime {
 pub struct PySyncRunner {
     handle: DenoExecutionHandle,
     description: String,
+    /// Tags this view's calls, their console messages, and their JavaScript errors.
+    request_id: Option<String>,
 }
 
 #[pyclass(name = "AsyncRunner", module = "belgie._core")]
@@ -40,6 +51,19 @@ pub struct PySyncRunner {
 pub struct PyAsyncRunner {
     handle: DenoExecutionHandle,
     description: String,
+    request_id: Option<String>,
+}
+
+#[pyclass(name = "SyncRunnerPool", module = "belgie._core")]
+#[derive(Debug)]
+pub struct PySyncRunnerPool {
+    pool: RunnerPool,
+}
+
+#[pyclass(name = "AsyncRunnerPool", module = "belgie._core")]
+#[derive(Debug)]
+pub struct PyAsyncRunnerPool {
+    pool: Arc<RunnerPool>,
 }
 
 #[pyclass(name = "SyncCommandRunner", module = "belgie._core")]
@@ -105,6 +129,123 @@ impl PySyncRuntime {
         }
     }
 
+    fn register(&self, name: String, function: &Bound<'_, PyAny>) -> PyResult<()> {
+        register_host_function(&self.session, name, function, None)
+    }
+
+    /// Evaluates one expression; `{a: 1}` reads as an object literal. With `this`, the
+    /// converted value is the expression's receiver. Expressions share the runtime's scratch
+    /// runner, so setup runs once and globals they assign stay for the next `eval`; one that
+    /// imports a module, or needs TSX, gets a runner of its own. With `isolate`, the expression
+    /// runs in a new context of the scratch runner, without its globals, setup, or `host`.
+    #[pyo3(signature = (expression, *, this = None, isolate = false))]
+    fn eval(
+        &self,
+        py: Python<'_>,
+        expression: &str,
+        this: Option<&Bound<'_, PyAny>>,
+        isolate: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let (target, arguments) = eval_target(py, expression, this, isolate)?;
+        let runner = borrow_runner(py, &self.session, target)?;
+        let value = executor::execute_sync(py, runner.handle(), arguments);
+        let _ = py.detach(|| self.session.return_runner(runner));
+        value
+    }
+
+    /// Binds a runner whose entry point is the given function expression, such as
+    /// `props => render(props)`; the function is compiled once and reused for every call.
+    fn define(&self, expression: &str) -> PyResult<PySyncRunner> {
+        let source = function_script(expression);
+        let description = source.description();
+        let handle = RuntimeSession::bind_script(&self.session, source)
+            .map_err(py_error::from_binding_error)?;
+        Ok(PySyncRunner::from_handle(handle, description))
+    }
+
+    /// Runs setup on the scratch runner `eval` and `iterate` share and raises its error, so a
+    /// broken setup fails at startup and a working one is not run again by the next `eval`.
+    fn ready(&self, py: Python<'_>) -> PyResult<()> {
+        let runner = borrow_runner(py, &self.session, None)?;
+        let result = executor::ready_sync(py, runner.handle());
+        let _ = py.detach(|| self.session.return_runner(runner));
+        result
+    }
+
+    /// Streams each value an async (or sync) iterable expression yields, from the scratch runner
+    /// `eval` uses.
+    fn iterate(&self, py: Python<'_>, expression: &str) -> PyResult<PySyncStream> {
+        let (target, arguments) = iterate_target(py, expression)?;
+        let runner = borrow_runner(py, &self.session, target)?;
+        let stream = py.detach(|| runner.handle().stream_blocking(arguments));
+        match stream {
+            Ok(stream) => Ok(PySyncStream::new(stream).owning(runner)),
+            Err(error) => {
+                let _ = py.detach(|| self.session.return_runner(runner));
+                Err(py_error::from_binding_error(error))
+            }
+        }
+    }
+
+    #[pyo3(signature = (script, *, size = None))]
+    fn pool(
+        &self,
+        py: Python<'_>,
+        script: PyRef<'_, PyScript>,
+        size: Option<usize>,
+    ) -> PyResult<PySyncRunnerPool> {
+        let source = script.source();
+        let size = pool_size(size)?;
+        let pool = py
+            .detach(|| RunnerPool::new(&self.session, source, size))
+            .map_err(py_error::from_binding_error)?;
+        Ok(PySyncRunnerPool { pool })
+    }
+
+    /// Runs a `Script.from_file` script now and again after every change to the file.
+    #[pyo3(signature = (script, callback, *args, **kwargs))]
+    fn watch(
+        &self,
+        py: Python<'_>,
+        script: PyRef<'_, PyScript>,
+        callback: &Bound<'_, PyAny>,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<PyScriptWatcher> {
+        watch::watch_script(py, &self.session, script.source(), callback, args, kwargs)
+    }
+
+    #[getter]
+    fn state(&self) -> PyRuntimeState {
+        PyRuntimeState::new(self.session.state().clone())
+    }
+
+    fn clear_module_cache(&self) {
+        self.session.clear_module_cache();
+    }
+
+    /// Terminates whatever script call is running, from any thread.
+    fn interrupt(&self) -> bool {
+        self.session.interrupt() > 0
+    }
+
+    /// Calls and total time per op across this runtime's runners.
+    fn op_metrics(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        self.session
+            .op_metrics()
+            .map_err(py_error::from_binding_error)?
+            .to_py(py)
+    }
+
+    fn is_alive(&self) -> bool {
+        self.session.is_active()
+    }
+
+    fn close(&self, py: Python<'_>) -> PyResult<()> {
+        py.detach(|| self.session.close_blocking())
+            .map_err(py_error::from_binding_error)
+    }
+
     fn __repr__(&self) -> String {
         format!("SyncRuntime({})", self.session.description())
     }
@@ -130,6 +271,126 @@ impl PyAsyncRuntime {
         }
     }
 
+    fn register(&self, py: Python<'_>, name: String, function: &Bound<'_, PyAny>) -> PyResult<()> {
+        let event_loop = py
+            .import("asyncio")?
+            .call_method0("get_running_loop")
+            .ok()
+            .map(Bound::unbind);
+        register_host_function(&self.session, name, function, event_loop)
+    }
+
+    #[pyo3(signature = (expression, *, this = None, isolate = false))]
+    fn eval<'py>(
+        &self,
+        py: Python<'py>,
+        expression: &str,
+        this: Option<&Bound<'py, PyAny>>,
+        isolate: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let (target, arguments) = eval_target(py, expression, this, isolate)?;
+        let runner = borrow_runner(py, &self.session, target)?;
+        let session = self.session.clone();
+        into_coroutine(py, async move {
+            let value = executor::execute_async(runner.handle().clone(), arguments).await;
+            let _ = tokio::task::spawn_blocking(move || session.return_runner(runner)).await;
+            value
+        })
+    }
+
+    fn define(&self, expression: &str) -> PyResult<PyAsyncRunner> {
+        let source = function_script(expression);
+        let description = source.description();
+        let handle = RuntimeSession::bind_script(&self.session, source)
+            .map_err(py_error::from_binding_error)?;
+        Ok(PyAsyncRunner::from_handle(handle, description))
+    }
+
+    fn ready<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let runner = borrow_runner(py, &self.session, None)?;
+        let session = self.session.clone();
+        into_coroutine(py, async move {
+            let result = executor::ready_async(runner.handle().clone()).await;
+            let _ = tokio::task::spawn_blocking(move || session.return_runner(runner)).await;
+            result
+        })
+    }
+
+    fn iterate(&self, py: Python<'_>, expression: &str) -> PyResult<PyAsyncStream> {
+        let (target, arguments) = iterate_target(py, expression)?;
+        let runner = borrow_runner(py, &self.session, target)?;
+        Ok(PyAsyncStream::owning(runner, arguments))
+    }
+
+    #[pyo3(signature = (script, *, size = None))]
+    fn pool(
+        &self,
+        py: Python<'_>,
+        script: PyRef<'_, PyScript>,
+        size: Option<usize>,
+    ) -> PyResult<PyAsyncRunnerPool> {
+        let source = script.source();
+        let size = pool_size(size)?;
+        let pool = py
+            .detach(|| RunnerPool::new(&self.session, source, size))
+            .map_err(py_error::from_binding_error)?;
+        Ok(PyAsyncRunnerPool {
+            pool: Arc::new(pool),
+        })
+    }
+
+    /// The callback runs on the watch thread, not the event loop.
+    #[pyo3(signature = (script, callback, *args, **kwargs))]
+    fn watch(
+        &self,
+        py: Python<'_>,
+        script: PyRef<'_, PyScript>,
+        callback: &Bound<'_, PyAny>,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<PyScriptWatcher> {
+        watch::watch_script(py, &self.session, script.source(), callback, args, kwargs)
+    }
+
+    #[getter]
+    fn state(&self) -> PyRuntimeState {
+        PyRuntimeState::new(self.session.state().clone())
+    }
+
+    fn clear_module_cache(&self) {
+        self.session.clear_module_cache();
+    }
+
+    fn interrupt(&self) -> bool {
+        self.session.interrupt() > 0
+    }
+
+    fn op_metrics(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        self.session
+            .op_metrics()
+            .map_err(py_error::from_binding_error)?
+            .to_py(py)
+    }
+
+    fn is_alive(&self) -> bool {
+        self.session.is_active()
+    }
+
+    fn close<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let session = self.session.clone();
+        into_coroutine(py, async move {
+            tokio::task::spawn_blocking(move || session.close_blocking())
+                .await
+                .map_err(|error| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Belgie runtime close task failed: {error}"
+                    ))
+                })?
+                .map_err(py_error::from_binding_error)?;
+            Ok(Python::attach(|py| py.None()))
+        })
+    }
+
     fn __repr__(&self) -> String {
         format!("AsyncRuntime({})", self.session.description())
     }
@@ -144,7 +405,150 @@ impl PySyncRunner {
         args: &Bound<'_, PyTuple>,
         kwargs: Option<&Bound<'_, PyDict>>,
     ) -> PyResult<Py<PyAny>> {
-        executor::execute_sync(py, &self.handle, RunnerArguments::from_py(args, kwargs)?)
+        executor::execute_sync(py, &self.handle, self.call_arguments(args, kwargs)?)
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    fn timed(
+        &self,
+        py: Python<'_>,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        executor::execute_timed_sync(py, &self.handle, self.call_arguments(args, kwargs)?)
+    }
+
+    /// Calls the runner and returns `(result, diagnostics)`, the values passed to `host.warn`.
+    #[pyo3(signature = (*args, **kwargs))]
+    fn diagnose(
+        &self,
+        py: Python<'_>,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        executor::execute_diagnosed_sync(py, &self.handle, self.call_arguments(args, kwargs)?)
+    }
+
+    /// Calls the runner and returns its result as MessagePack `bytes`.
+    #[pyo3(signature = (*args, **kwargs))]
+    fn msgpack(
+        &self,
+        py: Python<'_>,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        executor::execute_msgpack_sync(py, &self.handle, self.call_arguments(args, kwargs)?)
+    }
+
+    fn batch(&self, py: Python<'_>, calls: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        executor::execute_batch_sync(py, &self.handle, self.batch_call_arguments(calls)?)
+    }
+
+    /// Calls the runner `iterations` times on its worker and discards the results, so V8 has
+    /// compiled the hot paths before real traffic arrives.
+    #[pyo3(signature = (*args, iterations = DEFAULT_WARMUP_ITERATIONS, **kwargs))]
+    fn warmup(
+        &self,
+        py: Python<'_>,
+        args: &Bound<'_, PyTuple>,
+        iterations: usize,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<()> {
+        let iterations = warmup_iterations(iterations)?;
+        executor::warmup_sync(
+            py,
+            &self.handle,
+            self.call_arguments(args, kwargs)?,
+            iterations,
+        )
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    fn stream(
+        &self,
+        py: Python<'_>,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<PySyncStream> {
+        let arguments = self.call_arguments(args, kwargs)?;
+        let stream = py
+            .detach(|| self.handle.stream_blocking(arguments))
+            .map_err(py_error::from_binding_error)?;
+        Ok(PySyncStream::new(stream))
+    }
+
+    /// Waits until setup has run, without loading the script module.
+    fn ready(&self, py: Python<'_>) -> PyResult<()> {
+        executor::ready_sync(py, &self.handle)
+    }
+
+    fn load(&self, py: Python<'_>) -> PyResult<()> {
+        executor::load_sync(py, &self.handle)
+    }
+
+    /// Returns the module's default export, or `None` when it has none.
+    fn evaluate(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        executor::evaluate_sync(py, &self.handle)
+    }
+
+    fn drain_logs(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        executor::drain_logs_sync(py, &self.handle)
+    }
+
+    fn drain_unhandled_rejections(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        executor::drain_rejections_sync(py, &self.handle)
+    }
+
+    /// Maps each global the script or its setup defined to its `typeof`.
+    #[pyo3(signature = (*, include_builtins = false))]
+    fn globals(&self, py: Python<'_>, include_builtins: bool) -> PyResult<Py<PyAny>> {
+        executor::globals_sync(py, &self.handle, include_builtins)
+    }
+
+    fn heap_stats(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        executor::heap_stats_sync(py, &self.handle)
+    }
+
+    /// Starts a V8 CPU profile covering every call until `stop_profiling`.
+    fn start_profiling(&self, py: Python<'_>) -> PyResult<()> {
+        executor::start_profiling_sync(py, &self.handle)
+    }
+
+    /// Returns the profile as `.cpuprofile` JSON for Chrome DevTools.
+    fn stop_profiling(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        executor::stop_profiling_sync(py, &self.handle)
+    }
+
+    /// Returns the isolate's heap as `.heapsnapshot` JSON for Chrome DevTools.
+    fn heap_snapshot(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        executor::heap_snapshot_sync(py, &self.handle)
+    }
+
+    /// Returns the script's own globals as bytes for `Runtime(restore=...)`.
+    fn capture_state(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        executor::capture_state_sync(py, &self.handle)
+    }
+
+    fn reset(&self, py: Python<'_>) -> PyResult<()> {
+        executor::reset_sync(py, &self.handle)
+    }
+
+    fn is_alive(&self) -> bool {
+        self.handle.is_alive()
+    }
+
+    /// The same runner, tagging each call with `request_id` for logs and errors.
+    fn with_request_id(&self, request_id: String) -> Self {
+        Self {
+            handle: self.handle.clone(),
+            description: self.description.clone(),
+            request_id: Some(request_id),
+        }
+    }
+
+    #[getter]
+    fn request_id(&self) -> Option<String> {
+        self.request_id.clone()
     }
 
     fn __repr__(&self) -> String {
@@ -162,11 +566,187 @@ impl PyAsyncRunner {
         kwargs: Option<&Bound<'py, PyDict>>,
     ) -> PyResult<Bound<'py, PyAny>> {
         let handle = self.handle.clone();
-        let arguments = RunnerArguments::from_py(args, kwargs)?;
-        let awaitable = pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let arguments = self.call_arguments(args, kwargs)?;
+        into_coroutine(py, async move {
             executor::execute_async(handle, arguments).await
-        })?;
-        as_coroutine(py, awaitable)
+        })
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    fn timed<'py>(
+        &self,
+        py: Python<'py>,
+        args: &Bound<'py, PyTuple>,
+        kwargs: Option<&Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        let arguments = self.call_arguments(args, kwargs)?;
+        into_coroutine(py, async move {
+            executor::execute_timed_async(handle, arguments).await
+        })
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    fn diagnose<'py>(
+        &self,
+        py: Python<'py>,
+        args: &Bound<'py, PyTuple>,
+        kwargs: Option<&Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        let arguments = self.call_arguments(args, kwargs)?;
+        into_coroutine(py, async move {
+            executor::execute_diagnosed_async(handle, arguments).await
+        })
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    fn msgpack<'py>(
+        &self,
+        py: Python<'py>,
+        args: &Bound<'py, PyTuple>,
+        kwargs: Option<&Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        let arguments = self.call_arguments(args, kwargs)?;
+        into_coroutine(py, async move {
+            executor::execute_msgpack_async(handle, arguments).await
+        })
+    }
+
+    fn batch<'py>(
+        &self,
+        py: Python<'py>,
+        calls: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        let calls = self.batch_call_arguments(calls)?;
+        into_coroutine(py, async move {
+            executor::execute_batch_async(handle, calls).await
+        })
+    }
+
+    #[pyo3(signature = (*args, iterations = DEFAULT_WARMUP_ITERATIONS, **kwargs))]
+    fn warmup<'py>(
+        &self,
+        py: Python<'py>,
+        args: &Bound<'py, PyTuple>,
+        iterations: usize,
+        kwargs: Option<&Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let iterations = warmup_iterations(iterations)?;
+        let handle = self.handle.clone();
+        let arguments = self.call_arguments(args, kwargs)?;
+        into_coroutine(py, async move {
+            executor::warmup_async(handle, arguments, iterations).await
+        })
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    fn stream(
+        &self,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<PyAsyncStream> {
+        Ok(PyAsyncStream::new(
+            self.handle.clone(),
+            self.call_arguments(args, kwargs)?,
+        ))
+    }
+
+    fn ready<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        into_coroutine(py, async move { executor::ready_async(handle).await })
+    }
+
+    fn load<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        into_coroutine(py, async move { executor::load_async(handle).await })
+    }
+
+    fn evaluate<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        into_coroutine(py, async move { executor::evaluate_async(handle).await })
+    }
+
+    fn drain_logs<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        into_coroutine(py, async move { executor::drain_logs_async(handle).await })
+    }
+
+    fn drain_unhandled_rejections<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        into_coroutine(
+            py,
+            async move { executor::drain_rejections_async(handle).await },
+        )
+    }
+
+    #[pyo3(signature = (*, include_builtins = false))]
+    fn globals<'py>(&self, py: Python<'py>, include_builtins: bool) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        into_coroutine(py, async move {
+            executor::globals_async(handle, include_builtins).await
+        })
+    }
+
+    fn heap_stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        into_coroutine(py, async move { executor::heap_stats_async(handle).await })
+    }
+
+    fn start_profiling<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        into_coroutine(
+            py,
+            async move { executor::start_profiling_async(handle).await },
+        )
+    }
+
+    fn stop_profiling<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        into_coroutine(
+            py,
+            async move { executor::stop_profiling_async(handle).await },
+        )
+    }
+
+    fn heap_snapshot<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        into_coroutine(
+            py,
+            async move { executor::heap_snapshot_async(handle).await },
+        )
+    }
+
+    fn capture_state<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        into_coroutine(
+            py,
+            async move { executor::capture_state_async(handle).await },
+        )
+    }
+
+    fn reset<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let handle = self.handle.clone();
+        into_coroutine(py, async move { executor::reset_async(handle).await })
+    }
+
+    fn is_alive(&self) -> bool {
+        self.handle.is_alive()
+    }
+
+    fn with_request_id(&self, request_id: String) -> Self {
+        Self {
+            handle: self.handle.clone(),
+            description: self.description.clone(),
+            request_id: Some(request_id),
+        }
+    }
+
+    #[getter]
+    fn request_id(&self) -> Option<String> {
+        self.request_id.clone()
     }
 
     fn __repr__(&self) -> String {
@@ -174,6 +754,74 @@ impl PyAsyncRunner {
     }
 }
 
+#[pymethods]
+impl PySyncRunnerPool {
+    #[pyo3(signature = (*args, **kwargs))]
+    fn __call__(
+        &self,
+        py: Python<'_>,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        let arguments = RunnerArguments::from_py(args, kwargs)?;
+        let lease = py
+            .detach(|| self.pool.acquire())
+            .map_err(py_error::from_binding_error)?;
+        executor::execute_sync(py, lease.handle(), arguments)
+    }
+
+    #[getter]
+    fn size(&self) -> usize {
+        self.pool.size()
+    }
+
+    fn is_alive(&self) -> bool {
+        self.pool.is_alive()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SyncRunnerPool({})", self.pool.description())
+    }
+}
+
+#[pymethods]
+impl PyAsyncRunnerPool {
+    #[pyo3(signature = (*args, **kwargs))]
+    fn __call__<'py>(
+        &self,
+        py: Python<'py>,
+        args: &Bound<'py, PyTuple>,
+        kwargs: Option<&Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let pool = self.pool.clone();
+        let arguments = RunnerArguments::from_py(args, kwargs)?;
+        into_coroutine(py, async move {
+            let lease = tokio::task::spawn_blocking(move || pool.acquire())
+                .await
+                .map_err(|error| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Belgie runner pool task failed: {error}"
+                    ))
+                })?
+                .map_err(py_error::from_binding_error)?;
+            executor::execute_async(lease.handle().clone(), arguments).await
+        })
+    }
+
+    #[getter]
+    fn size(&self) -> usize {
+        self.pool.size()
+    }
+
+    fn is_alive(&self) -> bool {
+        self.pool.is_alive()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("AsyncRunnerPool({})", self.pool.description())
+    }
+}
+
 #[pymethods]
 impl PySyncCommandRunner {
     #[pyo3(signature = (*args))]
@@ -204,7 +852,7 @@ impl PyAsyncCommandRunner {
         let handle =
             RuntimeSession::start_command(self.session.clone(), self.command.clone(), argv)
                 .map_err(py_error::from_binding_error)?;
-        let awaitable = pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        into_coroutine(py, async move {
             let mut guard = CancelGuard::new(handle);
             guard
                 .get()
@@ -213,8 +861,7 @@ impl PyAsyncCommandRunner {
                 .map_err(py_error::from_binding_error)?;
             guard.disarm();
             Ok(Python::attach(|py| py.None()))
-        })?;
-        as_coroutine(py, awaitable)
+        })
     }
 
     fn __repr__(&self) -> String {
@@ -239,8 +886,24 @@ impl PySyncRunner {
         Self {
             handle,
             description,
+            request_id: None,
         }
     }
+
+    fn call_arguments(
+        &self,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<RunnerArguments> {
+        Ok(RunnerArguments::from_py(args, kwargs)?.with_request_id(self.request_id.clone()))
+    }
+
+    fn batch_call_arguments(&self, calls: &Bound<'_, PyAny>) -> PyResult<Vec<RunnerArguments>> {
+        Ok(batch_arguments(calls)?
+            .into_iter()
+            .map(|arguments| arguments.with_request_id(self.request_id.clone()))
+            .collect())
+    }
 }
 
 impl PyAsyncRunner {
@@ -248,8 +911,24 @@ impl PyAsyncRunner {
         Self {
             handle,
             description,
+            request_id: None,
         }
     }
+
+    fn call_arguments(
+        &self,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<RunnerArguments> {
+        Ok(RunnerArguments::from_py(args, kwargs)?.with_request_id(self.request_id.clone()))
+    }
+
+    fn batch_call_arguments(&self, calls: &Bound<'_, PyAny>) -> PyResult<Vec<RunnerArguments>> {
+        Ok(batch_arguments(calls)?
+            .into_iter()
+            .map(|arguments| arguments.with_request_id(self.request_id.clone()))
+            .collect())
+    }
 }
 
 impl PySyncCommandRunner {
@@ -264,6 +943,236 @@ impl PyAsyncCommandRunner {
     }
 }
 
+fn register_host_function(
+    session: &RuntimeSession,
+    name: String,
+    function: &Bound<'_, PyAny>,
+    event_loop: Option<Py<PyAny>>,
+) -> PyResult<()> {
+    if !is_host_function_name(&name) {
+        return Err(PyValueError::new_err(format!(
+            "Host function name must be a JavaScript identifier, got {name:?}"
+        )));
+    }
+    if name == EMIT_FUNCTION {
+        return Err(PyValueError::new_err(
+            "host.emit is reserved for streaming and cannot be registered",
+        ));
+    }
+    if name == WARN_FUNCTION {
+        return Err(PyValueError::new_err(
+            "host.warn is reserved for diagnostics and cannot be registered",
+        ));
+    }
+    if !function.is_callable() {
+        return Err(PyTypeError::new_err(format!(
+            "Host function {name} must be callable"
+        )));
+    }
+    session
+        .register_host_function(
+            name,
+            HostFunction::new(function.clone().unbind(), event_loop),
+        )
+        .map_err(py_error::from_binding_error)
+}
+
+fn is_host_function_name(name: &str) -> bool {
+    let mut characters = name.chars();
+    characters
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '$')
+        && characters.all(|character| {
+            character.is_ascii_alphanumeric() || character == '_' || character == '$'
+        })
+}
+
+/// The scratch runner's entry point: it evaluates the function source it is given and calls it
+/// on the receiver, if any. The indirect `eval` runs that source in global scope, as a module of
+/// its own would, so it never sees this module's bindings; an isolated one runs it in a new
+/// context instead.
+fn scratch_script() -> ScriptSource {
+    ScriptSource::from_options(ScriptOptions::inline(
+        "export default (source, isolated, ...receiver) => (isolated ? globalThis[Symbol.for(\"@belgie/eval/isolated\")] : (0, eval))(source).call(...receiver);\n"
+            .to_string(),
+    ))
+}
+
+/// Takes the scratch runner, or binds `script` to a runner of its own when the code cannot be
+/// evaluated there.
+fn borrow_runner(
+    py: Python<'_>,
+    session: &Arc<RuntimeSession>,
+    script: Option<ScriptSource>,
+) -> PyResult<BorrowedRunner> {
+    py.detach(|| match script {
+        None => RuntimeSession::take_scratch(session, scratch_script),
+        Some(script) => RuntimeSession::bind_throwaway(session, script),
+    })
+    .map_err(py_error::from_binding_error)
+}
+
+/// The scratch runner's arguments for `function_source`, or `None` when it must have a module
+/// of its own.
+fn scratch_arguments(
+    py: Python<'_>,
+    function_source: &str,
+    isolated: bool,
+    this: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Option<RunnerArguments>> {
+    let Some(source) =
+        transpile_eval_source(function_source).map_err(py_error::from_binding_error)?
+    else {
+        return Ok(None);
+    };
+    let mut arguments = vec![
+        source.into_pyobject(py)?.into_any(),
+        isolated.into_pyobject(py)?.to_owned().into_any(),
+    ];
+    arguments.extend(this.cloned());
+    RunnerArguments::from_py(&PyTuple::new(py, arguments)?, None).map(Some)
+}
+
+/// Parenthesizes the expression so a leading `{` opens an object literal instead of a block.
+/// The newlines keep a trailing line comment from swallowing the closing parenthesis.
+fn expression_script(expression: &str) -> ScriptSource {
+    let expression = expression.trim().trim_end_matches(';').trim_end();
+    ScriptSource::from_options(ScriptOptions::inline(format!(
+        "export default () => (\n{expression}\n);\n"
+    )))
+}
+
+/// Wraps the expression in a non-arrow function and calls it on the receiver, which arrives as
+/// the script's only argument.
+fn receiver_script(expression: &str) -> ScriptSource {
+    let expression = expression.trim().trim_end_matches(';').trim_end();
+    ScriptSource::from_options(ScriptOptions::inline(format!(
+        "export default (receiver) => function () {{\n  return (\n{expression}\n);\n}}.call(receiver);\n"
+    )))
+}
+
+/// The same wrapper as `receiver_script`, as a strict function the scratch runner calls.
+fn eval_function(expression: &str) -> String {
+    let expression = expression.trim().trim_end_matches(';').trim_end();
+    format!("(function () {{\n  \"use strict\";\n  return (\n{expression}\n);\n}});\n")
+}
+
+/// The script to bind for `expression`, `None` for the scratch runner, and the call's arguments.
+/// A runner of its own is isolated already, so `isolate` only changes how the scratch runner
+/// evaluates.
+fn eval_target(
+    py: Python<'_>,
+    expression: &str,
+    this: Option<&Bound<'_, PyAny>>,
+    isolate: bool,
+) -> PyResult<(Option<ScriptSource>, RunnerArguments)> {
+    if let Some(arguments) = scratch_arguments(py, &eval_function(expression), isolate, this)? {
+        return Ok((None, arguments));
+    }
+    match this {
+        None => Ok((
+            Some(expression_script(expression)),
+            RunnerArguments::default(),
+        )),
+        Some(this) => Ok((
+            Some(receiver_script(expression)),
+            RunnerArguments::from_py(&PyTuple::new(py, [this])?, None)?,
+        )),
+    }
+}
+
+/// Exports the function expression itself, so each call is a direct call of the stored function.
+fn function_script(expression: &str) -> ScriptSource {
+    let expression = expression.trim().trim_end_matches(';').trim_end();
+    ScriptSource::from_options(ScriptOptions::inline(format!(
+        "export default (\n{expression}\n);\n"
+    )))
+}
+
+/// Drives the iterable with `for await`, so closing the stream early runs the generator's `finally`.
+fn iterator_script(expression: &str) -> ScriptSource {
+    ScriptSource::from_options(ScriptOptions::inline(format!(
+        "export default async () => {{\n{}}};\n",
+        iterator_body(expression)
+    )))
+}
+
+/// `iterator_script` as a strict async function the scratch runner calls.
+fn iterator_function(expression: &str) -> String {
+    format!(
+        "(async function () {{\n  \"use strict\";\n{}}});\n",
+        iterator_body(expression)
+    )
+}
+
+fn iterate_target(
+    py: Python<'_>,
+    expression: &str,
+) -> PyResult<(Option<ScriptSource>, RunnerArguments)> {
+    match scratch_arguments(py, &iterator_function(expression), false, None)? {
+        Some(arguments) => Ok((None, arguments)),
+        None => Ok((
+            Some(iterator_script(expression)),
+            RunnerArguments::default(),
+        )),
+    }
+}
+
+fn iterator_body(expression: &str) -> String {
+    let expression = expression.trim().trim_end_matches(';').trim_end();
+    format!(
+        r#"  let iterable = (
+{expression}
+);
+  if (typeof iterable?.[Symbol.asyncIterator] !== "function" && typeof iterable?.[Symbol.iterator] !== "function") {{
+    if (typeof iterable?.next !== "function") {{
+      throw new TypeError("iterate() expects an expression that yields an async iterator");
+    }}
+    const iterator = iterable;
+    iterable = {{ [Symbol.asyncIterator]: () => iterator }};
+  }}
+  for await (const value of iterable) {{
+    host.{EMIT_FUNCTION}(value);
+  }}
+"#
+    )
+}
+
+/// Defaults to one worker per available CPU.
+fn pool_size(size: Option<usize>) -> PyResult<usize> {
+    match size {
+        Some(0) => Err(PyValueError::new_err("Runner pool size must be at least 1")),
+        Some(size) => Ok(size),
+        None => Ok(std::thread::available_parallelism().map_or(1, usize::from)),
+    }
+}
+
+fn warmup_iterations(iterations: usize) -> PyResult<usize> {
+    if iterations == 0 {
+        return Err(PyValueError::new_err(
+            "Warmup iterations must be at least 1",
+        ));
+    }
+    Ok(iterations)
+}
+
+/// Each call is a tuple of positional arguments, so `[(1,), (2, "x")]` makes two calls.
+fn batch_arguments(calls: &Bound<'_, PyAny>) -> PyResult<Vec<RunnerArguments>> {
+    calls
+        .try_iter()?
+        .enumerate()
+        .map(|(index, call)| {
+            let call = call?;
+            let args = call.cast::<PyTuple>().map_err(|_| {
+                PyTypeError::new_err(format!(
+                    "Batch call {index} must be a tuple of positional arguments"
+                ))
+            })?;
+            RunnerArguments::from_py(args, None)
+        })
+        .collect()
+}
+
 fn command_arguments(args: &Bound<'_, PyTuple>) -> PyResult<Vec<String>> {
     args.iter()
         .enumerate()
@@ -277,8 +1186,24 @@ fn command_arguments(args: &Bound<'_, PyTuple>) -> PyResult<Vec<String>> {
         .collect()
 }
 
-fn as_coroutine<'py>(py: Python<'py>, awaitable: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+/// Wraps `future` in a coroutine that hands it to Tokio only once it is awaited, so the result
+/// is delivered to the loop that awaits it: a loop started after the call, as in
+/// `asyncio.run(runner())`, or any asyncio-compatible loop such as uvloop.
+pub(crate) fn into_coroutine<'py, F, T>(py: Python<'py>, future: F) -> PyResult<Bound<'py, PyAny>>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: for<'a> IntoPyObject<'a> + Send + 'static,
+{
+    let future = std::sync::Mutex::new(Some(future));
+    let start = PyCFunction::new_closure(py, None, None, move |args, _kwargs| {
+        let future = future
+            .lock()
+            .expect("coroutine future lock should not be poisoned")
+            .take()
+            .ok_or_else(|| PyRuntimeError::new_err("cannot reuse already awaited coroutine"))?;
+        pyo3_async_runtimes::tokio::future_into_py(args.py(), future).map(Bound::unbind)
+    })?;
     py.import("belgie._awaitable")?
         .getattr("as_coroutine")?
-        .call1((awaitable,))
+        .call1((start,))
 }
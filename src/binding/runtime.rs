@@ -1,27 +1,35 @@
 use std::{
+    collections::BTreeMap,
     path::PathBuf,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use pyo3::{
     Borrowed, Bound, FromPyObject, PyAny, PyErr, PyResult, Python,
     exceptions::{PyBaseException, PyTypeError, PyValueError},
     prelude::*,
-    types::{PyTraceback, PyType},
+    types::{PyBytes, PyTraceback, PyType},
 };
 
 use crate::{
     binding::{
-        PyAsyncEnvironment, PyAsyncRuntime, PyEnvironment, PySyncEnvironment, PySyncRuntime,
-        normalize,
+        PyAsyncEnvironment, PyAsyncRuntime, PyEnvironment, PyRuntimeState, PySyncEnvironment,
+        PySyncRuntime, normalize,
     },
     environment::SharedEnvironment,
     exceptions::BelgieRuntimeError,
     options::{
+        BuiltinExtension, CpuAffinity, Determinism, ExecutionOptions, InspectorOptions,
         JsRuntimeOptions, RuntimeEnvironment, RuntimeOptions as InternalRuntimeOptions,
         RuntimePermissionOptions, RuntimeWorkerOptions,
     },
-    runtime::{DenoRuntime, RuntimeSession},
+    runtime::{
+        DenoRuntime, RuntimeSession, WebStorage, captured_state, cpu_affinity, cpu_budget,
+        startup_snapshot,
+    },
+    script,
+    types::value::Unserializable,
     utils::{normalize_path, py_error},
 };
 
@@ -41,6 +49,7 @@ pub struct PyRuntimePermissions {
 pub struct PyRuntimeOptions {
     js_runtime: JsRuntimeOptions,
     worker: RuntimeWorkerOptions,
+    execution: ExecutionOptions,
     permissions_repr: String,
     log_level_repr: Option<String>,
 }
@@ -128,9 +137,10 @@ impl PyRuntimePermissions {
 #[pymethods]
 impl PyRuntimeOptions {
     #[new]
-    #[pyo3(signature = (*, max_old_generation_size_mb = None, max_young_generation_size_mb = None, code_range_size_mb = None, permissions = None, seed = None, location = None, log_level = None, enable_testing_features = false, enable_raw_imports = false, disable_offscreen_canvas = false, trace_ops = None))]
+    #[pyo3(signature = (*, max_old_generation_size_mb = None, max_young_generation_size_mb = None, code_range_size_mb = None, permissions = None, seed = None, location = None, log_level = None, enable_testing_features = false, enable_raw_imports = false, disable_offscreen_canvas = false, trace_ops = None, timeout = None, capture_console = false, queue_size = None, inspect = None, inspect_brk = false, extensions = None, setup = None, deterministic = false, fixed_time_ms = None, max_result_bytes = None, env_vars = None, native_backtraces = false, cpu_budget = None, module_root = None, op_metrics = false, node_compat = false, on_unserializable = "error", cpu_affinity = None))]
     #[allow(clippy::too_many_arguments)]
     fn new(
+        py: Python<'_>,
         max_old_generation_size_mb: Option<i64>,
         max_young_generation_size_mb: Option<i64>,
         code_range_size_mb: Option<i64>,
@@ -142,8 +152,28 @@ impl PyRuntimeOptions {
         enable_raw_imports: bool,
         disable_offscreen_canvas: bool,
         trace_ops: Option<Vec<String>>,
+        timeout: Option<f64>,
+        capture_console: bool,
+        queue_size: Option<i64>,
+        inspect: Option<&str>,
+        inspect_brk: bool,
+        extensions: Option<Vec<String>>,
+        setup: Option<String>,
+        deterministic: bool,
+        fixed_time_ms: Option<i64>,
+        max_result_bytes: Option<i64>,
+        env_vars: Option<BTreeMap<String, String>>,
+        native_backtraces: bool,
+        cpu_budget: Option<f64>,
+        module_root: Option<PathBuf>,
+        op_metrics: bool,
+        node_compat: bool,
+        on_unserializable: &str,
+        cpu_affinity: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Self> {
         let log_level_value = normalize_log_level(log_level)?;
+        let seed = normalize_seed(seed)?;
+        let determinism = normalize_determinism(deterministic, seed, fixed_time_ms)?;
         Ok(Self {
             js_runtime: JsRuntimeOptions::new(
                 normalize_memory_size("max_old_generation_size_mb", max_old_generation_size_mb)?,
@@ -152,20 +182,39 @@ impl PyRuntimeOptions {
                     max_young_generation_size_mb,
                 )?,
                 normalize_memory_size("code_range_size_mb", code_range_size_mb)?,
-            ),
+            )
+            .with_extensions(normalize_extensions(extensions)?)
+            .with_module_root(normalize_module_root(py, module_root)?),
             worker: RuntimeWorkerOptions::new(
                 permissions
                     .as_deref()
                     .map(PyRuntimePermissions::runtime_permissions)
                     .unwrap_or_default(),
-                normalize_seed(seed)?,
+                // Deterministic mode seeds its own generator, so the Deno worker is not needed.
+                seed.filter(|_| determinism.is_none()),
                 normalize_location(location)?,
                 log_level_value.0,
                 enable_testing_features,
                 enable_raw_imports,
                 disable_offscreen_canvas,
                 trace_ops,
-            ),
+            )
+            .with_inspector(normalize_inspector(inspect, inspect_brk)?),
+            execution: ExecutionOptions::new(
+                normalize_timeout(timeout)?,
+                capture_console,
+                normalize_queue_size(queue_size)?,
+            )
+            .with_setup(setup)
+            .with_determinism(determinism)
+            .with_max_result_bytes(normalize_max_result_bytes(max_result_bytes)?)
+            .with_env_vars(normalize_env_vars(env_vars)?)
+            .with_native_backtraces(native_backtraces)
+            .with_cpu_budget(normalize_cpu_budget(cpu_budget)?)
+            .with_op_metrics(op_metrics)
+            .with_node_compat(node_compat)
+            .with_unserializable(normalize_unserializable(on_unserializable)?)
+            .with_cpu_affinity(normalize_cpu_affinity(cpu_affinity)?),
             permissions_repr: permissions
                 .as_deref()
                 .map_or_else(|| "None".to_string(), repr_permission_mode),
@@ -175,15 +224,54 @@ impl PyRuntimeOptions {
 
     fn __repr__(&self) -> String {
         format!(
-            "RuntimeOptions(max_old_generation_size_mb={:?}, max_young_generation_size_mb={:?}, code_range_size_mb={:?}, permissions={}, seed={:?}, location={:?}, log_level={:?}, disable_offscreen_canvas={:?})",
+            "RuntimeOptions(max_old_generation_size_mb={:?}, max_young_generation_size_mb={:?}, code_range_size_mb={:?}, permissions={}, seed={:?}, location={:?}, log_level={:?}, disable_offscreen_canvas={:?}, timeout={:?}, capture_console={:?}, queue_size={:?}, inspect={:?}, inspect_brk={:?}, extensions={:?}, setup={}, deterministic={:?}, fixed_time_ms={:?}, max_result_bytes={:?}, env_vars={:?}, native_backtraces={:?}, cpu_budget={:?}, module_root={:?}, op_metrics={:?}, node_compat={:?}, on_unserializable={:?}, cpu_affinity={:?})",
             self.js_runtime.max_old_generation_size_mb(),
             self.js_runtime.max_young_generation_size_mb(),
             self.js_runtime.code_range_size_mb(),
             self.permissions_repr,
-            self.worker.seed(),
+            self.worker
+                .seed()
+                .or(self.execution.determinism().map(Determinism::seed)),
             self.worker.location().map(|url| url.to_string()),
             self.log_level_repr,
             self.worker.disable_offscreen_canvas(),
+            self.execution
+                .timeout()
+                .map(|timeout| timeout.as_secs_f64()),
+            self.execution.capture_console(),
+            self.execution.queue_size(),
+            self.worker
+                .inspector()
+                .map(|inspector| inspector.address().to_string()),
+            self.worker
+                .inspector()
+                .is_some_and(|inspector| inspector.break_on_first_statement()),
+            self.js_runtime
+                .extensions()
+                .iter()
+                .map(|extension| extension.name())
+                .collect::<Vec<_>>(),
+            if self.execution.setup().is_some() {
+                "<code>"
+            } else {
+                "None"
+            },
+            self.execution.determinism().is_some(),
+            self.execution.determinism().map(Determinism::time_ms),
+            self.execution.max_result_bytes(),
+            // Names only: values are often secrets.
+            self.execution
+                .env_vars()
+                .map(|env_vars| env_vars.keys().collect::<Vec<_>>()),
+            self.execution.native_backtraces(),
+            self.execution
+                .cpu_budget()
+                .map(|cpu_budget| cpu_budget.as_secs_f64()),
+            self.js_runtime.module_root(),
+            self.execution.op_metrics(),
+            self.execution.node_compat(),
+            self.execution.unserializable().name(),
+            self.execution.cpu_affinity().map(CpuAffinity::cores),
         )
     }
 }
@@ -196,6 +284,10 @@ impl PyRuntimeOptions {
     pub(crate) fn worker_options(&self) -> RuntimeWorkerOptions {
         self.worker.clone()
     }
+
+    pub(crate) fn execution_options(&self) -> ExecutionOptions {
+        self.execution.clone()
+    }
 }
 
 impl PyRuntimePermissions {
@@ -257,11 +349,15 @@ impl FromPyObject<'_, '_> for RuntimeEnvironmentArg {
 #[pymethods]
 impl PyRuntime {
     #[new]
-    #[pyo3(signature = (*, env = None, options = None))]
+    #[pyo3(signature = (*, env = None, options = None, storage = None, name = None, restore = None, snapshot = None))]
     fn new(
         py: Python<'_>,
         env: Option<RuntimeEnvironmentArg>,
         options: Option<PyRef<'_, PyRuntimeOptions>>,
+        storage: Option<Bound<'_, PyAny>>,
+        name: Option<String>,
+        restore: Option<Bound<'_, PyBytes>>,
+        snapshot: Option<Bound<'_, PyBytes>>,
     ) -> PyResult<Self> {
         let environment = env
             .map(RuntimeEnvironmentArg::into_shared)
@@ -276,19 +372,94 @@ impl PyRuntime {
                     .to_path_buf())
             },
         )?;
-        Self::from_parts(cwd, environment, options.as_deref(), false)
+        Self::from_parts(
+            cwd,
+            environment,
+            options.as_deref(),
+            storage,
+            name,
+            restore,
+            snapshot,
+            false,
+        )
     }
 
     #[classmethod]
-    #[pyo3(signature = (path, *, options = None))]
+    #[pyo3(signature = (path, *, options = None, storage = None, name = None, restore = None, snapshot = None))]
+    #[allow(clippy::too_many_arguments)]
     fn from_folder(
         _cls: &Bound<'_, PyType>,
         py: Python<'_>,
         path: PathBuf,
         options: Option<PyRef<'_, PyRuntimeOptions>>,
+        storage: Option<Bound<'_, PyAny>>,
+        name: Option<String>,
+        restore: Option<Bound<'_, PyBytes>>,
+        snapshot: Option<Bound<'_, PyBytes>>,
     ) -> PyResult<Self> {
         let path = normalize_path::normalize_directory(py, path, "path")?;
-        Self::from_parts(path, None, options.as_deref(), true)
+        Self::from_parts(
+            path,
+            None,
+            options.as_deref(),
+            storage,
+            name,
+            restore,
+            snapshot,
+            true,
+        )
+    }
+
+    /// Runs `setup_code` as a classic script on a bare isolate and returns its heap, for
+    /// `Runtime(snapshot=...)` to start every runner from.
+    #[classmethod]
+    fn create_snapshot<'py>(
+        _cls: &Bound<'py, PyType>,
+        py: Python<'py>,
+        setup_code: String,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let snapshot = py
+            .detach(|| startup_snapshot::create(setup_code))
+            .map_err(py_error::from_binding_error)?;
+        Ok(PyBytes::new(py, &snapshot))
+    }
+
+    /// Parses inline script source without binding or running it.
+    #[staticmethod]
+    fn check(code: &str) -> PyResult<()> {
+        script::check_script(code).map_err(py_error::from_binding_error)
+    }
+
+    /// Shared with `globalThis.state` in every script this runtime runs, across sessions.
+    #[getter]
+    fn state(&self) -> PyRuntimeState {
+        PyRuntimeState::new(self.inner.state().clone())
+    }
+
+    /// Runners bound or reset afterwards re-read and re-transpile their TypeScript imports.
+    fn clear_module_cache(&self) {
+        self.inner.module_cache().clear();
+    }
+
+    /// Terminates whatever script call the active session is running, from any thread, and
+    /// reports whether one was. The runtime stays open and later calls run normally.
+    fn interrupt(&self) -> bool {
+        let state = self
+            .context_state
+            .lock()
+            .expect("runtime context state lock should not be poisoned");
+        match &*state {
+            RuntimeContextState::Active(session) => session.interrupt() > 0,
+            RuntimeContextState::Inactive | RuntimeContextState::Entering => false,
+        }
+    }
+
+    /// Calls and total time per op, across every session and runner of this runtime.
+    fn op_metrics(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        self.inner
+            .op_metrics_snapshot()
+            .map_err(py_error::from_binding_error)?
+            .to_py(py)
     }
 
     fn __enter__(&self) -> PyResult<PySyncRuntime> {
@@ -391,6 +562,198 @@ fn normalize_seed(value: Option<i64>) -> PyResult<Option<u64>> {
     normalize::normalize_non_negative_u64("seed", value)
 }
 
+/// Date values are limited to 8.64e15 ms on either side of the epoch.
+const MAX_DATE_MS: i64 = 8_640_000_000_000_000;
+
+fn normalize_determinism(
+    deterministic: bool,
+    seed: Option<u64>,
+    fixed_time_ms: Option<i64>,
+) -> PyResult<Option<Determinism>> {
+    if !deterministic {
+        return match fixed_time_ms {
+            Some(_) => Err(PyValueError::new_err(
+                "fixed_time_ms requires deterministic=True",
+            )),
+            None => Ok(None),
+        };
+    }
+    let time_ms = fixed_time_ms.unwrap_or(0);
+    if !(-MAX_DATE_MS..=MAX_DATE_MS).contains(&time_ms) {
+        return Err(PyValueError::new_err(
+            "fixed_time_ms must be within 8.64e15 ms of the epoch",
+        ));
+    }
+    Ok(Some(Determinism::new(seed.unwrap_or(0), time_ms)))
+}
+
+fn normalize_timeout(value: Option<f64>) -> PyResult<Option<Duration>> {
+    match value {
+        Some(value) if !value.is_finite() || value <= 0.0 => Err(PyValueError::new_err(
+            "timeout must be a positive number of seconds",
+        )),
+        Some(value) => Duration::try_from_secs_f64(value)
+            .map(Some)
+            .map_err(|_| PyValueError::new_err("timeout is too large")),
+        None => Ok(None),
+    }
+}
+
+/// Canonical, so symlinked roots compare equal to the canonical paths of the modules inside.
+fn normalize_module_root(py: Python<'_>, value: Option<PathBuf>) -> PyResult<Option<PathBuf>> {
+    value
+        .map(|path| {
+            let path = normalize_path::normalize_directory(py, path, "module_root")?;
+            Ok(path.canonicalize().unwrap_or(path))
+        })
+        .transpose()
+}
+
+fn normalize_cpu_budget(value: Option<f64>) -> PyResult<Option<Duration>> {
+    match value {
+        Some(_) if !cpu_budget::SUPPORTED => Err(PyValueError::new_err(
+            "cpu_budget is only supported on Linux",
+        )),
+        Some(value) if !value.is_finite() || value <= 0.0 => Err(PyValueError::new_err(
+            "cpu_budget must be a positive number of seconds",
+        )),
+        Some(value) => Duration::try_from_secs_f64(value)
+            .map(Some)
+            .map_err(|_| PyValueError::new_err("cpu_budget is too large")),
+        None => Ok(None),
+    }
+}
+
+/// One core or a sequence of them; pool workers and other runners take them in turn.
+fn normalize_cpu_affinity(value: Option<&Bound<'_, PyAny>>) -> PyResult<Option<CpuAffinity>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    if !cpu_affinity::SUPPORTED {
+        return Err(PyValueError::new_err(
+            "cpu_affinity is only supported on Linux",
+        ));
+    }
+    let cores = match value.extract::<i64>() {
+        Ok(core) => vec![core],
+        Err(_) => value.extract::<Vec<i64>>().map_err(|_| {
+            PyTypeError::new_err("cpu_affinity must be a core index or a list of them")
+        })?,
+    };
+    if cores.is_empty() {
+        return Err(PyValueError::new_err(
+            "cpu_affinity must name at least one core",
+        ));
+    }
+    let allowed = cpu_affinity::allowed_cores().unwrap_or_default();
+    let cores = cores
+        .into_iter()
+        .map(|core| {
+            usize::try_from(core)
+                .ok()
+                .filter(|core| allowed.contains(core))
+                .ok_or_else(|| {
+                    PyValueError::new_err(format!(
+                        "cpu_affinity core {core} is not available to this process; allowed cores are {allowed:?}"
+                    ))
+                })
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(Some(CpuAffinity::new(cores)))
+}
+
+fn normalize_queue_size(value: Option<i64>) -> PyResult<Option<usize>> {
+    match value {
+        Some(value) if value <= 0 => Err(PyValueError::new_err(
+            "queue_size must be a positive integer",
+        )),
+        Some(value) => usize::try_from(value)
+            .map(Some)
+            .map_err(|_| PyValueError::new_err("queue_size is too large")),
+        None => Ok(None),
+    }
+}
+
+fn normalize_unserializable(value: &str) -> PyResult<Unserializable> {
+    Unserializable::parse(value).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "on_unserializable must be one of: error, null, or stringify; got {value:?}"
+        ))
+    })
+}
+
+fn normalize_max_result_bytes(value: Option<i64>) -> PyResult<Option<usize>> {
+    match value {
+        Some(value) if value <= 0 => Err(PyValueError::new_err(
+            "max_result_bytes must be a positive integer",
+        )),
+        Some(value) => usize::try_from(value)
+            .map(Some)
+            .map_err(|_| PyValueError::new_err("max_result_bytes is too large")),
+        None => Ok(None),
+    }
+}
+
+fn normalize_env_vars(
+    env_vars: Option<BTreeMap<String, String>>,
+) -> PyResult<Option<BTreeMap<String, String>>> {
+    let Some(env_vars) = env_vars else {
+        return Ok(None);
+    };
+    for (name, value) in &env_vars {
+        if name.is_empty() || name.contains(['=', '\0']) {
+            return Err(PyValueError::new_err(format!(
+                "env_vars name {name:?} must be non-empty and contain no '=' or NUL characters"
+            )));
+        }
+        if value.contains('\0') {
+            return Err(PyValueError::new_err(format!(
+                "env_vars value for {name} must not contain NUL characters"
+            )));
+        }
+    }
+    Ok(Some(env_vars))
+}
+
+fn normalize_inspector(
+    address: Option<&str>,
+    break_on_first_statement: bool,
+) -> PyResult<Option<InspectorOptions>> {
+    match address {
+        Some(address) => address
+            .parse()
+            .map(|address| Some(InspectorOptions::new(address, break_on_first_statement)))
+            .map_err(|_| {
+                PyValueError::new_err(format!(
+                    "inspect must be a host:port socket address like 127.0.0.1:9229, got {address:?}"
+                ))
+            }),
+        None if break_on_first_statement => {
+            Err(PyValueError::new_err("inspect_brk requires inspect"))
+        }
+        None => Ok(None),
+    }
+}
+
+fn normalize_extensions(value: Option<Vec<String>>) -> PyResult<Option<Vec<BuiltinExtension>>> {
+    let Some(names) = value else {
+        return Ok(None);
+    };
+    let mut extensions = Vec::new();
+    for name in names {
+        let extension = BuiltinExtension::from_name(&name).ok_or_else(|| {
+            let supported = BuiltinExtension::ALL.map(BuiltinExtension::name).join(", ");
+            PyValueError::new_err(format!(
+                "extensions must be drawn from: {supported}; got {name:?}"
+            ))
+        })?;
+        if !extensions.contains(&extension) {
+            extensions.push(extension);
+        }
+    }
+    Ok(Some(extensions))
+}
+
 fn normalize_location(value: Option<&str>) -> PyResult<Option<url::Url>> {
     value
         .map(|value| {
@@ -433,18 +796,48 @@ fn repr_permission_mode(permissions: &PyRuntimePermissions) -> String {
 }
 
 impl PyRuntime {
+    #[allow(clippy::too_many_arguments)]
     fn from_parts(
         cwd: std::path::PathBuf,
         environment: Option<RuntimeEnvironment>,
         options: Option<&PyRuntimeOptions>,
+        storage: Option<Bound<'_, PyAny>>,
+        name: Option<String>,
+        restore: Option<Bound<'_, PyBytes>>,
+        snapshot: Option<Bound<'_, PyBytes>>,
         project: bool,
     ) -> PyResult<Self> {
+        let storage = storage.map(web_storage).transpose()?;
+        let restored_globals = restore
+            .map(|restore| captured_state::decode(restore.as_bytes()))
+            .transpose()
+            .map_err(py_error::from_binding_error)?;
+        if snapshot.is_some() && environment.is_some() {
+            return Err(PyValueError::new_err(
+                "snapshot runs scripts on the lightweight backend and cannot be combined with env",
+            ));
+        }
+        let startup_snapshot = snapshot
+            .map(|snapshot| startup_snapshot::load(snapshot.as_bytes()))
+            .transpose()
+            .map_err(py_error::from_binding_error)?;
+        if name
+            .as_deref()
+            .is_some_and(|name| name.is_empty() || name.contains('\0'))
+        {
+            return Err(PyValueError::new_err(
+                "name must be a non-empty string without NUL characters",
+            ));
+        }
         let js_runtime_options = options
             .map(PyRuntimeOptions::js_runtime_options)
             .unwrap_or_default();
         let worker_options = options
             .map(PyRuntimeOptions::worker_options)
             .unwrap_or_default();
+        let execution_options = options
+            .map(PyRuntimeOptions::execution_options)
+            .unwrap_or_default();
         if environment.is_none() && worker_options.requires_package_worker() {
             return Err(BelgieRuntimeError::new_err(
                 "Deno worker RuntimeOptions require Runtime(env=Environment(...)); Runtime() and Runtime.from_folder() only support V8 memory options",
@@ -455,8 +848,13 @@ impl PyRuntime {
                 cwd,
                 js_runtime_options,
                 worker_options,
+                execution_options,
                 environment,
-            )),
+            ))
+            .with_storage(storage)
+            .with_restored_globals(restored_globals)
+            .with_startup_snapshot(startup_snapshot)
+            .with_name(name),
             context_state: Arc::new(Mutex::new(RuntimeContextState::Inactive)),
             project,
         })
@@ -502,6 +900,20 @@ impl PyRuntime {
     }
 }
 
+/// `localStorage` writes through to `storage`, so it must accept string keys and values.
+fn web_storage(storage: Bound<'_, PyAny>) -> PyResult<WebStorage> {
+    let mutable_mapping = storage
+        .py()
+        .import("collections.abc")?
+        .getattr("MutableMapping")?;
+    if !storage.is_instance(&mutable_mapping)? {
+        return Err(PyTypeError::new_err(
+            "storage must be a mutable mapping of strings",
+        ));
+    }
+    Ok(WebStorage::new(storage.unbind()))
+}
+
 fn set_active(context_state: &Arc<Mutex<RuntimeContextState>>, session: Arc<RuntimeSession>) {
     *context_state
         .lock()
@@ -1,8 +1,18 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
-use pyo3::{Bound, PyResult, prelude::*, types::PyType};
+use pyo3::{
+    Borrowed, Bound, PyErr, PyResult,
+    exceptions::{PyTypeError, PyValueError},
+    prelude::*,
+    types::{PyDict, PyString, PyType},
+};
 
-use crate::{options::ScriptOptions, script::ScriptSource, utils::normalize_path};
+use crate::{
+    options::ScriptOptions,
+    script::{ModuleEncoding, ModuleSource, ScriptSource},
+    types::value::PyJsValue,
+    utils::normalize_path,
+};
 
 #[pyclass(name = "Script", module = "belgie._core")]
 #[derive(Debug)]
@@ -10,20 +20,112 @@ pub struct PyScript {
     source: ScriptSource,
 }
 
+/// Module source kept gzip or brotli compressed until a runner loads it.
+#[pyclass(name = "CompressedModule", module = "belgie._core", frozen)]
+#[derive(Debug)]
+pub struct PyCompressedModule {
+    source: ModuleSource,
+    encoding: ModuleEncoding,
+    size: usize,
+}
+
+/// A `modules` value: plain source text or a `CompressedModule`.
+struct ModuleSourceArg(ModuleSource);
+
+impl FromPyObject<'_, '_> for ModuleSourceArg {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+        if let Ok(source) = obj.cast::<PyString>() {
+            return Ok(Self(ModuleSource::from(source.to_str()?.to_string())));
+        }
+        if let Ok(module) = obj.extract::<PyRef<'_, PyCompressedModule>>() {
+            return Ok(Self(module.source.clone()));
+        }
+        Err(PyTypeError::new_err(
+            "Script modules must be source strings or CompressedModule values",
+        ))
+    }
+}
+
+#[pymethods]
+impl PyCompressedModule {
+    #[new]
+    fn new(data: Vec<u8>, encoding: &str) -> PyResult<Self> {
+        let encoding = ModuleEncoding::from_name(encoding).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "CompressedModule encoding must be \"gzip\" or \"br\", got {encoding:?}"
+            ))
+        })?;
+        let size = data.len();
+        Ok(Self {
+            source: ModuleSource::Compressed {
+                encoding,
+                data: data.into(),
+            },
+            encoding,
+            size,
+        })
+    }
+
+    #[getter]
+    fn encoding(&self) -> &'static str {
+        self.encoding.name()
+    }
+
+    fn __len__(&self) -> usize {
+        self.size
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "CompressedModule(encoding={:?}, {} bytes)",
+            self.encoding.name(),
+            self.size
+        )
+    }
+}
+
 #[pymethods]
 impl PyScript {
     #[new]
-    pub fn new(content: String) -> Self {
-        Self {
-            source: ScriptSource::from_options(ScriptOptions::inline(content)),
-        }
+    #[pyo3(signature = (content, *, name = None, globals = None, modules = None, imports = None))]
+    pub fn new(
+        content: String,
+        name: Option<String>,
+        globals: Option<&Bound<'_, PyDict>>,
+        modules: Option<BTreeMap<String, ModuleSourceArg>>,
+        imports: Option<BTreeMap<String, String>>,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            source: ScriptSource::from_options(
+                ScriptOptions::inline(content)
+                    .with_name(normalize_name(name)?)
+                    .with_globals(normalize_globals(globals)?)
+                    .with_modules(normalize_modules(modules)?)
+                    .with_imports(normalize_imports(imports)?),
+            ),
+        })
     }
 
     #[classmethod]
-    pub fn from_file(_cls: &Bound<'_, PyType>, py: Python<'_>, path: PathBuf) -> PyResult<Self> {
+    #[pyo3(signature = (path, *, globals = None, modules = None, imports = None))]
+    pub fn from_file(
+        _cls: &Bound<'_, PyType>,
+        py: Python<'_>,
+        path: PathBuf,
+        globals: Option<&Bound<'_, PyDict>>,
+        modules: Option<BTreeMap<String, ModuleSourceArg>>,
+        imports: Option<BTreeMap<String, String>>,
+    ) -> PyResult<Self> {
         let (path, content) = normalize_path::read_script_file(py, path)?;
         Ok(Self {
-            source: ScriptSource::from_options(ScriptOptions::from_file(content, path)),
+            source: ScriptSource::from_options(
+                ScriptOptions::from_file(content, path)
+                    .with_globals(normalize_globals(globals)?)
+                    .with_modules(normalize_modules(modules)?)
+                    .with_imports(normalize_imports(imports)?),
+            ),
         })
     }
 
@@ -47,3 +149,57 @@ impl PyScript {
         self.source.clone()
     }
 }
+
+fn normalize_globals(globals: Option<&Bound<'_, PyDict>>) -> PyResult<Option<PyJsValue>> {
+    globals
+        .map(|globals| PyJsValue::from_py(globals.as_any()))
+        .transpose()
+}
+
+fn normalize_name(name: Option<String>) -> PyResult<Option<String>> {
+    if let Some(name) = &name
+        && (name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']))
+    {
+        return Err(PyValueError::new_err(format!(
+            "Script name must be a file name without directories, got {name:?}"
+        )));
+    }
+    Ok(name)
+}
+
+fn normalize_modules(
+    modules: Option<BTreeMap<String, ModuleSourceArg>>,
+) -> PyResult<BTreeMap<String, ModuleSource>> {
+    let modules = modules.unwrap_or_default();
+    if let Some(specifier) = modules
+        .keys()
+        .find(|specifier| !specifier.starts_with("./") && !specifier.starts_with("../"))
+    {
+        return Err(PyValueError::new_err(format!(
+            "Script module specifiers must be relative (start with ./ or ../), got {specifier:?}"
+        )));
+    }
+    Ok(modules
+        .into_iter()
+        .map(|(specifier, ModuleSourceArg(source))| (specifier, source))
+        .collect())
+}
+
+fn normalize_imports(
+    imports: Option<BTreeMap<String, String>>,
+) -> PyResult<BTreeMap<String, String>> {
+    let imports = imports.unwrap_or_default();
+    for (specifier, target) in &imports {
+        if specifier.is_empty() || target.is_empty() {
+            return Err(PyValueError::new_err(format!(
+                "Script imports must map non-empty specifiers to non-empty targets, got {specifier:?}: {target:?}"
+            )));
+        }
+        if specifier.ends_with('/') && !target.ends_with('/') {
+            return Err(PyValueError::new_err(format!(
+                "Script import prefix {specifier:?} must map to a target ending in /, got {target:?}"
+            )));
+        }
+    }
+    Ok(imports)
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use pyo3::{
+    exceptions::PyKeyError,
+    prelude::*,
+    types::{PyIterator, PyList},
+};
+
+use crate::{runtime::SharedState, types::value::PyJsValue};
+
+/// `globalThis.state` seen from Python; values are copied across the JSON boundary.
+#[pyclass(name = "RuntimeState", module = "belgie._core", mapping)]
+#[derive(Debug)]
+pub struct PyRuntimeState {
+    state: Arc<SharedState>,
+}
+
+impl PyRuntimeState {
+    pub(crate) fn new(state: Arc<SharedState>) -> Self {
+        Self { state }
+    }
+}
+
+#[pymethods]
+impl PyRuntimeState {
+    fn __getitem__(&self, py: Python<'_>, key: &str) -> PyResult<Py<PyAny>> {
+        self.state
+            .get(key)
+            .ok_or_else(|| PyKeyError::new_err(key.to_string()))?
+            .to_py(py)
+    }
+
+    fn __setitem__(&self, key: String, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.state.set(key, PyJsValue::from_py(value)?);
+        Ok(())
+    }
+
+    fn __delitem__(&self, key: &str) -> PyResult<()> {
+        self.state
+            .remove(key)
+            .map(drop)
+            .ok_or_else(|| PyKeyError::new_err(key.to_string()))
+    }
+
+    fn __contains__(&self, key: &Bound<'_, PyAny>) -> bool {
+        key.extract::<String>()
+            .is_ok_and(|key| self.state.contains(&key))
+    }
+
+    fn __len__(&self) -> usize {
+        self.state.len()
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyList::new(py, self.state.keys())?.as_any().try_iter()
+    }
+
+    #[pyo3(signature = (key, default = None))]
+    fn get(&self, py: Python<'_>, key: &str, default: Option<Py<PyAny>>) -> PyResult<Py<PyAny>> {
+        match self.state.get(key) {
+            Some(value) => value.to_py(py),
+            None => Ok(default.unwrap_or_else(|| py.None())),
+        }
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.state.keys()
+    }
+
+    fn clear(&self) {
+        self.state.clear();
+    }
+
+    fn __repr__(&self) -> String {
+        format!("RuntimeState(keys={:?})", self.state.keys())
+    }
+}
@@ -0,0 +1,195 @@
+use std::sync::{Arc, Mutex};
+
+use pyo3::{
+    exceptions::{PyStopAsyncIteration, PyStopIteration},
+    prelude::*,
+};
+
+use crate::{
+    binding::runner::into_coroutine,
+    runtime::{BorrowedRunner, DenoExecutionHandle, InvocationStream},
+    types::runner::RunnerArguments,
+    utils::py_error,
+};
+
+/// Chunks a call passes to `host.emit`, yielded as they are produced.
+#[pyclass(name = "SyncStream", module = "belgie._core")]
+#[derive(Debug)]
+pub struct PySyncStream {
+    stream: Mutex<Option<InvocationStream>>,
+    /// A runner borrowed from the runtime for this stream, returned once the stream ends or is
+    /// dropped.
+    runner: Mutex<Option<BorrowedRunner>>,
+}
+
+#[pyclass(name = "AsyncStream", module = "belgie._core")]
+#[derive(Debug)]
+pub struct PyAsyncStream {
+    state: Arc<tokio::sync::Mutex<AsyncStreamState>>,
+}
+
+/// The call is queued on the first `__anext__`, so creating the stream never blocks.
+#[derive(Debug)]
+struct AsyncStreamState {
+    pending: Option<(DenoExecutionHandle, RunnerArguments)>,
+    stream: Option<InvocationStream>,
+    /// A runner borrowed from the runtime for this stream, returned once the stream ends or is
+    /// dropped.
+    runner: Option<BorrowedRunner>,
+}
+
+impl AsyncStreamState {
+    async fn release(&mut self) {
+        self.pending = None;
+        self.stream = None;
+        if let Some(runner) = self.runner.take() {
+            let _ = tokio::task::spawn_blocking(move || runner.give_back()).await;
+        }
+    }
+}
+
+impl PySyncStream {
+    pub(crate) fn new(stream: InvocationStream) -> Self {
+        Self {
+            stream: Mutex::new(Some(stream)),
+            runner: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn owning(self, runner: BorrowedRunner) -> Self {
+        *self
+            .runner
+            .lock()
+            .expect("stream runner lock should not be poisoned") = Some(runner);
+        self
+    }
+
+    fn release_runner(&self) {
+        let runner = self
+            .runner
+            .lock()
+            .expect("stream runner lock should not be poisoned")
+            .take();
+        if let Some(runner) = runner {
+            let _ = runner.give_back();
+        }
+    }
+}
+
+impl PyAsyncStream {
+    pub(crate) fn new(handle: DenoExecutionHandle, arguments: RunnerArguments) -> Self {
+        Self {
+            state: Arc::new(tokio::sync::Mutex::new(AsyncStreamState {
+                pending: Some((handle, arguments)),
+                stream: None,
+                runner: None,
+            })),
+        }
+    }
+
+    pub(crate) fn owning(runner: BorrowedRunner, arguments: RunnerArguments) -> Self {
+        Self {
+            state: Arc::new(tokio::sync::Mutex::new(AsyncStreamState {
+                pending: Some((runner.handle().clone(), arguments)),
+                stream: None,
+                runner: Some(runner),
+            })),
+        }
+    }
+}
+
+#[pymethods]
+impl PySyncStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        py.detach(|| {
+            let mut state = self
+                .stream
+                .lock()
+                .expect("stream state lock should not be poisoned");
+            let Some(stream) = state.as_mut() else {
+                return Ok(None);
+            };
+            if let Some(chunk) = stream.next_blocking() {
+                return Ok(Some(chunk));
+            }
+            let result = state.take().map(InvocationStream::finish_blocking);
+            drop(state);
+            self.release_runner();
+            result.transpose()?;
+            Ok(None)
+        })
+        .map_err(py_error::from_binding_error)?
+        .map_or_else(
+            || Err(PyStopIteration::new_err(())),
+            |chunk| chunk.to_py(py),
+        )
+    }
+
+    /// Stops the call if it is still running; the runner stays usable.
+    fn close(&self, py: Python<'_>) {
+        let stream = py.detach(|| {
+            self.stream
+                .lock()
+                .expect("stream state lock should not be poisoned")
+                .take()
+        });
+        drop(stream);
+        py.detach(|| self.release_runner());
+    }
+
+    fn __repr__(&self) -> String {
+        "SyncStream()".to_string()
+    }
+}
+
+#[pymethods]
+impl PyAsyncStream {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let state = self.state.clone();
+        into_coroutine(py, async move {
+            let mut state = state.lock().await;
+            if let Some((handle, arguments)) = state.pending.take() {
+                match handle.stream_async(arguments).await {
+                    Ok(stream) => state.stream = Some(stream),
+                    Err(error) => {
+                        state.release().await;
+                        return Err(py_error::from_binding_error(error));
+                    }
+                }
+            }
+            let Some(stream) = state.stream.as_mut() else {
+                return Err(PyStopAsyncIteration::new_err(()));
+            };
+            if let Some(chunk) = stream.next_async().await {
+                return Python::attach(|py| chunk.to_py(py));
+            }
+            let result = match state.stream.take() {
+                Some(stream) => stream.finish_async().await.map(drop),
+                None => Ok(()),
+            };
+            state.release().await;
+            result.map_err(py_error::from_binding_error)?;
+            Err(PyStopAsyncIteration::new_err(()))
+        })
+    }
+
+    fn aclose<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let state = self.state.clone();
+        into_coroutine(py, async move {
+            state.lock().await.release().await;
+            Ok(Python::attach(|py| py.None()))
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        "AsyncStream()".to_string()
+    }
+}
@@ -0,0 +1,43 @@
+use deno_core::v8;
+use deno_lib::version::DENO_VERSION_INFO;
+use pyo3::{prelude::*, types::PyDict};
+
+/// deno_core does not export its own version; kept in step with `Cargo.lock` by a test.
+const DENO_CORE_VERSION: &str = "0.408.0";
+
+/// Reads compile-time constants only, so it never initializes V8 or blocks `configure`.
+#[pyfunction]
+pub(crate) fn version(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let belgie = py
+        .import("importlib.metadata")?
+        .call_method1("version", ("belgie",))
+        .and_then(|version| version.extract::<String>())
+        .unwrap_or_else(|_| env!("CARGO_PKG_VERSION").to_string());
+    let versions = PyDict::new(py);
+    versions.set_item("belgie", belgie)?;
+    versions.set_item("deno", DENO_VERSION_INFO.deno)?;
+    versions.set_item("deno_core", DENO_CORE_VERSION)?;
+    versions.set_item("v8", v8::V8::get_version())?;
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DENO_CORE_VERSION;
+
+    #[test]
+    fn deno_core_version_matches_the_lockfile() {
+        let lockfile = include_str!("../../Cargo.lock");
+        let locked = lockfile
+            .split("[[package]]")
+            .find(|package| package.contains("\nname = \"deno_core\"\n"))
+            .and_then(|package| {
+                package
+                    .lines()
+                    .find_map(|line| line.strip_prefix("version = \""))
+            })
+            .and_then(|version| version.strip_suffix('"'));
+
+        assert_eq!(locked, Some(DENO_CORE_VERSION));
+    }
+}
@@ -0,0 +1,90 @@
+use std::{path::PathBuf, sync::Arc};
+
+use pyo3::{
+    exceptions::{PyBaseException, PyTypeError},
+    prelude::*,
+    types::{PyDict, PyTraceback, PyTuple, PyType},
+};
+
+use crate::{
+    runtime::{RuntimeSession, ScriptWatch, WatchCallback},
+    script::ScriptSource,
+    types::runner::RunnerArguments,
+    utils::py_error,
+};
+
+/// Re-runs a file script whenever it changes, passing each result to a callback.
+#[pyclass(name = "ScriptWatcher", module = "belgie._core")]
+#[derive(Debug)]
+pub struct PyScriptWatcher {
+    watch: ScriptWatch,
+}
+
+/// The callback runs on the watch thread and gets the result, or the exception the run raised.
+pub(crate) fn watch_script(
+    py: Python<'_>,
+    session: &Arc<RuntimeSession>,
+    source: ScriptSource,
+    callback: &Bound<'_, PyAny>,
+    args: &Bound<'_, PyTuple>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyScriptWatcher> {
+    if !callback.is_callable() {
+        return Err(PyTypeError::new_err("watch callback must be callable"));
+    }
+    let arguments = RunnerArguments::from_py(args, kwargs)?;
+    let callback = callback.clone().unbind();
+    let on_result: WatchCallback = Box::new(move |result| {
+        Python::attach(|py| {
+            let argument = match result {
+                Ok(value) => value.to_py(py),
+                Err(error) => Ok(py_error::from_binding_error(error)
+                    .into_value(py)
+                    .into_any()),
+            };
+            if let Err(error) = argument.and_then(|argument| callback.call1(py, (argument,))) {
+                error.write_unraisable(py, Some(callback.bind(py)));
+            }
+        });
+    });
+    let watch = py
+        .detach(|| ScriptWatch::start(session, source, arguments, on_result))
+        .map_err(py_error::from_binding_error)?;
+    Ok(PyScriptWatcher { watch })
+}
+
+#[pymethods]
+impl PyScriptWatcher {
+    #[getter]
+    fn path(&self) -> PathBuf {
+        self.watch.path().to_path_buf()
+    }
+
+    fn is_alive(&self) -> bool {
+        self.watch.is_alive()
+    }
+
+    /// Stops watching and waits for a run in progress to finish.
+    fn close(&self, py: Python<'_>) {
+        py.detach(|| self.watch.close_blocking());
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __exit__(
+        &self,
+        py: Python<'_>,
+        _exc_type: Option<&Bound<'_, PyType>>,
+        _exc: Option<&Bound<'_, PyBaseException>>,
+        _traceback: Option<&Bound<'_, PyTraceback>>,
+    ) -> bool {
+        self.close(py);
+        false
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ScriptWatcher({})", self.watch.path().display())
+    }
+}
@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -10,6 +10,7 @@ use deno_cache_dir::file_fetcher::SendError;
 use deno_cache_dir::file_fetcher::SendResponse;
 use deno_config::deno_json::{NodeModulesDirMode, NodeModulesLinkerMode};
 use deno_core::error::AnyError;
+use deno_core::serde_json;
 use deno_core::url::Url;
 use deno_error::JsErrorBox;
 use deno_graph::ModuleSpecifier;
@@ -280,6 +281,37 @@ impl EmbedContextOptions {
     pub(crate) fn apply_worker_options(&mut self, worker: &RuntimeWorkerOptions) {
         self.enable_raw_imports = worker.enable_raw_imports();
     }
+
+    /// Layers a script's own import map over the environment's, so script entries win.
+    pub(crate) fn apply_script_imports(
+        &mut self,
+        base_url: &Url,
+        imports: &BTreeMap<String, ModuleSpecifier>,
+    ) {
+        if imports.is_empty() {
+            return;
+        }
+        let import_map = self
+            .specified_import_map
+            .get_or_insert_with(|| SpecifiedImportMap {
+                base_url: base_url.clone(),
+                value: serde_json::json!({ "imports": {} }),
+            });
+        let Some(entries) = import_map.value.as_object_mut().and_then(|value| {
+            value
+                .entry("imports")
+                .or_insert_with(|| serde_json::json!({}))
+                .as_object_mut()
+        }) else {
+            return;
+        };
+        for (specifier, target) in imports {
+            entries.insert(
+                specifier.clone(),
+                serde_json::Value::String(target.to_string()),
+            );
+        }
+    }
 }
 
 impl std::fmt::Debug for EmbedContext {
@@ -494,7 +526,7 @@ impl EmbedContext {
         self.unsafely_ignore_certificate_errors.clone()
     }
 
-    pub fn insert_memory_file(&self, url: Url, source: String) {
+    pub fn insert_memory_file(&self, url: Url, source: Arc<str>) {
         memory::insert_memory_file(&self.memory_files, url, source);
     }
 }
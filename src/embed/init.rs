@@ -1,23 +1,90 @@
 use std::sync::mpsc::{self, SyncSender};
-use std::sync::{Once, OnceLock};
+use std::sync::{Mutex, Once, OnceLock, PoisonError};
 use std::thread::{self, JoinHandle};
 
 use deno_core::JsRuntime;
 use deno_lib::args::get_root_cert_store;
 
 use crate::embed::sys::EmbedSys;
+use crate::types::error::BindingError;
 
 static GLOBAL_INIT: Once = Once::new();
 // Python may call belgie from arbitrary threads, but V8 requires init_platform on a parent of
 // every isolate-spawning thread (same rule as Deno CLI's main thread in cli/lib.rs::init_v8).
 static V8_HOST_TX: OnceLock<SyncSender<Box<dyn FnOnce() + Send>>> = OnceLock::new();
+// Set by configure_v8_flags, or frozen empty when the platform starts first.
+static V8_FLAGS: OnceLock<Vec<String>> = OnceLock::new();
+// V8 aborts when flags change after the platform starts, so setting flags and starting the
+// platform never overlap.
+static V8_FLAGS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Applies V8 command-line flags; only allowed once, before the first isolate is created.
+pub(crate) fn configure_v8_flags(flags: Vec<String>) -> Result<(), BindingError> {
+    let _lock = V8_FLAGS_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(current) = V8_FLAGS.get() {
+        return Err(reconfigured_error(current, &flags));
+    }
+    for flag in &flags {
+        validate_v8_flag(flag)?;
+    }
+    let unrecognized = deno_core::v8_set_flags(
+        std::iter::once("belgie".to_string())
+            .chain(flags.iter().cloned())
+            .collect(),
+    );
+    if unrecognized.len() > 1 {
+        return Err(BindingError::value_conversion(format!(
+            "Unrecognized V8 flags: {}",
+            unrecognized[1..].join(" ")
+        )));
+    }
+    V8_FLAGS
+        .set(flags)
+        .map_err(|flags| reconfigured_error(V8_FLAGS.get().map_or(&[], Vec::as_slice), &flags))
+}
+
+/// Rejects flags V8 would act on by exiting the process instead of returning an error.
+fn validate_v8_flag(flag: &str) -> Result<(), BindingError> {
+    let Some(name) = flag.strip_prefix('-') else {
+        return Err(BindingError::value_conversion(format!(
+            "V8 flags must start with '-', got {flag:?}"
+        )));
+    };
+    let name = name.trim_start_matches('-');
+    let name = name.split_once('=').map_or(name, |(name, _)| name);
+    if name.replace('_', "-") == "help" {
+        return Err(BindingError::value_conversion(format!(
+            "V8 flag {flag} would exit the process"
+        )));
+    }
+    Ok(())
+}
+
+fn reconfigured_error(current: &[String], requested: &[String]) -> BindingError {
+    let message = "V8 flags must be configured once, before the first Runtime starts";
+    if current == requested {
+        return BindingError::runtime(message);
+    }
+    let current = if current.is_empty() {
+        "no flags".to_string()
+    } else {
+        current.join(" ")
+    };
+    BindingError::runtime(format!(
+        "{message}; V8 is already configured with {current}"
+    ))
+}
 
 fn v8_host_sender() -> &'static SyncSender<Box<dyn FnOnce() + Send>> {
     V8_HOST_TX.get_or_init(|| {
         let (ready_tx, ready_rx) = mpsc::sync_channel::<()>(0);
         let (tx, rx) = mpsc::sync_channel::<Box<dyn FnOnce() + Send>>(0);
         thread::spawn(move || {
-            JsRuntime::init_platform(None);
+            {
+                let _lock = V8_FLAGS_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+                V8_FLAGS.get_or_init(Vec::new);
+                JsRuntime::init_platform(None);
+            }
             let _ = ready_tx.send(());
             for job in rx {
                 job();
@@ -46,7 +113,9 @@ pub(crate) fn ensure_initialized() {
     });
 }
 
-pub(crate) fn spawn_v8_worker<F, T>(f: F) -> JoinHandle<T>
+/// Spawns `f` on a thread named `name`, which shows up in debuggers, profilers, and `/proc`
+/// (where Linux keeps only the first 15 bytes).
+pub(crate) fn spawn_v8_worker<F, T>(name: String, f: F) -> JoinHandle<T>
 where
     F: FnOnce() -> T + Send + 'static,
     T: Send + 'static,
@@ -55,7 +124,10 @@ where
     let (done_tx, done_rx) = mpsc::sync_channel::<JoinHandle<T>>(0);
     v8_host_sender()
         .send(Box::new(move || {
-            let handle = thread::spawn(f);
+            let handle = thread::Builder::new()
+                .name(name)
+                .spawn(f)
+                .expect("v8 worker thread should spawn");
             let _ = done_tx.send(handle);
         }))
         .expect("v8 host thread should be alive");
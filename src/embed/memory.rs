@@ -1,17 +1,19 @@
+use std::sync::Arc;
+
 use deno_cache_dir::file_fetcher::{File, LoadedFrom};
 use deno_core::url::Url;
 use deno_resolver::loader::MemoryFilesRc;
 
-pub(crate) fn memory_file(url: Url, source: String) -> File {
+pub(crate) fn memory_file(url: Url, source: Arc<str>) -> File {
     File {
         url: url.clone(),
         mtime: None,
         maybe_headers: None,
-        source: source.into_bytes().into(),
+        source: source.into(),
         loaded_from: LoadedFrom::Local,
     }
 }
 
-pub(crate) fn insert_memory_file(memory_files: &MemoryFilesRc, url: Url, source: String) {
+pub(crate) fn insert_memory_file(memory_files: &MemoryFilesRc, url: Url, source: Arc<str>) {
     memory_files.insert(url.clone(), memory_file(url, source));
 }
@@ -49,7 +49,7 @@ impl std::fmt::Debug for PackageRuntimeState {
 pub(crate) async fn prepare_package_runtime(
     context: Rc<EmbedContext>,
     main_module: ModuleSpecifier,
-    main_source: Option<String>,
+    main_source: Option<Arc<str>>,
     file_header_overrides: HashMap<ModuleSpecifier, HashMap<String, String>>,
 ) -> Result<PackageRuntimeState, AnyError> {
     if let Some(main_source) = main_source {
@@ -594,9 +594,11 @@ impl ActiveEnvironment {
     pub(crate) fn embed_context_with_worker_options(
         &self,
         worker_options: &RuntimeWorkerOptions,
+        configure: impl FnOnce(&mut EmbedContextOptions),
     ) -> Result<Rc<EmbedContext>, AnyError> {
         let mut options = self.resolve_embed_options()?;
         options.apply_worker_options(worker_options);
+        configure(&mut options);
         Ok(Rc::new(EmbedContext::new_with_options(
             self.workspace.clone(),
             self.lockfile.clone(),
@@ -4,3 +4,7 @@ pyo3::create_exception!(belgie.errors, BelgieError, PyException);
 pyo3::create_exception!(belgie.errors, BelgieRuntimeError, BelgieError);
 pyo3::create_exception!(belgie.errors, BelgieModuleError, BelgieError);
 pyo3::create_exception!(belgie.errors, BelgieJavaScriptError, BelgieError);
+pyo3::create_exception!(belgie.errors, BelgieTimeoutError, BelgieError);
+pyo3::create_exception!(belgie.errors, BelgieSyntaxError, BelgieModuleError);
+pyo3::create_exception!(belgie.errors, BelgieClosedError, BelgieRuntimeError);
+pyo3::create_exception!(belgie.errors, BelgieResultTooLargeError, BelgieRuntimeError);
@@ -23,12 +23,16 @@ fn _core(py: Python<'_>, m: &pyo3::Bound<'_, pyo3::types::PyModule>) -> pyo3::Py
         .import("sysconfig")?
         .call_method1("get_path", ("scripts",))?
         .extract::<PathBuf>()?;
+    utils::tokio::init_async_bridge();
     runtime::child_process::set_executable(
         scripts_dir.join(format!("belgie-runtime{}", std::env::consts::EXE_SUFFIX)),
     );
     m.add_function(wrap_pyfunction!(binding::run_node_child, m)?)?;
+    m.add_function(wrap_pyfunction!(binding::configure, m)?)?;
+    m.add_function(wrap_pyfunction!(binding::version, m)?)?;
     m.add_class::<binding::PyCommand>()?;
     m.add_class::<binding::PyScript>()?;
+    m.add_class::<binding::PyCompressedModule>()?;
     m.add_class::<binding::PyEnvironment>()?;
     m.add_class::<binding::PyEnvironmentOptions>()?;
     m.add_class::<binding::PySyncEnvironment>()?;
@@ -36,10 +40,16 @@ fn _core(py: Python<'_>, m: &pyo3::Bound<'_, pyo3::types::PyModule>) -> pyo3::Py
     m.add_class::<binding::PyRuntime>()?;
     m.add_class::<binding::PyRuntimeOptions>()?;
     m.add_class::<binding::PyRuntimePermissions>()?;
+    m.add_class::<binding::PyRuntimeState>()?;
     m.add_class::<binding::PySyncRuntime>()?;
     m.add_class::<binding::PyAsyncRuntime>()?;
     m.add_class::<binding::PySyncRunner>()?;
     m.add_class::<binding::PyAsyncRunner>()?;
+    m.add_class::<binding::PySyncRunnerPool>()?;
+    m.add_class::<binding::PyAsyncRunnerPool>()?;
+    m.add_class::<binding::PySyncStream>()?;
+    m.add_class::<binding::PyAsyncStream>()?;
+    m.add_class::<binding::PyScriptWatcher>()?;
     m.add_class::<binding::PySyncCommandRunner>()?;
     m.add_class::<binding::PyAsyncCommandRunner>()?;
     m.add_class::<binding::PyEnvironmentInstallResult>()?;
@@ -58,5 +68,21 @@ fn _core(py: Python<'_>, m: &pyo3::Bound<'_, pyo3::types::PyModule>) -> pyo3::Py
         "BelgieJavaScriptError",
         py.get_type::<exceptions::BelgieJavaScriptError>(),
     )?;
+    m.add(
+        "BelgieTimeoutError",
+        py.get_type::<exceptions::BelgieTimeoutError>(),
+    )?;
+    m.add(
+        "BelgieSyntaxError",
+        py.get_type::<exceptions::BelgieSyntaxError>(),
+    )?;
+    m.add(
+        "BelgieClosedError",
+        py.get_type::<exceptions::BelgieClosedError>(),
+    )?;
+    m.add(
+        "BelgieResultTooLargeError",
+        py.get_type::<exceptions::BelgieResultTooLargeError>(),
+    )?;
     Ok(())
 }
@@ -4,6 +4,7 @@ mod script_options;
 
 pub(crate) use environment_options::EnvironmentOptions;
 pub(crate) use runtime_options::{
+    BuiltinExtension, CpuAffinity, Determinism, ExecutionOptions, InspectorOptions,
     JsRuntimeOptions, RuntimeEnvironment, RuntimeOptions, RuntimePermissionOptions,
     RuntimeWorkerOptions,
 };
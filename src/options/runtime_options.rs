@@ -1,4 +1,8 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 use deno_lib::worker::create_isolate_create_params;
 use deno_runtime::WorkerLogLevel;
@@ -6,12 +10,16 @@ use deno_runtime::deno_permissions::{PermissionDescriptorParser, Permissions, Pe
 
 use crate::embed::sys::EmbedSys;
 use crate::environment::SharedEnvironment;
+use crate::types::value::Unserializable;
+
+pub(crate) const DEFAULT_QUEUE_SIZE: usize = 64;
 
 #[derive(Clone, Debug)]
 pub(crate) struct RuntimeOptions {
     cwd: PathBuf,
     js_runtime: JsRuntimeOptions,
     worker: RuntimeWorkerOptions,
+    execution: ExecutionOptions,
     environment: Option<RuntimeEnvironment>,
 }
 
@@ -25,6 +33,50 @@ pub(crate) struct JsRuntimeOptions {
     max_old_generation_size_mb: Option<u64>,
     max_young_generation_size_mb: Option<u64>,
     code_range_size_mb: Option<u64>,
+    extensions: Option<Vec<BuiltinExtension>>,
+    module_root: Option<PathBuf>,
+}
+
+/// Web API bundles the lightweight backend installs; Deno workers always provide all of them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BuiltinExtension {
+    TextEncoding,
+    Crypto,
+    Performance,
+    Url,
+    Base64,
+    StructuredClone,
+    Abort,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ExecutionOptions {
+    timeout: Option<Duration>,
+    capture_console: bool,
+    queue_size: Option<usize>,
+    setup: Option<String>,
+    determinism: Option<Determinism>,
+    max_result_bytes: Option<usize>,
+    env_vars: Option<BTreeMap<String, String>>,
+    native_backtraces: bool,
+    cpu_budget: Option<Duration>,
+    op_metrics: bool,
+    node_compat: bool,
+    unserializable: Unserializable,
+    cpu_affinity: Option<CpuAffinity>,
+}
+
+/// Cores runner threads are pinned to, handed out in turn as a runtime's runners start.
+#[derive(Clone, Debug)]
+pub(crate) struct CpuAffinity {
+    cores: Arc<[usize]>,
+}
+
+/// Replaces `Math.random` with a seeded generator and freezes `Date` at `time_ms`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Determinism {
+    seed: u64,
+    time_ms: i64,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -37,6 +89,13 @@ pub(crate) struct RuntimeWorkerOptions {
     enable_raw_imports: bool,
     disable_offscreen_canvas: bool,
     trace_ops: Option<Vec<String>>,
+    inspector: Option<InspectorOptions>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct InspectorOptions {
+    address: SocketAddr,
+    break_on_first_statement: bool,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -65,6 +124,7 @@ impl RuntimeOptions {
             cwd,
             js_runtime,
             RuntimeWorkerOptions::default(),
+            ExecutionOptions::default(),
             environment,
         )
     }
@@ -73,12 +133,14 @@ impl RuntimeOptions {
         cwd: PathBuf,
         js_runtime: JsRuntimeOptions,
         worker: RuntimeWorkerOptions,
+        execution: ExecutionOptions,
         environment: Option<RuntimeEnvironment>,
     ) -> Self {
         Self {
             cwd,
             js_runtime,
             worker,
+            execution,
             environment,
         }
     }
@@ -95,6 +157,10 @@ impl RuntimeOptions {
         &self.worker
     }
 
+    pub(crate) fn execution(&self) -> &ExecutionOptions {
+        &self.execution
+    }
+
     pub(crate) fn environment(&self) -> Option<&RuntimeEnvironment> {
         self.environment.as_ref()
     }
@@ -108,6 +174,209 @@ impl RuntimeEnvironment {
     }
 }
 
+impl ExecutionOptions {
+    pub(crate) fn new(
+        timeout: Option<Duration>,
+        capture_console: bool,
+        queue_size: Option<usize>,
+    ) -> Self {
+        Self {
+            timeout,
+            capture_console,
+            queue_size,
+            setup: None,
+            determinism: None,
+            max_result_bytes: None,
+            env_vars: None,
+            native_backtraces: false,
+            cpu_budget: None,
+            op_metrics: false,
+            node_compat: false,
+            unserializable: Unserializable::Error,
+            cpu_affinity: None,
+        }
+    }
+
+    pub(crate) fn with_setup(mut self, setup: Option<String>) -> Self {
+        self.setup = setup;
+        self
+    }
+
+    pub(crate) fn with_determinism(mut self, determinism: Option<Determinism>) -> Self {
+        self.determinism = determinism;
+        self
+    }
+
+    pub(crate) fn with_max_result_bytes(mut self, max_result_bytes: Option<usize>) -> Self {
+        self.max_result_bytes = max_result_bytes;
+        self
+    }
+
+    pub(crate) fn with_env_vars(mut self, env_vars: Option<BTreeMap<String, String>>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
+
+    pub(crate) fn with_native_backtraces(mut self, native_backtraces: bool) -> Self {
+        self.native_backtraces = native_backtraces;
+        self
+    }
+
+    pub(crate) fn with_cpu_budget(mut self, cpu_budget: Option<Duration>) -> Self {
+        self.cpu_budget = cpu_budget;
+        self
+    }
+
+    pub(crate) fn with_op_metrics(mut self, op_metrics: bool) -> Self {
+        self.op_metrics = op_metrics;
+        self
+    }
+
+    pub(crate) fn with_node_compat(mut self, node_compat: bool) -> Self {
+        self.node_compat = node_compat;
+        self
+    }
+
+    pub(crate) fn with_unserializable(mut self, unserializable: Unserializable) -> Self {
+        self.unserializable = unserializable;
+        self
+    }
+
+    pub(crate) fn with_cpu_affinity(mut self, cpu_affinity: Option<CpuAffinity>) -> Self {
+        self.cpu_affinity = cpu_affinity;
+        self
+    }
+
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub(crate) fn capture_console(&self) -> bool {
+        self.capture_console
+    }
+
+    /// How many calls a runner buffers before callers wait for the worker to catch up.
+    pub(crate) fn queue_size(&self) -> usize {
+        self.queue_size.unwrap_or(DEFAULT_QUEUE_SIZE)
+    }
+
+    /// Classic-script source each runner's isolate runs once, before the script module loads.
+    pub(crate) fn setup(&self) -> Option<&str> {
+        self.setup.as_deref()
+    }
+
+    pub(crate) fn determinism(&self) -> Option<Determinism> {
+        self.determinism
+    }
+
+    /// The largest serialized JSON a call may return before the result is rejected.
+    pub(crate) fn max_result_bytes(&self) -> Option<usize> {
+        self.max_result_bytes
+    }
+
+    /// Variables scripts see through `Deno.env` and `process.env` in place of the host's.
+    pub(crate) fn env_vars(&self) -> Option<&BTreeMap<String, String>> {
+        self.env_vars.as_ref()
+    }
+
+    /// Whether errors thrown by Rust callbacks carry the callback's Rust backtrace.
+    pub(crate) fn native_backtraces(&self) -> bool {
+        self.native_backtraces
+    }
+
+    /// CPU time one call may spend on the worker thread; blocked time does not count.
+    pub(crate) fn cpu_budget(&self) -> Option<Duration> {
+        self.cpu_budget
+    }
+
+    /// Whether runners count calls and time spent per op and host function.
+    pub(crate) fn op_metrics(&self) -> bool {
+        self.op_metrics
+    }
+
+    /// Whether runners without a `process` global get a minimal one for Node-targeted packages.
+    pub(crate) fn node_compat(&self) -> bool {
+        self.node_compat
+    }
+
+    /// How call results and default exports replace values that have no JSON form.
+    pub(crate) fn unserializable(&self) -> Unserializable {
+        self.unserializable
+    }
+
+    /// Cores each runner's worker thread is pinned to, round-robin across runners.
+    pub(crate) fn cpu_affinity(&self) -> Option<&CpuAffinity> {
+        self.cpu_affinity.as_ref()
+    }
+}
+
+impl CpuAffinity {
+    /// `cores` must be non-empty.
+    pub(crate) fn new(cores: Vec<usize>) -> Self {
+        Self {
+            cores: cores.into(),
+        }
+    }
+
+    pub(crate) fn cores(&self) -> &[usize] {
+        &self.cores
+    }
+
+    /// The core for a runtime's `index`th runner, wrapping around the list.
+    pub(crate) fn core(&self, index: usize) -> usize {
+        self.cores[index % self.cores.len()]
+    }
+}
+
+impl Determinism {
+    pub(crate) fn new(seed: u64, time_ms: i64) -> Self {
+        Self { seed, time_ms }
+    }
+
+    pub(crate) fn seed(self) -> u64 {
+        self.seed
+    }
+
+    /// Folds the seed to the 32-bit state the in-isolate generator keeps.
+    pub(crate) fn generator_state(self) -> u32 {
+        (self.seed ^ (self.seed >> 32)) as u32
+    }
+
+    pub(crate) fn time_ms(self) -> i64 {
+        self.time_ms
+    }
+}
+
+impl BuiltinExtension {
+    pub(crate) const ALL: [Self; 7] = [
+        Self::TextEncoding,
+        Self::Crypto,
+        Self::Performance,
+        Self::Url,
+        Self::Base64,
+        Self::StructuredClone,
+        Self::Abort,
+    ];
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|extension| extension.name() == name)
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::TextEncoding => "text_encoding",
+            Self::Crypto => "crypto",
+            Self::Performance => "performance",
+            Self::Url => "url",
+            Self::Base64 => "base64",
+            Self::StructuredClone => "structured_clone",
+            Self::Abort => "abort",
+        }
+    }
+}
+
 impl RuntimeWorkerOptions {
     #[allow(
         clippy::too_many_arguments,
@@ -132,9 +401,15 @@ impl RuntimeWorkerOptions {
             enable_raw_imports,
             disable_offscreen_canvas,
             trace_ops,
+            inspector: None,
         }
     }
 
+    pub(crate) fn with_inspector(mut self, inspector: Option<InspectorOptions>) -> Self {
+        self.inspector = inspector;
+        self
+    }
+
     pub(crate) fn requires_package_worker(&self) -> bool {
         !matches!(self.permissions, RuntimePermissionOptions::AllowAll)
             || self.seed.is_some()
@@ -177,6 +452,27 @@ impl RuntimeWorkerOptions {
     pub(crate) fn trace_ops(&self) -> Option<Vec<String>> {
         self.trace_ops.clone()
     }
+
+    pub(crate) fn inspector(&self) -> Option<InspectorOptions> {
+        self.inspector
+    }
+}
+
+impl InspectorOptions {
+    pub(crate) fn new(address: SocketAddr, break_on_first_statement: bool) -> Self {
+        Self {
+            address,
+            break_on_first_statement,
+        }
+    }
+
+    pub(crate) fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub(crate) fn break_on_first_statement(&self) -> bool {
+        self.break_on_first_statement
+    }
 }
 
 impl RuntimePermissionOptions {
@@ -219,9 +515,31 @@ impl JsRuntimeOptions {
             max_old_generation_size_mb,
             max_young_generation_size_mb,
             code_range_size_mb,
+            extensions: None,
+            module_root: None,
         }
     }
 
+    pub(crate) fn with_extensions(mut self, extensions: Option<Vec<BuiltinExtension>>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    pub(crate) fn with_module_root(mut self, module_root: Option<PathBuf>) -> Self {
+        self.module_root = module_root;
+        self
+    }
+
+    /// The directory file imports must stay inside, already canonicalized.
+    pub(crate) fn module_root(&self) -> Option<&Path> {
+        self.module_root.as_deref()
+    }
+
+    /// The bundles to install, in installation order; every bundle when none were selected.
+    pub(crate) fn extensions(&self) -> &[BuiltinExtension] {
+        self.extensions.as_deref().unwrap_or(&BuiltinExtension::ALL)
+    }
+
     pub(crate) fn to_create_params(&self) -> Result<Option<deno_core::v8::CreateParams>, String> {
         if self.max_old_generation_size_mb.is_none()
             && self.max_young_generation_size_mb.is_none()
@@ -277,7 +595,10 @@ fn mb_to_bytes(value: u64, field_name: &str) -> Result<usize, String> {
 mod tests {
     use deno_runtime::deno_permissions::PermissionsOptions;
 
-    use super::{JsRuntimeOptions, RuntimeOptions, RuntimePermissionOptions, RuntimeWorkerOptions};
+    use super::{
+        Determinism, JsRuntimeOptions, RuntimeOptions, RuntimePermissionOptions,
+        RuntimeWorkerOptions,
+    };
     use std::path::PathBuf;
 
     #[test]
@@ -327,6 +648,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn determinism_folds_high_seed_bits_into_generator_state() {
+        assert_eq!(Determinism::new(42, 0).generator_state(), 42);
+        assert_ne!(
+            Determinism::new(1 << 32, 0).generator_state(),
+            Determinism::new(0, 0).generator_state(),
+        );
+    }
+
     #[test]
     fn default_worker_options_do_not_require_package_worker() {
         assert!(!RuntimeWorkerOptions::default().requires_package_worker());
@@ -1,9 +1,17 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
+use crate::script::ModuleSource;
+use crate::types::value::PyJsValue;
+
 #[derive(Clone, Debug)]
 pub(crate) struct ScriptOptions {
     content: String,
     path: Option<PathBuf>,
+    name: Option<String>,
+    globals: Option<PyJsValue>,
+    modules: BTreeMap<String, ModuleSource>,
+    imports: BTreeMap<String, String>,
 }
 
 impl ScriptOptions {
@@ -11,6 +19,10 @@ impl ScriptOptions {
         Self {
             content,
             path: None,
+            name: None,
+            globals: None,
+            modules: BTreeMap::new(),
+            imports: BTreeMap::new(),
         }
     }
 
@@ -18,13 +30,53 @@ impl ScriptOptions {
         Self {
             content,
             path: Some(path),
+            name: None,
+            globals: None,
+            modules: BTreeMap::new(),
+            imports: BTreeMap::new(),
         }
     }
 
+    pub(crate) fn with_name(mut self, name: Option<String>) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub(crate) fn with_globals(mut self, globals: Option<PyJsValue>) -> Self {
+        self.globals = globals;
+        self
+    }
+
+    pub(crate) fn with_modules(mut self, modules: BTreeMap<String, ModuleSource>) -> Self {
+        self.modules = modules;
+        self
+    }
+
+    pub(crate) fn with_imports(mut self, imports: BTreeMap<String, String>) -> Self {
+        self.imports = imports;
+        self
+    }
+
     pub(crate) fn path(&self) -> Option<&Path> {
         self.path.as_deref()
     }
 
+    pub(crate) fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub(crate) fn globals(&self) -> Option<&PyJsValue> {
+        self.globals.as_ref()
+    }
+
+    pub(crate) fn modules(&self) -> &BTreeMap<String, ModuleSource> {
+        &self.modules
+    }
+
+    pub(crate) fn imports(&self) -> &BTreeMap<String, String> {
+        &self.imports
+    }
+
     pub(crate) fn into_content(self) -> String {
         self.content
     }
@@ -1,24 +1,31 @@
 use std::{
+    collections::BTreeMap,
     path::{Path, PathBuf},
     rc::Rc,
     sync::Arc,
 };
 
+use deno_core::ModuleSpecifier;
 use tempfile::TempDir;
 
 use crate::embed::{EmbedContext, EmbedContextOptions};
 use crate::environment::ActiveEnvironment;
-use crate::options::{JsRuntimeOptions, RuntimeEnvironment, RuntimeWorkerOptions};
+use crate::options::{
+    ExecutionOptions, JsRuntimeOptions, RuntimeEnvironment, RuntimeWorkerOptions,
+};
 use crate::script::ScriptSource;
 use crate::types::error::BindingError;
 
-use super::DenoRuntime;
+use super::{
+    DenoRuntime, HostFunctions, OpMetrics, SharedState, WebStorage, module_loader::ModuleCache,
+};
 
 #[derive(Clone, Debug)]
 pub(crate) struct BoundRuntime {
     runtime: DenoRuntime,
     script: ScriptSource,
     package_environment: Option<BoundPackageEnvironment>,
+    host_functions: HostFunctions,
 }
 
 #[derive(Clone, Debug)]
@@ -69,9 +76,11 @@ impl ImplicitPackageEnvironment {
     fn embed_context(
         &self,
         worker_options: &RuntimeWorkerOptions,
+        configure: impl FnOnce(&mut EmbedContextOptions),
     ) -> Result<Rc<EmbedContext>, BindingError> {
         let mut options = self.options.clone();
         options.apply_worker_options(worker_options);
+        configure(&mut options);
         Ok(Rc::new(
             EmbedContext::new_with_options(self.workspace.clone(), self.lockfile.clone(), options)
                 .map_err(|error| BindingError::runtime(error.to_string()))?,
@@ -115,12 +124,32 @@ impl BoundPackageEnvironment {
     pub(crate) fn embed_context_rc(
         &self,
         worker_options: &RuntimeWorkerOptions,
+    ) -> Result<Rc<EmbedContext>, BindingError> {
+        self.embed_context_with(worker_options, |_| {})
+    }
+
+    /// Like [`Self::embed_context_rc`], with the script's `imports` layered into the import map.
+    pub(crate) fn embed_context_for_script(
+        &self,
+        worker_options: &RuntimeWorkerOptions,
+        main_module: &ModuleSpecifier,
+        imports: &BTreeMap<String, ModuleSpecifier>,
+    ) -> Result<Rc<EmbedContext>, BindingError> {
+        self.embed_context_with(worker_options, |options| {
+            options.apply_script_imports(main_module, imports);
+        })
+    }
+
+    fn embed_context_with(
+        &self,
+        worker_options: &RuntimeWorkerOptions,
+        configure: impl FnOnce(&mut EmbedContextOptions),
     ) -> Result<Rc<EmbedContext>, BindingError> {
         match self {
             Self::Isolated(environment) => environment
-                .embed_context_with_worker_options(worker_options)
+                .embed_context_with_worker_options(worker_options, configure)
                 .map_err(|error| BindingError::runtime(error.to_string())),
-            Self::Implicit(environment) => environment.embed_context(worker_options),
+            Self::Implicit(environment) => environment.embed_context(worker_options, configure),
         }
     }
 
@@ -140,6 +169,7 @@ impl BoundRuntime {
             runtime,
             script,
             package_environment: None,
+            host_functions: HostFunctions::default(),
         }
     }
 
@@ -155,6 +185,10 @@ impl BoundRuntime {
         self.runtime.worker_options()
     }
 
+    pub(crate) fn execution_options(&self) -> &ExecutionOptions {
+        self.runtime.execution_options()
+    }
+
     pub(crate) fn package_environment(&self) -> Option<&BoundPackageEnvironment> {
         self.package_environment.as_ref()
     }
@@ -167,6 +201,47 @@ impl BoundRuntime {
         self
     }
 
+    pub(crate) fn with_host_functions(mut self, host_functions: HostFunctions) -> Self {
+        self.host_functions = host_functions;
+        self
+    }
+
+    pub(crate) fn host_functions(&self) -> &HostFunctions {
+        &self.host_functions
+    }
+
+    pub(crate) fn state(&self) -> &Arc<SharedState> {
+        self.runtime.state()
+    }
+
+    pub(crate) fn storage(&self) -> Option<&Arc<WebStorage>> {
+        self.runtime.storage()
+    }
+
+    pub(crate) fn module_cache(&self) -> &Arc<ModuleCache> {
+        self.runtime.module_cache()
+    }
+
+    pub(crate) fn op_metrics(&self) -> Option<&Arc<OpMetrics>> {
+        self.runtime.op_metrics()
+    }
+
+    pub(crate) fn next_core(&self) -> Option<usize> {
+        self.runtime.next_core()
+    }
+
+    pub(crate) fn restored_globals(&self) -> Option<&serde_json::Map<String, serde_json::Value>> {
+        self.runtime.restored_globals()
+    }
+
+    pub(crate) fn startup_snapshot(&self) -> Option<&'static [u8]> {
+        self.runtime.startup_snapshot()
+    }
+
+    pub(crate) fn name(&self) -> Option<&str> {
+        self.runtime.name()
+    }
+
     pub(crate) fn script(&self) -> &ScriptSource {
         &self.script
     }
@@ -0,0 +1,63 @@
+use serde_json::{Map, Value};
+
+use crate::types::error::BindingError;
+
+/// Bumped whenever the layout changes, so an old capture fails loudly instead of half-restoring.
+const FORMAT_VERSION: u64 = 1;
+const VERSION_KEY: &str = "belgie_state";
+const GLOBALS_KEY: &str = "globals";
+
+/// Encodes captured globals, already in `PyJsValue`'s tagged JSON form, as UTF-8 JSON.
+pub(crate) fn encode(globals: Map<String, Value>) -> Vec<u8> {
+    let mut state = Map::new();
+    state.insert(VERSION_KEY.to_string(), Value::from(FORMAT_VERSION));
+    state.insert(GLOBALS_KEY.to_string(), Value::Object(globals));
+    serde_json::to_vec(&Value::Object(state)).expect("captured state should serialize")
+}
+
+pub(crate) fn decode(bytes: &[u8]) -> Result<Map<String, Value>, BindingError> {
+    let invalid = || BindingError::value_conversion("restore must be bytes from capture_state()");
+    let Value::Object(mut state) = serde_json::from_slice(bytes).map_err(|_| invalid())? else {
+        return Err(invalid());
+    };
+    match state.get(VERSION_KEY).and_then(Value::as_u64) {
+        Some(FORMAT_VERSION) => {}
+        Some(version) => {
+            return Err(BindingError::value_conversion(format!(
+                "restore was captured in state format {version}; this belgie reads format {FORMAT_VERSION}"
+            )));
+        }
+        None => return Err(invalid()),
+    }
+    match state.remove(GLOBALS_KEY) {
+        Some(Value::Object(globals)) => Ok(globals),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn round_trips_globals_and_rejects_other_bytes() {
+        let globals = json!({ "count": 3, "labels": ["a", "b"] });
+        let Value::Object(globals) = globals else {
+            unreachable!();
+        };
+
+        assert_eq!(
+            decode(&encode(globals.clone())).expect("state should decode"),
+            globals
+        );
+        assert!(decode(b"not json").is_err());
+        assert!(
+            decode(br#"{"belgie_state": 2, "globals": {}}"#)
+                .expect_err("newer formats should be rejected")
+                .message()
+                .contains("format 2")
+        );
+    }
+}
@@ -36,9 +36,11 @@ pub(crate) fn configure_worker(worker: &mut LibMainWorker) -> Result<(), Binding
 }
 
 pub(crate) fn run(module: PathBuf, argv: Vec<String>) -> Result<i32, BindingError> {
-    spawn_v8_worker(move || run_on_worker_thread(&module, argv))
-        .join()
-        .map_err(|_| BindingError::runtime("Belgie child process worker panicked"))?
+    spawn_v8_worker("belgie-child".to_string(), move || {
+        run_on_worker_thread(&module, argv)
+    })
+    .join()
+    .map_err(|_| BindingError::runtime("Belgie child process worker panicked"))?
 }
 
 fn run_on_worker_thread(module: &Path, argv: Vec<String>) -> Result<i32, BindingError> {
@@ -85,6 +87,7 @@ async fn run_async(module: &Path, argv: Vec<String>) -> Result<i32, BindingError
             main_source: None,
             header_overrides: crate::embed::js_content_type_header_overrides(main_module),
             node_ipc_init,
+            module_cache: None,
         },
         &LibWorkerFactoryRoots::default(),
     )
@@ -57,7 +57,7 @@ impl CommandExecutionHandle {
     pub(crate) fn spawn(options: CommandExecutionOptions) -> Self {
         let (cancel, cancel_rx) = watch::channel(false);
         let (respond_to, response) = oneshot::channel();
-        let join_handle = spawn_v8_worker(move || {
+        let join_handle = spawn_v8_worker("belgie-command".to_string(), move || {
             let result = run_command_thread(options, cancel_rx);
             let _ = respond_to.send(result);
         });
@@ -368,6 +368,7 @@ async fn run_js_command(
             main_source: None,
             header_overrides: js_content_type_header_overrides(main_module),
             node_ipc_init: None,
+            module_cache: None,
         },
         &options.worker_factory_roots,
     )
@@ -0,0 +1,37 @@
+use core_affinity::CoreId;
+
+/// Whether this platform can pin a thread to chosen CPUs. `core_affinity` also builds elsewhere,
+/// but only Linux restricts the thread to the core rather than treating it as a hint.
+pub(crate) const SUPPORTED: bool = cfg!(target_os = "linux");
+
+/// The CPUs the calling thread may run on, which bounds the cores a runner can pin to.
+pub(crate) fn allowed_cores() -> Option<Vec<usize>> {
+    if !SUPPORTED {
+        return None;
+    }
+    core_affinity::get_core_ids().map(|cores| cores.into_iter().map(|core| core.id).collect())
+}
+
+/// Restricts the calling thread to `core`; `false` if the kernel refused.
+pub(crate) fn pin_current_thread(core: usize) -> bool {
+    SUPPORTED && core_affinity::set_for_current(CoreId { id: core })
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinning_restricts_the_thread_to_one_core() {
+        let core = *allowed_cores()
+            .expect("affinity should be readable")
+            .last()
+            .expect("the thread should be allowed some core");
+
+        let pinned = std::thread::spawn(move || (pin_current_thread(core), allowed_cores()))
+            .join()
+            .expect("pinning thread should not panic");
+
+        assert_eq!(pinned, (true, Some(vec![core])));
+    }
+}
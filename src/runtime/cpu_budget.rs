@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+/// How often the watchdog samples the worker's CPU clock while a call runs.
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Whether this platform can measure another thread's CPU time.
+pub(crate) const SUPPORTED: bool = cfg!(target_os = "linux");
+
+/// CPU time a call may spend on the worker thread. Time the thread spends blocked, awaiting an
+/// op, a timer, or a Python host function, does not count.
+pub(crate) struct CpuBudget {
+    clock: ThreadCpuClock,
+    started: Duration,
+    budget: Duration,
+}
+
+impl CpuBudget {
+    /// Must start on the worker thread, whose CPU clock the watchdog samples.
+    pub(crate) fn start(budget: Duration) -> Option<Self> {
+        let clock = ThreadCpuClock::current()?;
+        let started = clock.elapsed()?;
+        Some(Self {
+            clock,
+            started,
+            budget,
+        })
+    }
+
+    fn used(&self) -> Option<Duration> {
+        self.clock
+            .elapsed()
+            .map(|elapsed| elapsed.saturating_sub(self.started))
+    }
+
+    /// A clock that can no longer be read belongs to an exited thread and never exhausts.
+    pub(crate) fn exhausted(&self) -> bool {
+        self.used().is_some_and(|used| used >= self.budget)
+    }
+
+    /// How long until the budget could run out, capped so the clock is sampled regularly.
+    pub(crate) fn next_sample(&self) -> Duration {
+        self.used().map_or(SAMPLE_INTERVAL, |used| {
+            self.budget
+                .saturating_sub(used)
+                .clamp(Duration::from_millis(1), SAMPLE_INTERVAL)
+        })
+    }
+}
+
+/// A thread's CPU-time clock, readable from any thread while that thread is alive.
+#[derive(Clone, Copy)]
+struct ThreadCpuClock {
+    #[cfg(target_os = "linux")]
+    id: libc::clockid_t,
+}
+
+impl ThreadCpuClock {
+    #[cfg(target_os = "linux")]
+    fn current() -> Option<Self> {
+        let mut id: libc::clockid_t = 0;
+        // SAFETY: `pthread_self` is always a valid thread and `id` is a valid out pointer.
+        let status = unsafe { libc::pthread_getcpuclockid(libc::pthread_self(), &mut id) };
+        (status == 0).then_some(Self { id })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn current() -> Option<Self> {
+        None
+    }
+
+    #[cfg(target_os = "linux")]
+    fn elapsed(self) -> Option<Duration> {
+        let mut time = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // SAFETY: `time` is a valid out pointer; a clock whose thread exited reports an error.
+        if unsafe { libc::clock_gettime(self.id, &mut time) } != 0 {
+            return None;
+        }
+        Some(Duration::new(
+            u64::try_from(time.tv_sec).ok()?,
+            u32::try_from(time.tv_nsec).ok()?,
+        ))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn elapsed(self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::{thread, time::Instant};
+
+    use super::*;
+
+    #[test]
+    fn thread_cpu_clock_ignores_sleeping() {
+        let clock = ThreadCpuClock::current().expect("thread CPU clock should be available");
+        let before = clock.elapsed().expect("clock should read");
+        thread::sleep(Duration::from_millis(50));
+        let slept = clock.elapsed().expect("clock should read") - before;
+
+        let spin_until = Instant::now() + Duration::from_millis(50);
+        while Instant::now() < spin_until {
+            std::hint::spin_loop();
+        }
+        let spun = clock.elapsed().expect("clock should read") - before - slept;
+
+        assert!(slept < Duration::from_millis(20), "slept {slept:?}");
+        assert!(spun >= Duration::from_millis(30), "spun {spun:?}");
+    }
+}
@@ -1,20 +1,81 @@
-use std::path::Path;
+use std::{
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
 
 use crate::{
-    options::{JsRuntimeOptions, RuntimeEnvironment, RuntimeOptions, RuntimeWorkerOptions},
+    options::{
+        ExecutionOptions, JsRuntimeOptions, RuntimeEnvironment, RuntimeOptions,
+        RuntimeWorkerOptions,
+    },
     script::ScriptSource,
+    types::{error::BindingError, value::PyJsValue},
 };
 
-use super::BoundRuntime;
+use super::{BoundRuntime, OpMetrics, SharedState, WebStorage, module_loader::ModuleCache};
 
 #[derive(Clone, Debug)]
 pub(crate) struct DenoRuntime {
     options: RuntimeOptions,
+    state: Arc<SharedState>,
+    storage: Option<Arc<WebStorage>>,
+    module_cache: Arc<ModuleCache>,
+    op_metrics: Option<Arc<OpMetrics>>,
+    restored_globals: Option<Arc<serde_json::Map<String, serde_json::Value>>>,
+    startup_snapshot: Option<&'static [u8]>,
+    name: Option<String>,
+    /// Runners started so far, which picks each one's `cpu_affinity` core.
+    runners_started: Arc<AtomicUsize>,
 }
 
 impl DenoRuntime {
     pub(crate) fn new(options: RuntimeOptions) -> Self {
-        Self { options }
+        let op_metrics = options
+            .execution()
+            .op_metrics()
+            .then(|| Arc::new(OpMetrics::default()));
+        Self {
+            options,
+            state: Arc::default(),
+            storage: None,
+            module_cache: Arc::default(),
+            op_metrics,
+            restored_globals: None,
+            startup_snapshot: None,
+            name: None,
+            runners_started: Arc::default(),
+        }
+    }
+
+    pub(crate) fn with_name(mut self, name: Option<String>) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Prefixes the names of this runtime's worker threads.
+    pub(crate) fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub(crate) fn with_storage(mut self, storage: Option<WebStorage>) -> Self {
+        self.storage = storage.map(Arc::new);
+        self
+    }
+
+    pub(crate) fn with_restored_globals(
+        mut self,
+        globals: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> Self {
+        self.restored_globals = globals.map(Arc::new);
+        self
+    }
+
+    pub(crate) fn with_startup_snapshot(mut self, snapshot: Option<&'static [u8]>) -> Self {
+        self.startup_snapshot = snapshot;
+        self
     }
 
     pub(crate) fn cwd(&self) -> &Path {
@@ -29,10 +90,64 @@ impl DenoRuntime {
         self.options.worker()
     }
 
+    pub(crate) fn execution_options(&self) -> &ExecutionOptions {
+        self.options.execution()
+    }
+
     pub(crate) fn environment(&self) -> Option<&RuntimeEnvironment> {
         self.options.environment()
     }
 
+    /// The core the next runner pins to. Each runtime starts at the first core of its
+    /// `cpu_affinity`, however many runtimes share the options.
+    pub(crate) fn next_core(&self) -> Option<usize> {
+        let affinity = self.execution_options().cpu_affinity()?;
+        Some(affinity.core(self.runners_started.fetch_add(1, Ordering::Relaxed)))
+    }
+
+    /// Shared by every session and runner created from this runtime.
+    pub(crate) fn state(&self) -> &Arc<SharedState> {
+        &self.state
+    }
+
+    /// Backs `localStorage` in every runner, when the runtime was given a storage mapping.
+    pub(crate) fn storage(&self) -> Option<&Arc<WebStorage>> {
+        self.storage.as_ref()
+    }
+
+    /// Transpiled imports shared by every lightweight runner created from this runtime.
+    pub(crate) fn module_cache(&self) -> &Arc<ModuleCache> {
+        &self.module_cache
+    }
+
+    /// Collected across every runner created from this runtime, when `op_metrics` is enabled.
+    pub(crate) fn op_metrics(&self) -> Option<&Arc<OpMetrics>> {
+        self.op_metrics.as_ref()
+    }
+
+    /// Globals from `capture_state()`, installed in every runner before its module loads.
+    pub(crate) fn restored_globals(&self) -> Option<&serde_json::Map<String, serde_json::Value>> {
+        self.restored_globals.as_deref()
+    }
+
+    /// The heap from `Runtime.create_snapshot()` every runner starts from, on the lightweight
+    /// backend.
+    pub(crate) fn startup_snapshot(&self) -> Option<&'static [u8]> {
+        self.startup_snapshot
+    }
+
+    /// Maps each op name to its calls and total time, or fails when metrics are disabled.
+    pub(crate) fn op_metrics_snapshot(&self) -> Result<PyJsValue, BindingError> {
+        self.op_metrics
+            .as_ref()
+            .map(|metrics| metrics.to_json())
+            .ok_or_else(|| {
+                BindingError::runtime(
+                    "Op metrics are disabled; pass RuntimeOptions(op_metrics=True)",
+                )
+            })
+    }
+
     pub(crate) fn bind(&self, script: ScriptSource) -> BoundRuntime {
         BoundRuntime::new(self.clone(), script)
     }
@@ -1,13 +1,81 @@
-use crate::types::error::BindingError;
+use deno_core::error::{CoreError, CoreErrorKind, JsError, ModuleLoaderError};
+use deno_error::JsErrorClass;
+
+use crate::types::error::{BindingError, JavaScriptErrorDetails, JavaScriptStackFrame};
 
 const MODULE_NOT_FOUND: &str = "Module not found";
+/// How deno_graph reports a module it could not parse while building the graph.
+const PARSE_FAILURE: &str = "The module's source code could not be parsed";
+const SYNTAX_ERROR_CLASS: &str = "SyntaxError";
 
 pub(crate) fn map_package_environment_error(error: impl std::fmt::Display) -> BindingError {
     let message = error.to_string();
     if message.starts_with(MODULE_NOT_FOUND) {
         return BindingError::module_load(message);
     }
+    if message.contains(PARSE_FAILURE) {
+        return BindingError::syntax(message);
+    }
     BindingError::runtime(format!(
         "Environment dependencies are missing or out of date: {message}"
     ))
 }
+
+pub(crate) fn map_core_error(error: CoreError) -> BindingError {
+    match error.as_kind() {
+        CoreErrorKind::Js(js_error) => map_js_error(js_error),
+        _ => BindingError::javascript(error.to_string()),
+    }
+}
+
+/// Loading the main module fails with a V8 `SyntaxError` when the source does not compile.
+pub(crate) fn map_module_load_error(error: CoreError) -> BindingError {
+    let message = error.to_string();
+    match error.as_kind() {
+        CoreErrorKind::Js(js_error) if js_error.name.as_deref() == Some(SYNTAX_ERROR_CLASS) => {
+            BindingError::syntax(message)
+        }
+        _ if message.contains(PARSE_FAILURE) => BindingError::syntax(message),
+        _ => BindingError::module_load(message),
+    }
+}
+
+/// Transpiling reports parse diagnostics as `SyntaxError`-class loader errors.
+pub(crate) fn map_loader_error(error: ModuleLoaderError) -> BindingError {
+    if error.get_class() == SYNTAX_ERROR_CLASS {
+        BindingError::syntax(error.to_string())
+    } else {
+        BindingError::module_load(error.to_string())
+    }
+}
+
+pub(crate) fn map_js_error(error: &JsError) -> BindingError {
+    let frame = error
+        .frames
+        .iter()
+        .find(|frame| frame.file_name.is_some())
+        .or_else(|| error.frames.first());
+    BindingError::javascript_with_details(
+        error.to_string(),
+        JavaScriptErrorDetails {
+            name: error.name.clone(),
+            message: error.message.clone(),
+            stack: error.stack.clone(),
+            filename: frame.and_then(|frame| frame.file_name.clone()),
+            line: frame.and_then(|frame| frame.line_number),
+            column: frame.and_then(|frame| frame.column_number),
+            source_line: error.source_line.clone(),
+            request_id: None,
+            frames: error
+                .frames
+                .iter()
+                .map(|frame| JavaScriptStackFrame {
+                    file: frame.file_name.clone(),
+                    line: frame.line_number,
+                    column: frame.column_number,
+                    function: frame.function_name.clone(),
+                })
+                .collect(),
+        },
+    )
+}
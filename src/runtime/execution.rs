@@ -1,25 +1,49 @@
 use std::{
-    path::PathBuf,
+    any::Any,
+    collections::{BTreeMap, HashMap, HashSet},
+    panic::{AssertUnwindSafe, catch_unwind},
+    path::{Path, PathBuf},
     rc::Rc,
-    sync::{Arc, Mutex, mpsc},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc,
+    },
     thread,
+    time::{Duration, Instant},
 };
 
 use deno_core::{
     ExternalOpsTracker, JsRuntime, ModuleId, ModuleSpecifier, PollEventLoopOptions, RuntimeOptions,
-    error::CoreError, v8,
+    v8,
 };
 use deno_lib::worker::{LibMainWorker, LibWorkerFactoryRoots};
 #[cfg(test)]
 use deno_runtime::tokio_util::create_and_run_current_thread;
 use deno_runtime::tokio_util::create_basic_runtime;
-use tokio::sync::{Notify, oneshot};
+use futures::FutureExt;
+use pyo3::Python;
+use tokio::sync::{
+    Notify,
+    mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+    oneshot,
+};
 
 use crate::{
     embed::{init::spawn_v8_worker, runtime::content_type_header_overrides},
-    runtime::{module_loader, package_worker, process_context},
+    options::BuiltinExtension,
+    runtime::{
+        OpMetrics, captured_state, cpu_affinity,
+        error::{map_core_error, map_js_error, map_loader_error, map_module_load_error},
+        host, isolated_eval, module_loader, native_error, package_worker, performance,
+        process_context,
+        profiler::{self, CpuProfiler},
+        state, storage, text_encoding,
+        watchdog::{CallWatchdog, Expiry},
+        web_abort, web_base64, web_crypto, web_structured_clone, web_url,
+    },
     types::{error::BindingError, runner::RunnerArguments, value::PyJsValue},
-    utils::cancel_guard::Cancel,
+    utils::cancel_guard::{Cancel, CancelGuard},
 };
 
 use super::BoundRuntime;
@@ -27,7 +51,198 @@ use crate::runtime::bound_runtime::BoundPackageEnvironment;
 
 type ExecutionResult<T> = Result<T, BindingError>;
 
+const WORKER_DROP_JOIN_TIMEOUT: Duration = Duration::from_secs(5);
+/// Resource name `deno_fetch` gives the handle that aborts one in-flight request.
+const FETCH_CANCEL_HANDLE: &str = "fetchCancelHandle";
 const RENDER_CONTEXT_SYMBOL: &str = "@belgie/render/context";
+/// Holds the running call's request id so console capture can tag its messages.
+const REQUEST_ID_SYMBOL: &str = "@belgie/request-id";
+const CONSOLE_CAPTURE_SOURCE: &str = r#"(() => {
+  const key = Symbol.for("@belgie/console/messages");
+  if (globalThis[key] !== undefined) {
+    return;
+  }
+  const messages = [];
+  Object.defineProperty(globalThis, key, { value: messages });
+  const format = (value) => {
+    if (typeof value === "string") {
+      return value;
+    }
+    return typeof Deno?.inspect === "function" ? Deno.inspect(value) : String(value);
+  };
+  const requestId = Symbol.for("@belgie/request-id");
+  for (const level of ["debug", "info", "log", "warn", "error"]) {
+    console[level] = (...args) => {
+      const entry = { level, message: args.map(format).join(" ") };
+      if (typeof globalThis[requestId] === "string") {
+        entry.request_id = globalThis[requestId];
+      }
+      messages.push(entry);
+    };
+  }
+})();"#;
+const CONSOLE_DRAIN_SOURCE: &str =
+    r#"globalThis[Symbol.for("@belgie/console/messages")]?.splice(0) ?? []"#;
+/// Maps every own string-keyed global to its `typeof`, reporting getters that throw as `unknown`.
+const GLOBAL_TYPES_SOURCE: &str = r#"Object.fromEntries(
+  Object.getOwnPropertyNames(globalThis).map((name) => {
+    try {
+      return [name, typeof globalThis[name]];
+    } catch {
+      return [name, "unknown"];
+    }
+  }),
+)"#;
+/// Deno workers dispatch `unhandledrejection` only once a microtask checkpoint leaves a
+/// rejection unhandled; the lightweight runtime exposes the same decision through
+/// `Deno.core`. Either way, promises that gain a handler in time are never recorded.
+const REJECTION_CAPTURE_SOURCE: &str = r#"(() => {
+  const key = Symbol.for("@belgie/unhandled-rejections");
+  if (globalThis[key] !== undefined) {
+    return;
+  }
+  const rejections = [];
+  Object.defineProperty(globalThis, key, { value: rejections });
+  const record = (reason) => {
+    if (reason instanceof Error) {
+      rejections.push(reason.stack ?? String(reason));
+    } else {
+      rejections.push(typeof Deno?.inspect === "function" ? Deno.inspect(reason) : String(reason));
+    }
+  };
+  if (typeof globalThis.addEventListener === "function") {
+    globalThis.addEventListener("unhandledrejection", (event) => {
+      event.preventDefault();
+      record(event.reason);
+    });
+  } else {
+    Deno.core.setUnhandledPromiseRejectionHandler((_promise, reason) => {
+      record(reason);
+      return true;
+    });
+  }
+})();"#;
+const REJECTION_DRAIN_SOURCE: &str =
+    r#"globalThis[Symbol.for("@belgie/unhandled-rejections")]?.splice(0) ?? []"#;
+/// Called with the configured variables. Replaces `Deno.env` (and `process.env`, where the
+/// worker has one) with an in-memory map, so scripts never see the host's variables. On Deno
+/// workers each access still goes through the real `Deno.env.get`, whose value is discarded, so
+/// `allow_env` / `deny_env` decide which names a script may touch.
+const ENV_VARS_SOURCE: &str = r#"((variables) => {
+  const values = new Map(Object.entries(variables));
+  const hostEnv = globalThis.Deno?.env;
+  const hostGet = typeof hostEnv?.get === "function" ? hostEnv.get.bind(hostEnv) : undefined;
+  const checked = (name) => {
+    const key = String(name);
+    hostGet?.(key);
+    return key;
+  };
+  const env = {
+    get: (name) => values.get(checked(name)),
+    has: (name) => values.has(checked(name)),
+    set: (name, value) => {
+      values.set(checked(name), String(value));
+    },
+    delete: (name) => {
+      values.delete(checked(name));
+    },
+    toObject: () => {
+      for (const name of values.keys()) {
+        checked(name);
+      }
+      return Object.fromEntries(values);
+    },
+  };
+  globalThis.Deno ??= {};
+  if (!Reflect.defineProperty(Deno, "env", { value: env, writable: true, configurable: true })) {
+    throw new TypeError("Deno.env cannot be replaced in this runtime");
+  }
+  if (typeof globalThis.process !== "object" || globalThis.process === null) {
+    return;
+  }
+  const processEnv = new Proxy({}, {
+    get: (_target, name) => (typeof name === "string" ? env.get(name) : undefined),
+    has: (_target, name) => typeof name === "string" && env.has(name),
+    set: (_target, name, value) => {
+      env.set(name, value);
+      return true;
+    },
+    deleteProperty: (_target, name) => {
+      env.delete(name);
+      return true;
+    },
+    ownKeys: () => Object.keys(env.toObject()),
+    getOwnPropertyDescriptor: (_target, name) =>
+      typeof name === "string" && env.has(name)
+        ? { value: env.get(name), writable: true, enumerable: true, configurable: true }
+        : undefined,
+  });
+  Reflect.defineProperty(process, "env", { value: processEnv, writable: true, configurable: true });
+})"#;
+/// Called with `process.platform`. Only installed where no `process` global exists, so the Deno
+/// worker keeps its Node-compatible one; `env_vars` later swaps `env` for its own mapping.
+const NODE_COMPAT_SOURCE: &str = r#"((platform) => {
+  if (globalThis.process !== undefined) {
+    return;
+  }
+  const process = {
+    env: {},
+    platform,
+    version: "v22.0.0",
+    versions: { node: "22.0.0" },
+    argv: [],
+    browser: false,
+    nextTick: (callback, ...args) => {
+      if (typeof callback !== "function") {
+        throw new TypeError("process.nextTick callback must be a function");
+      }
+      queueMicrotask(() => callback(...args));
+    },
+  };
+  Object.defineProperty(globalThis, "process", {
+    value: process,
+    writable: true,
+    configurable: true,
+  });
+})"#;
+/// Called with the generator state and frozen time. `Math.random` is mulberry32; `Date` keeps
+/// parsing and explicit timestamps but reads the frozen time wherever it would read the clock.
+/// Both are locked in place, so setup and scripts cannot swap the real clock or generator back.
+const DETERMINISM_SOURCE: &str = r#"((seed, now) => {
+  let state = seed;
+  Object.defineProperty(Math, "random", {
+    value: function random() {
+      state = (state + 0x6d2b79f5) | 0;
+      let value = Math.imul(state ^ (state >>> 15), 1 | state);
+      value = (value + Math.imul(value ^ (value >>> 7), 61 | value)) ^ value;
+      return ((value ^ (value >>> 14)) >>> 0) / 4294967296;
+    },
+    writable: false,
+    configurable: false,
+  });
+  const RealDate = Date;
+  class FrozenDate extends RealDate {
+    constructor(...args) {
+      if (args.length === 0) {
+        super(now);
+      } else {
+        super(...args);
+      }
+    }
+    static now() {
+      return now;
+    }
+  }
+  Object.defineProperty(FrozenDate, "name", { value: "Date" });
+  Object.freeze(FrozenDate);
+  Object.defineProperty(globalThis, "Date", {
+    value: new Proxy(FrozenDate, {
+      apply: () => new RealDate(now).toString(),
+    }),
+    writable: false,
+    configurable: false,
+  });
+})"#;
 const SAFE_PROCESS_ENVIRONMENT: [(&str, &str); 3] = [
     ("APPVEYOR", "1"),
     ("NODE_ENV", "production"),
@@ -41,42 +256,204 @@ pub(crate) struct DenoExecutionHandle {
 
 #[derive(Debug)]
 struct DenoExecutionHandleInner {
-    sender: mpsc::Sender<ExecutionCommand>,
+    sender: mpsc::SyncSender<ExecutionCommand>,
     isolate_handle: Arc<Mutex<Option<v8::IsolateHandle>>>,
-    shutdown: Arc<Notify>,
+    serving: Arc<AtomicBool>,
+    signals: Arc<WorkerSignals>,
+    next_call: AtomicU64,
     join_handle: Mutex<Option<thread::JoinHandle<()>>>,
 }
 
+/// Identifies a queued call so cancelling it can never interrupt whichever call runs next.
+type CallId = u64;
+
+/// Wakeups the worker thread selects on while a call is running.
+#[derive(Debug, Default)]
+struct WorkerSignals {
+    shutdown: Notify,
+    interrupt: Notify,
+    running: Mutex<RunningCall>,
+}
+
+#[derive(Debug, Default)]
+struct RunningCall {
+    call: Option<CallId>,
+    interrupted: Option<Interruption>,
+}
+
+/// Why a running call was terminated before it finished.
+#[derive(Clone, Copy, Debug)]
+enum Interruption {
+    /// The call's async caller stopped waiting for it.
+    CallerCancelled,
+    /// `Runtime.interrupt()` stopped whatever was running.
+    Requested,
+}
+
+#[derive(Debug)]
+enum InvokeTarget {
+    /// Loads and evaluates the module without calling its run function.
+    Load,
+    /// Evaluates the module and reads its default export as a value.
+    DefaultExport,
+    Call(RunnerArguments),
+}
+
 #[derive(Debug)]
 enum ExecutionCommand {
     Invoke {
+        call: CallId,
+        target: InvokeTarget,
+        /// Receives `host.emit` chunks; the call is abandoned once its receiver is dropped.
+        chunks: Option<UnboundedSender<PyJsValue>>,
+        respond_to: oneshot::Sender<ExecutionResult<Invocation>>,
+    },
+    /// Runs each call in order on the same isolate; one failing call does not stop the rest.
+    Batch {
+        call: CallId,
+        calls: Vec<RunnerArguments>,
+        respond_to: oneshot::Sender<ExecutionResult<Vec<ExecutionResult<PyJsValue>>>>,
+    },
+    /// Repeats one call so V8 tiers up its hot paths; stops at the first failing call.
+    Warmup {
+        call: CallId,
         arguments: RunnerArguments,
+        iterations: usize,
+        respond_to: oneshot::Sender<ExecutionResult<()>>,
+    },
+    /// Answered once the isolate exists and setup has run, without loading the module.
+    Ready {
+        respond_to: oneshot::Sender<ExecutionResult<()>>,
+    },
+    DrainConsole {
+        respond_to: oneshot::Sender<ExecutionResult<PyJsValue>>,
+    },
+    DrainRejections {
+        respond_to: oneshot::Sender<ExecutionResult<PyJsValue>>,
+    },
+    Globals {
+        include_builtins: bool,
         respond_to: oneshot::Sender<ExecutionResult<PyJsValue>>,
     },
+    HeapStats {
+        respond_to: oneshot::Sender<ExecutionResult<PyJsValue>>,
+    },
+    StartProfiling {
+        respond_to: oneshot::Sender<ExecutionResult<()>>,
+    },
+    StopProfiling {
+        respond_to: oneshot::Sender<ExecutionResult<Vec<u8>>>,
+    },
+    HeapSnapshot {
+        respond_to: oneshot::Sender<ExecutionResult<Vec<u8>>>,
+    },
+    CaptureState {
+        respond_to: oneshot::Sender<ExecutionResult<Vec<u8>>>,
+    },
+    Reset {
+        respond_to: oneshot::Sender<ExecutionResult<()>>,
+    },
     Shutdown,
 }
 
+#[derive(Debug)]
+pub(crate) struct Invocation {
+    pub(crate) value: PyJsValue,
+    pub(crate) timings: CallTimings,
+    /// Values passed to `host.warn` during the call, in order.
+    pub(crate) diagnostics: Vec<PyJsValue>,
+}
+
+/// A call whose `host.emit` chunks arrive before its return value. Dropping it stops the call.
+#[derive(Debug)]
+pub(crate) struct InvocationStream {
+    chunks: UnboundedReceiver<PyJsValue>,
+    result: oneshot::Receiver<ExecutionResult<Invocation>>,
+}
+
+/// Wall-clock phases of one call, measured on the worker thread.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct CallTimings {
+    /// Module loading, transpiling, and evaluation; zero once the module is loaded.
+    compile: Duration,
+    /// The synchronous part of the exported function.
+    run: Duration,
+    /// Event loop turns until the returned promise settles.
+    event_loop: Duration,
+}
+
+impl CallTimings {
+    pub(crate) fn to_json(self) -> PyJsValue {
+        let milliseconds = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        PyJsValue::from_json(serde_json::json!({
+            "compile_ms": milliseconds(self.compile),
+            "run_ms": milliseconds(self.run),
+            "event_loop_ms": milliseconds(self.event_loop),
+        }))
+    }
+}
+
+impl InvocationStream {
+    /// The next emitted chunk, or `None` once the call has returned or failed.
+    pub(crate) fn next_blocking(&mut self) -> Option<PyJsValue> {
+        self.chunks.blocking_recv()
+    }
+
+    pub(crate) async fn next_async(&mut self) -> Option<PyJsValue> {
+        self.chunks.recv().await
+    }
+
+    /// The call's return value; only meaningful after `next_*` returned `None`.
+    pub(crate) fn finish_blocking(self) -> ExecutionResult<PyJsValue> {
+        self.result
+            .blocking_recv()
+            .map_err(|_| BindingError::runtime("Deno execution worker stopped unexpectedly"))?
+            .map(|invocation| invocation.value)
+    }
+
+    pub(crate) async fn finish_async(self) -> ExecutionResult<PyJsValue> {
+        self.result
+            .await
+            .map_err(|_| BindingError::runtime("Deno execution worker stopped unexpectedly"))?
+            .map(|invocation| invocation.value)
+    }
+}
+
 impl DenoExecutionHandle {
     pub(crate) fn new(bound: BoundRuntime, worker_factory_roots: LibWorkerFactoryRoots) -> Self {
-        let (sender, receiver) = mpsc::channel();
+        let (sender, receiver) = mpsc::sync_channel(bound.execution_options().queue_size());
         let isolate_handle = Arc::new(Mutex::new(None));
         let worker_isolate_handle = isolate_handle.clone();
-        let shutdown = Arc::new(Notify::new());
-        let worker_shutdown = shutdown.clone();
-        let join_handle = spawn_v8_worker(move || {
+        let serving = Arc::new(AtomicBool::new(true));
+        let worker_serving = serving.clone();
+        let signals = Arc::new(WorkerSignals::default());
+        let worker_signals = signals.clone();
+        let thread_name = worker_thread_name(bound.name());
+        let core = bound.next_core();
+        let join_handle = spawn_v8_worker(thread_name, move || {
+            // Affinity was validated when the options were built; a refusal here leaves the
+            // thread unpinned rather than failing the runner.
+            if let Some(core) = core
+                && !cpu_affinity::pin_current_thread(core)
+            {
+                log::warn!("Could not pin runner thread to CPU core {core}; it runs unpinned");
+            }
             run_worker_thread(
                 bound,
                 worker_factory_roots,
                 receiver,
                 worker_isolate_handle,
-                worker_shutdown,
+                worker_serving,
+                worker_signals,
             )
         });
         Self {
             inner: Arc::new(DenoExecutionHandleInner {
                 sender,
                 isolate_handle,
-                shutdown,
+                serving,
+                signals,
+                next_call: AtomicU64::new(0),
                 join_handle: Mutex::new(Some(join_handle)),
             }),
         }
@@ -90,6 +467,11 @@ impl DenoExecutionHandle {
             .is_none()
     }
 
+    /// Whether the worker thread is still accepting calls, without queueing a command.
+    pub(crate) fn is_alive(&self) -> bool {
+        !self.is_closed() && self.inner.serving.load(Ordering::Acquire)
+    }
+
     pub(crate) fn close_blocking(&self) -> ExecutionResult<()> {
         let join_handle = self
             .inner
@@ -101,165 +483,1049 @@ impl DenoExecutionHandle {
             return Ok(());
         };
 
-        self.cancel();
+        if !self.inner.signal_shutdown() {
+            // The worker drains the full queue once cancelled, so this send cannot stall.
+            let _ = self.inner.sender.send(ExecutionCommand::Shutdown);
+        }
         join_handle
             .join()
-            .map_err(|_| BindingError::runtime("Deno execution worker panicked"))?;
+            .map_err(|payload| worker_panic_error(payload.as_ref()))?;
         Ok(())
     }
 
     pub(crate) fn cancel(&self) {
-        self.inner.signal_shutdown();
+        let _ = self.inner.signal_shutdown();
+    }
+
+    /// Whether both handles drive the same worker thread.
+    pub(crate) fn same_worker(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Terminates the call running on the worker, leaving queued calls and the worker intact.
+    /// Returns whether a call was running.
+    pub(crate) fn interrupt(&self) -> bool {
+        self.inner
+            .signals
+            .interrupt_running(&self.inner.isolate_handle)
     }
 
     pub(crate) fn invoke_blocking(&self, arguments: RunnerArguments) -> ExecutionResult<PyJsValue> {
+        self.invoke_timed_blocking(arguments)
+            .map(|invocation| invocation.value)
+    }
+
+    pub(crate) async fn invoke_async(
+        &self,
+        arguments: RunnerArguments,
+    ) -> ExecutionResult<PyJsValue> {
+        self.invoke_timed_async(arguments)
+            .await
+            .map(|invocation| invocation.value)
+    }
+
+    pub(crate) fn invoke_timed_blocking(
+        &self,
+        arguments: RunnerArguments,
+    ) -> ExecutionResult<Invocation> {
+        let call = self.next_call();
+        self.request_blocking(|respond_to| ExecutionCommand::Invoke {
+            call,
+            target: InvokeTarget::Call(arguments),
+            chunks: None,
+            respond_to,
+        })
+    }
+
+    pub(crate) async fn invoke_timed_async(
+        &self,
+        arguments: RunnerArguments,
+    ) -> ExecutionResult<Invocation> {
+        self.request_call_async(|call, respond_to| ExecutionCommand::Invoke {
+            call,
+            target: InvokeTarget::Call(arguments),
+            chunks: None,
+            respond_to,
+        })
+        .await
+    }
+
+    pub(crate) fn batch_blocking(
+        &self,
+        calls: Vec<RunnerArguments>,
+    ) -> ExecutionResult<Vec<ExecutionResult<PyJsValue>>> {
+        let call = self.next_call();
+        self.request_blocking(|respond_to| ExecutionCommand::Batch {
+            call,
+            calls,
+            respond_to,
+        })
+    }
+
+    pub(crate) async fn batch_async(
+        &self,
+        calls: Vec<RunnerArguments>,
+    ) -> ExecutionResult<Vec<ExecutionResult<PyJsValue>>> {
+        self.request_call_async(|call, respond_to| ExecutionCommand::Batch {
+            call,
+            calls,
+            respond_to,
+        })
+        .await
+    }
+
+    pub(crate) fn warmup_blocking(
+        &self,
+        arguments: RunnerArguments,
+        iterations: usize,
+    ) -> ExecutionResult<()> {
+        let call = self.next_call();
+        self.request_blocking(|respond_to| ExecutionCommand::Warmup {
+            call,
+            arguments,
+            iterations,
+            respond_to,
+        })
+    }
+
+    pub(crate) async fn warmup_async(
+        &self,
+        arguments: RunnerArguments,
+        iterations: usize,
+    ) -> ExecutionResult<()> {
+        self.request_call_async(|call, respond_to| ExecutionCommand::Warmup {
+            call,
+            arguments,
+            iterations,
+            respond_to,
+        })
+        .await
+    }
+
+    /// Queues a streaming call without waiting for it to start.
+    pub(crate) fn stream_blocking(
+        &self,
+        arguments: RunnerArguments,
+    ) -> ExecutionResult<InvocationStream> {
+        let (command, stream) = stream_command(self.next_call(), arguments);
+        self.send_blocking(command)?;
+        Ok(stream)
+    }
+
+    pub(crate) async fn stream_async(
+        &self,
+        arguments: RunnerArguments,
+    ) -> ExecutionResult<InvocationStream> {
+        let (command, stream) = stream_command(self.next_call(), arguments);
+        self.send_async(command).await?;
+        Ok(stream)
+    }
+
+    /// Compiles and evaluates the module so load errors surface before the first call.
+    pub(crate) fn load_blocking(&self) -> ExecutionResult<()> {
+        let call = self.next_call();
+        self.request_blocking(|respond_to| ExecutionCommand::Invoke {
+            call,
+            target: InvokeTarget::Load,
+            chunks: None,
+            respond_to,
+        })
+        .map(drop)
+    }
+
+    pub(crate) async fn load_async(&self) -> ExecutionResult<()> {
+        self.request_call_async(|call, respond_to| ExecutionCommand::Invoke {
+            call,
+            target: InvokeTarget::Load,
+            chunks: None,
+            respond_to,
+        })
+        .await
+        .map(drop)
+    }
+
+    /// Evaluates the module once and returns its default export, or `null` without one.
+    pub(crate) fn default_export_blocking(&self) -> ExecutionResult<PyJsValue> {
+        let call = self.next_call();
+        self.request_blocking(|respond_to| ExecutionCommand::Invoke {
+            call,
+            target: InvokeTarget::DefaultExport,
+            chunks: None,
+            respond_to,
+        })
+        .map(|invocation| invocation.value)
+    }
+
+    pub(crate) async fn default_export_async(&self) -> ExecutionResult<PyJsValue> {
+        self.request_call_async(|call, respond_to| ExecutionCommand::Invoke {
+            call,
+            target: InvokeTarget::DefaultExport,
+            chunks: None,
+            respond_to,
+        })
+        .await
+        .map(|invocation| invocation.value)
+    }
+
+    pub(crate) fn drain_console_blocking(&self) -> ExecutionResult<PyJsValue> {
+        self.request_blocking(|respond_to| ExecutionCommand::DrainConsole { respond_to })
+    }
+
+    pub(crate) async fn drain_console_async(&self) -> ExecutionResult<PyJsValue> {
+        self.request_async(|respond_to| ExecutionCommand::DrainConsole { respond_to })
+            .await
+    }
+
+    pub(crate) fn drain_rejections_blocking(&self) -> ExecutionResult<PyJsValue> {
+        self.request_blocking(|respond_to| ExecutionCommand::DrainRejections { respond_to })
+    }
+
+    pub(crate) async fn drain_rejections_async(&self) -> ExecutionResult<PyJsValue> {
+        self.request_async(|respond_to| ExecutionCommand::DrainRejections { respond_to })
+            .await
+    }
+
+    /// Loads the module first so the snapshot reflects its top-level code.
+    pub(crate) fn globals_blocking(&self, include_builtins: bool) -> ExecutionResult<PyJsValue> {
+        self.load_blocking()?;
+        self.request_blocking(|respond_to| ExecutionCommand::Globals {
+            include_builtins,
+            respond_to,
+        })
+    }
+
+    pub(crate) async fn globals_async(&self, include_builtins: bool) -> ExecutionResult<PyJsValue> {
+        self.load_async().await?;
+        self.request_async(|respond_to| ExecutionCommand::Globals {
+            include_builtins,
+            respond_to,
+        })
+        .await
+    }
+
+    pub(crate) fn ready_blocking(&self) -> ExecutionResult<()> {
+        self.request_blocking(|respond_to| ExecutionCommand::Ready { respond_to })
+    }
+
+    pub(crate) async fn ready_async(&self) -> ExecutionResult<()> {
+        self.request_async(|respond_to| ExecutionCommand::Ready { respond_to })
+            .await
+    }
+
+    pub(crate) fn heap_stats_blocking(&self) -> ExecutionResult<PyJsValue> {
+        self.request_blocking(|respond_to| ExecutionCommand::HeapStats { respond_to })
+    }
+
+    pub(crate) async fn heap_stats_async(&self) -> ExecutionResult<PyJsValue> {
+        self.request_async(|respond_to| ExecutionCommand::HeapStats { respond_to })
+            .await
+    }
+
+    pub(crate) fn start_profiling_blocking(&self) -> ExecutionResult<()> {
+        self.request_blocking(|respond_to| ExecutionCommand::StartProfiling { respond_to })
+    }
+
+    pub(crate) async fn start_profiling_async(&self) -> ExecutionResult<()> {
+        self.request_async(|respond_to| ExecutionCommand::StartProfiling { respond_to })
+            .await
+    }
+
+    pub(crate) fn stop_profiling_blocking(&self) -> ExecutionResult<Vec<u8>> {
+        self.request_blocking(|respond_to| ExecutionCommand::StopProfiling { respond_to })
+    }
+
+    pub(crate) async fn stop_profiling_async(&self) -> ExecutionResult<Vec<u8>> {
+        self.request_async(|respond_to| ExecutionCommand::StopProfiling { respond_to })
+            .await
+    }
+
+    pub(crate) fn heap_snapshot_blocking(&self) -> ExecutionResult<Vec<u8>> {
+        self.request_blocking(|respond_to| ExecutionCommand::HeapSnapshot { respond_to })
+    }
+
+    pub(crate) async fn heap_snapshot_async(&self) -> ExecutionResult<Vec<u8>> {
+        self.request_async(|respond_to| ExecutionCommand::HeapSnapshot { respond_to })
+            .await
+    }
+
+    /// Loads the module first so globals set by its top-level code are captured too.
+    pub(crate) fn capture_state_blocking(&self) -> ExecutionResult<Vec<u8>> {
+        self.load_blocking()?;
+        self.request_blocking(|respond_to| ExecutionCommand::CaptureState { respond_to })
+    }
+
+    pub(crate) async fn capture_state_async(&self) -> ExecutionResult<Vec<u8>> {
+        self.load_async().await?;
+        self.request_async(|respond_to| ExecutionCommand::CaptureState { respond_to })
+            .await
+    }
+
+    /// Replaces the script's isolate with a fresh one; module state and globals start over.
+    pub(crate) fn reset_blocking(&self) -> ExecutionResult<()> {
+        self.request_blocking(|respond_to| ExecutionCommand::Reset { respond_to })
+    }
+
+    pub(crate) async fn reset_async(&self) -> ExecutionResult<()> {
+        self.request_async(|respond_to| ExecutionCommand::Reset { respond_to })
+            .await
+    }
+
+    fn request_blocking<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<ExecutionResult<T>>) -> ExecutionCommand,
+    ) -> ExecutionResult<T> {
+        let (respond_to, response) = oneshot::channel();
+        self.send_blocking(command(respond_to))?;
+        response
+            .blocking_recv()
+            .map_err(|_| BindingError::runtime("Deno execution worker stopped unexpectedly"))?
+    }
+
+    async fn request_async<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<ExecutionResult<T>>) -> ExecutionCommand,
+    ) -> ExecutionResult<T> {
+        let (respond_to, response) = oneshot::channel();
+        self.send_async(command(respond_to)).await?;
+        response
+            .await
+            .map_err(|_| BindingError::runtime("Deno execution worker stopped unexpectedly"))?
+    }
+
+    /// Like `request_async`, but dropping the future interrupts the call instead of leaving it
+    /// running; the worker then stays ready for the next call.
+    async fn request_call_async<T>(
+        &self,
+        command: impl FnOnce(CallId, oneshot::Sender<ExecutionResult<T>>) -> ExecutionCommand,
+    ) -> ExecutionResult<T> {
+        let call = self.next_call();
+        let mut guard = CancelGuard::new(CallInterrupt {
+            inner: &self.inner,
+            call,
+        });
+        let result = self
+            .request_async(|respond_to| command(call, respond_to))
+            .await;
+        guard.disarm();
+        result
+    }
+
+    fn next_call(&self) -> CallId {
+        self.inner.next_call.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Blocks while the command queue is full.
+    fn send_blocking(&self, command: ExecutionCommand) -> ExecutionResult<()> {
         if self.is_closed() {
-            return Err(BindingError::runtime("Deno execution runner is closed"));
+            return Err(BindingError::closed("Deno execution runner is closed"));
         }
-        let (respond_to, response) = oneshot::channel();
         self.inner
             .sender
-            .send(ExecutionCommand::Invoke {
+            .send(command)
+            .map_err(|_| worker_unavailable())
+    }
+
+    /// Suspends while the command queue is full instead of blocking the event loop thread.
+    async fn send_async(&self, command: ExecutionCommand) -> ExecutionResult<()> {
+        if self.is_closed() {
+            return Err(BindingError::closed("Deno execution runner is closed"));
+        }
+        match self.inner.sender.try_send(command) {
+            Ok(()) => Ok(()),
+            Err(mpsc::TrySendError::Full(command)) => {
+                let sender = self.inner.sender.clone();
+                tokio::task::spawn_blocking(move || sender.send(command))
+                    .await
+                    .map_err(|_| worker_unavailable())?
+                    .map_err(|_| worker_unavailable())
+            }
+            Err(mpsc::TrySendError::Disconnected(_)) => Err(worker_unavailable()),
+        }
+    }
+}
+
+fn stream_command(
+    call: CallId,
+    arguments: RunnerArguments,
+) -> (ExecutionCommand, InvocationStream) {
+    let (chunk_sender, chunks) = unbounded_channel();
+    let (respond_to, result) = oneshot::channel();
+    let command = ExecutionCommand::Invoke {
+        call,
+        target: InvokeTarget::Call(arguments),
+        chunks: Some(chunk_sender),
+        respond_to,
+    };
+    (command, InvocationStream { chunks, result })
+}
+
+fn worker_unavailable() -> BindingError {
+    BindingError::runtime("Deno execution worker is not available")
+}
+
+impl Cancel for DenoExecutionHandle {
+    fn cancel(&self) {
+        DenoExecutionHandle::cancel(self);
+    }
+}
+
+/// Interrupts one call whose caller stopped waiting for it.
+struct CallInterrupt<'a> {
+    inner: &'a DenoExecutionHandleInner,
+    call: CallId,
+}
+
+impl Cancel for CallInterrupt<'_> {
+    fn cancel(&self) {
+        self.inner.signals.interrupt(
+            self.call,
+            Interruption::CallerCancelled,
+            &self.inner.isolate_handle,
+        );
+    }
+}
+
+impl WorkerSignals {
+    fn begin_call(&self, call: CallId) {
+        *self.running_call() = RunningCall {
+            call: Some(call),
+            interrupted: None,
+        };
+    }
+
+    /// Terminates `call` only while it is the one running; queued calls are skipped instead.
+    fn interrupt(
+        &self,
+        call: CallId,
+        reason: Interruption,
+        isolate_handle: &Mutex<Option<v8::IsolateHandle>>,
+    ) -> bool {
+        let mut running = self.running_call();
+        if running.call != Some(call) || running.interrupted.is_some() {
+            return false;
+        }
+        running.interrupted = Some(reason);
+        if let Some(handle) = isolate_handle
+            .lock()
+            .expect("execution isolate handle lock should not be poisoned")
+            .as_ref()
+        {
+            handle.terminate_execution();
+        }
+        self.interrupt.notify_one();
+        true
+    }
+
+    /// Terminates whichever call is running, if any.
+    fn interrupt_running(&self, isolate_handle: &Mutex<Option<v8::IsolateHandle>>) -> bool {
+        let running = self.running_call().call;
+        running.is_some_and(|call| self.interrupt(call, Interruption::Requested, isolate_handle))
+    }
+
+    fn interruption(&self) -> Option<Interruption> {
+        self.running_call().interrupted
+    }
+
+    /// Reports why the finished call was interrupted, discarding an unused wakeup.
+    fn finish_call(&self) -> Option<Interruption> {
+        let interrupted = std::mem::take(&mut *self.running_call()).interrupted;
+        if interrupted.is_some() {
+            let _ = self.interrupt.notified().now_or_never();
+        }
+        interrupted
+    }
+
+    fn running_call(&self) -> std::sync::MutexGuard<'_, RunningCall> {
+        self.running
+            .lock()
+            .expect("running call lock should not be poisoned")
+    }
+}
+
+impl DenoExecutionHandleInner {
+    /// Returns whether `Shutdown` was queued.
+    fn signal_shutdown(&self) -> bool {
+        if let Some(handle) = self
+            .isolate_handle
+            .lock()
+            .expect("execution isolate handle lock should not be poisoned")
+            .as_ref()
+        {
+            handle.terminate_execution();
+        }
+        self.signals.shutdown.notify_one();
+        // Never block here: a full queue is drained by the cancelled worker, and dropping the
+        // sender ends its receive loop even if Shutdown never fits.
+        self.sender.try_send(ExecutionCommand::Shutdown).is_ok()
+    }
+}
+
+impl Drop for DenoExecutionHandleInner {
+    fn drop(&mut self) {
+        let _ = self.signal_shutdown();
+        if let Some(join_handle) = self
+            .join_handle
+            .lock()
+            .expect("execution worker join handle lock should not be poisoned")
+            .take()
+        {
+            // Shutdown terminates running JavaScript, but a worker blocked outside V8 (for
+            // example in a host function waiting on Python) must not hang the dropping thread.
+            let wait = move || {
+                let deadline = Instant::now() + WORKER_DROP_JOIN_TIMEOUT;
+                while !join_handle.is_finished() && Instant::now() < deadline {
+                    thread::sleep(Duration::from_millis(1));
+                }
+                if join_handle.is_finished() {
+                    let _ = join_handle.join();
+                }
+            };
+            // Python frees runners with the GIL held, and a worker finishing a host call needs it,
+            // so wait without it rather than stall every Python thread until the timeout.
+            // SAFETY: both calls only read interpreter state and are valid on any thread.
+            let holds_gil = unsafe {
+                pyo3::ffi::Py_IsInitialized() != 0 && pyo3::ffi::PyGILState_Check() == 1
+            };
+            if holds_gil {
+                Python::attach(|py| py.detach(wait));
+            } else {
+                wait();
+            }
+        }
+    }
+}
+
+fn run_worker_thread(
+    bound: BoundRuntime,
+    worker_factory_roots: LibWorkerFactoryRoots,
+    receiver: mpsc::Receiver<ExecutionCommand>,
+    isolate_handle: Arc<Mutex<Option<v8::IsolateHandle>>>,
+    serving: Arc<AtomicBool>,
+    signals: Arc<WorkerSignals>,
+) {
+    let _serving = ServingGuard(serving.clone());
+    // Stays current-thread. `block_on` would poll the isolate here on any flavor, but deno_core
+    // polls pending ops from a task it starts with `deno_unsync::spawn`, which passes the
+    // non-`Send` op futures off as `Send` and is only sound on a current-thread runtime; a
+    // multi-thread one would run that task on a pool thread, beside the isolate. Async ops still
+    // overlap here and blocking ops hop to Tokio's blocking pool, so parallelism across cores
+    // comes from more runners, not more threads here.
+    let runtime = create_basic_runtime();
+    host::set_worker_host_functions(bound.host_functions().clone());
+    host::set_worker_op_metrics(bound.op_metrics().cloned());
+    state::set_worker_state(bound.state().clone());
+    storage::set_worker_storage(bound.storage().cloned());
+    native_error::set_worker_backtraces(bound.execution_options().native_backtraces());
+    let mut context = {
+        let _process_context = process_context::blocking_guard();
+        match runtime.block_on(DenoExecutionContext::new(bound, &worker_factory_roots)) {
+            Ok(context) => context,
+            Err(error) => {
+                reject_commands(&receiver, &serving, &error);
+                return;
+            }
+        }
+    };
+    let (mut watchdog, mut heap_limit) = attach_isolate(&mut context, &isolate_handle);
+
+    while let Ok(command) = receiver.recv() {
+        match command {
+            ExecutionCommand::Invoke {
+                call,
+                target,
+                chunks,
+                respond_to,
+            } => {
+                // The caller was cancelled while this call was still queued.
+                if respond_to.is_closed() {
+                    continue;
+                }
+                signals.begin_call(call);
+                let result = run_invocation(
+                    &runtime,
+                    &mut context,
+                    &mut watchdog,
+                    &heap_limit,
+                    &signals,
+                    target,
+                    chunks,
+                );
+                let result = finish_call(&signals, &mut context, result);
+                let cancelled = is_cancellation(&result);
+                let panicked = worker_panic(&result);
+                let _ = respond_to.send(result);
+                // Leave terminate set until final teardown (Deno kill path). Reject any
+                // Invokes queued ahead of Shutdown so they cannot run after cancel.
+                if cancelled {
+                    reject_commands(&receiver, &serving, &shutdown_error());
+                    break;
+                }
+                if let Some(error) = panicked {
+                    reject_commands(&receiver, &serving, &error);
+                    break;
+                }
+            }
+            ExecutionCommand::Batch {
+                call,
+                calls,
+                respond_to,
+            } => {
+                if respond_to.is_closed() {
+                    continue;
+                }
+                signals.begin_call(call);
+                let mut results = Vec::with_capacity(calls.len());
+                let mut cancelled = false;
+                let mut panicked = None;
+                for arguments in calls {
+                    if cancelled {
+                        results.push(Err(cancellation_error()));
+                        continue;
+                    }
+                    if let Some(error) = &panicked {
+                        results.push(Err(error.clone()));
+                        continue;
+                    }
+                    if let Some(interruption) = signals.interruption() {
+                        results.push(Err(interruption_error(interruption)));
+                        continue;
+                    }
+                    let result = run_invocation(
+                        &runtime,
+                        &mut context,
+                        &mut watchdog,
+                        &heap_limit,
+                        &signals,
+                        InvokeTarget::Call(arguments),
+                        None,
+                    );
+                    cancelled = is_cancellation(&result);
+                    panicked = worker_panic(&result);
+                    results.push(result.map(|invocation| invocation.value));
+                }
+                let results = finish_call(&signals, &mut context, Ok(results));
+                let _ = respond_to.send(results);
+                if cancelled {
+                    reject_commands(&receiver, &serving, &shutdown_error());
+                    break;
+                }
+                if let Some(error) = panicked {
+                    reject_commands(&receiver, &serving, &error);
+                    break;
+                }
+            }
+            ExecutionCommand::Warmup {
+                call,
                 arguments,
+                iterations,
+                respond_to,
+            } => {
+                if respond_to.is_closed() {
+                    continue;
+                }
+                signals.begin_call(call);
+                let mut result = Ok(());
+                for _ in 0..iterations {
+                    if signals.interruption().is_some() {
+                        break;
+                    }
+                    if let Err(error) = run_invocation(
+                        &runtime,
+                        &mut context,
+                        &mut watchdog,
+                        &heap_limit,
+                        &signals,
+                        InvokeTarget::Call(arguments.clone()),
+                        None,
+                    ) {
+                        result = Err(error);
+                        break;
+                    }
+                }
+                let result = finish_call(&signals, &mut context, result);
+                let cancelled = is_cancellation(&result);
+                let panicked = worker_panic(&result);
+                let _ = respond_to.send(result);
+                if cancelled {
+                    reject_commands(&receiver, &serving, &shutdown_error());
+                    break;
+                }
+                if let Some(error) = panicked {
+                    reject_commands(&receiver, &serving, &error);
+                    break;
+                }
+            }
+            ExecutionCommand::Ready { respond_to } => {
+                let _ = respond_to.send(Ok(()));
+            }
+            ExecutionCommand::DrainConsole { respond_to } => {
+                let _ = respond_to.send(context.drain_console());
+            }
+            ExecutionCommand::DrainRejections { respond_to } => {
+                let _ = respond_to.send(context.drain_rejections());
+            }
+            ExecutionCommand::Globals {
+                include_builtins,
                 respond_to,
+            } => {
+                let _ = respond_to.send(context.globals(include_builtins));
+            }
+            ExecutionCommand::HeapStats { respond_to } => {
+                let _ = respond_to.send(Ok(context.heap_stats()));
+            }
+            ExecutionCommand::StartProfiling { respond_to } => {
+                let _ = respond_to.send(context.start_profiling());
+            }
+            ExecutionCommand::StopProfiling { respond_to } => {
+                let _ = respond_to.send(context.stop_profiling());
+            }
+            ExecutionCommand::HeapSnapshot { respond_to } => {
+                let _ = respond_to.send(Ok(profiler::heap_snapshot(context.js_runtime())));
+            }
+            ExecutionCommand::CaptureState { respond_to } => {
+                let _ = respond_to.send(context.capture_state());
+            }
+            ExecutionCommand::Reset { respond_to } => {
+                let _process_context = process_context::blocking_guard();
+                let bound = context.bound.clone();
+                // Drop the old isolate before creating its replacement on this thread.
+                *isolate_handle
+                    .lock()
+                    .expect("execution isolate handle lock should not be poisoned") = None;
+                host::clear_worker_pending_calls();
+                drop(context);
+                match runtime.block_on(DenoExecutionContext::new(bound, &worker_factory_roots)) {
+                    Ok(fresh) => {
+                        context = fresh;
+                        (watchdog, heap_limit) = attach_isolate(&mut context, &isolate_handle);
+                        let _ = respond_to.send(Ok(()));
+                    }
+                    Err(error) => {
+                        let _ = respond_to.send(Err(error.clone()));
+                        reject_commands(&receiver, &serving, &error);
+                        runtime.shutdown_background();
+                        return;
+                    }
+                }
+            }
+            ExecutionCommand::Shutdown => break,
+        }
+    }
+    serving.store(false, Ordering::Release);
+    drain_commands(&receiver);
+
+    // signal_shutdown always terminates; clear before dropping Globals / shutting down.
+    context
+        .js_runtime()
+        .v8_isolate()
+        .cancel_terminate_execution();
+    host::clear_worker_pending_calls();
+    runtime.shutdown_background();
+}
+
+fn attach_isolate(
+    context: &mut DenoExecutionContext,
+    isolate_handle: &Mutex<Option<v8::IsolateHandle>>,
+) -> (CallWatchdog, HeapLimitGuard) {
+    let handle = context.js_runtime().v8_isolate().thread_safe_handle();
+    *isolate_handle
+        .lock()
+        .expect("execution isolate handle lock should not be poisoned") = Some(handle.clone());
+    let heap_limit = HeapLimitGuard::install(context.js_runtime(), handle.clone());
+    (CallWatchdog::new(handle), heap_limit)
+}
+
+/// `belgie-{id}`, or `{name}-{id}` for named runtimes; ids count every runner in the process.
+fn worker_thread_name(name: Option<&str>) -> String {
+    static NEXT_WORKER: AtomicU64 = AtomicU64::new(1);
+    let id = NEXT_WORKER.fetch_add(1, Ordering::Relaxed);
+    format!("{}-{id}", name.unwrap_or("belgie"))
+}
+
+/// Marks the worker as down when its thread exits, including on panic.
+struct ServingGuard(Arc<AtomicBool>);
+
+impl Drop for ServingGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// Runs one call under the runner's timeout, CPU budget, heap limit, and cancellation.
+fn run_invocation(
+    runtime: &tokio::runtime::Runtime,
+    context: &mut DenoExecutionContext,
+    watchdog: &mut CallWatchdog,
+    heap_limit: &HeapLimitGuard,
+    signals: &WorkerSignals,
+    target: InvokeTarget,
+    chunks: Option<UnboundedSender<PyJsValue>>,
+) -> ExecutionResult<Invocation> {
+    let _process_context = process_context::blocking_guard();
+    let timeout = context.bound.execution_options().timeout();
+    let cpu_budget = context.bound.execution_options().cpu_budget();
+    watchdog.arm(timeout, cpu_budget);
+    host::set_worker_stream(chunks.clone());
+    host::take_worker_diagnostics();
+    // A panic in an op future or the loader unwinds to here; report it instead of losing the call.
+    // `stopped` marks calls cut short before their function settled.
+    let (result, stopped) = catch_unwind(AssertUnwindSafe(|| {
+        runtime.block_on(async {
+            tokio::select! {
+                result = context.invoke(target) => (result, false),
+                () = signals.shutdown.notified() => (Err(cancellation_error()), true),
+                () = signals.interrupt.notified() => (
+                    Err(interruption_error(
+                        signals.interruption().unwrap_or(Interruption::CallerCancelled),
+                    )),
+                    true,
+                ),
+                () = invocation_deadline(timeout) => {
+                    (Err(BindingError::timeout(timeout.unwrap_or_default())), true)
+                }
+                () = stream_abandoned(chunks.as_ref()) => {
+                    (Err(BindingError::runtime("Stream consumer stopped reading")), true)
+                }
+            }
+        })
+    }))
+    .unwrap_or_else(|payload| (Err(worker_panic_error(payload.as_ref())), true));
+    // Closing the chunk channel tells the consumer to wait for the call's result.
+    host::set_worker_stream(None);
+    drop(chunks);
+    let diagnostics = host::take_worker_diagnostics();
+    let cancelled = is_cancellation(&result);
+    let expiry = watchdog.disarm();
+    let terminated = expiry == Some(Expiry::Timeout);
+    let cpu_exhausted = expiry == Some(Expiry::CpuBudget);
+    let heap_limit_reached = heap_limit.reset(context.js_runtime());
+    if stopped || terminated || cpu_exhausted || heap_limit_reached {
+        context.cancel_pending_fetches();
+    }
+    if heap_limit_reached && !cancelled {
+        context
+            .js_runtime()
+            .v8_isolate()
+            .cancel_terminate_execution();
+        Err(BindingError::runtime(
+            "Deno execution exceeded the V8 heap limit",
+        ))
+    } else if terminated && !cancelled {
+        // The watchdog only interrupts running JavaScript; clear it so the
+        // isolate keeps serving later invocations.
+        context
+            .js_runtime()
+            .v8_isolate()
+            .cancel_terminate_execution();
+        Err(BindingError::timeout(timeout.unwrap_or_default()))
+    } else if cpu_exhausted && !cancelled {
+        context
+            .js_runtime()
+            .v8_isolate()
+            .cancel_terminate_execution();
+        Err(BindingError::cpu_budget_exceeded(
+            cpu_budget.unwrap_or_default(),
+        ))
+    } else {
+        let max_result_bytes = context.bound.execution_options().max_result_bytes();
+        result
+            .and_then(|invocation| enforce_result_limit(invocation, max_result_bytes))
+            .map(|invocation| Invocation {
+                diagnostics,
+                ..invocation
             })
-            .map_err(|_| BindingError::runtime("Deno execution worker is not available"))?;
-        response
-            .blocking_recv()
-            .map_err(|_| BindingError::runtime("Deno execution worker stopped unexpectedly"))?
     }
+}
 
-    pub(crate) async fn invoke_async(
-        &self,
-        arguments: RunnerArguments,
-    ) -> ExecutionResult<PyJsValue> {
-        if self.is_closed() {
-            return Err(BindingError::runtime("Deno execution runner is closed"));
+/// Rejects an oversized result on the worker so it never crosses to Python.
+fn enforce_result_limit(
+    invocation: Invocation,
+    max_result_bytes: Option<usize>,
+) -> ExecutionResult<Invocation> {
+    let Some(limit) = max_result_bytes else {
+        return Ok(invocation);
+    };
+    let size = invocation.value.serialized_len();
+    if size > limit {
+        Err(BindingError::result_too_large(size, limit))
+    } else {
+        Ok(invocation)
+    }
+}
+
+/// Clears the termination an interrupt left on the isolate; shutdown keeps it set.
+fn finish_call<T>(
+    signals: &WorkerSignals,
+    context: &mut DenoExecutionContext,
+    result: ExecutionResult<T>,
+) -> ExecutionResult<T> {
+    let Some(interruption) = signals.finish_call().filter(|_| !is_cancellation(&result)) else {
+        return result;
+    };
+    context
+        .js_runtime()
+        .v8_isolate()
+        .cancel_terminate_execution();
+    Err(interruption_error(interruption))
+}
+
+fn interruption_error(interruption: Interruption) -> BindingError {
+    match interruption {
+        Interruption::CallerCancelled => {
+            BindingError::runtime("Deno execution was interrupted because its caller was cancelled")
+        }
+        Interruption::Requested => {
+            BindingError::runtime("Deno execution was interrupted by Runtime.interrupt()")
+        }
+    }
+}
+
+fn cancellation_error() -> BindingError {
+    BindingError::runtime("Deno execution was cancelled")
+}
+
+/// Sent to calls still queued when the worker is closed or cancelled.
+fn shutdown_error() -> BindingError {
+    BindingError::runtime("Deno runtime is shutting down")
+}
+
+fn is_cancellation<T>(result: &ExecutionResult<T>) -> bool {
+    result
+        .as_ref()
+        .is_err_and(|error| error.message() == "Deno execution was cancelled")
+}
+
+const WORKER_PANIC: &str = "Deno execution worker panicked";
+
+/// The worker stops serving after a panic, since the isolate may be left mid-operation.
+fn worker_panic_error(payload: &(dyn Any + Send)) -> BindingError {
+    let reason = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic payload");
+    log::error!("{WORKER_PANIC}: {reason}");
+    BindingError::runtime(format!("{WORKER_PANIC}: {reason}"))
+}
+
+fn worker_panic<T>(result: &ExecutionResult<T>) -> Option<BindingError> {
+    result
+        .as_ref()
+        .err()
+        .filter(|error| error.message().starts_with(WORKER_PANIC))
+        .cloned()
+}
+
+/// Answers every queued command with `error` until shutdown.
+fn reject_commands(
+    receiver: &mpsc::Receiver<ExecutionCommand>,
+    serving: &AtomicBool,
+    error: &BindingError,
+) {
+    serving.store(false, Ordering::Release);
+    while let Ok(command) = receiver.recv() {
+        if !reject_command(command, error) {
+            break;
         }
-        let (respond_to, response) = oneshot::channel();
-        self.inner
-            .sender
-            .send(ExecutionCommand::Invoke {
-                arguments,
-                respond_to,
-            })
-            .map_err(|_| BindingError::runtime("Deno execution worker is not available"))?;
-        response
-            .await
-            .map_err(|_| BindingError::runtime("Deno execution worker stopped unexpectedly"))?
     }
+    drain_commands(receiver);
 }
 
-impl Cancel for DenoExecutionHandle {
-    fn cancel(&self) {
-        DenoExecutionHandle::cancel(self);
+/// Answers commands queued behind `Shutdown` so their callers are not left waiting on a dropped
+/// response channel.
+fn drain_commands(receiver: &mpsc::Receiver<ExecutionCommand>) {
+    let error = shutdown_error();
+    while let Ok(command) = receiver.try_recv() {
+        reject_command(command, &error);
     }
 }
 
-impl DenoExecutionHandleInner {
-    fn signal_shutdown(&self) {
-        if let Some(handle) = self
-            .isolate_handle
-            .lock()
-            .expect("execution isolate handle lock should not be poisoned")
-            .as_ref()
-        {
-            handle.terminate_execution();
+/// Returns `false` for `Shutdown`, which has no caller to answer.
+fn reject_command(command: ExecutionCommand, error: &BindingError) -> bool {
+    match command {
+        ExecutionCommand::Invoke { respond_to, .. } => {
+            let _ = respond_to.send(Err(error.clone()));
+        }
+        ExecutionCommand::Batch { respond_to, .. } => {
+            let _ = respond_to.send(Err(error.clone()));
+        }
+        ExecutionCommand::DrainConsole { respond_to }
+        | ExecutionCommand::DrainRejections { respond_to }
+        | ExecutionCommand::Globals { respond_to, .. }
+        | ExecutionCommand::HeapStats { respond_to } => {
+            let _ = respond_to.send(Err(error.clone()));
+        }
+        ExecutionCommand::StopProfiling { respond_to }
+        | ExecutionCommand::HeapSnapshot { respond_to }
+        | ExecutionCommand::CaptureState { respond_to } => {
+            let _ = respond_to.send(Err(error.clone()));
         }
-        self.shutdown.notify_one();
-        let _ = self.sender.send(ExecutionCommand::Shutdown);
+        ExecutionCommand::Warmup { respond_to, .. }
+        | ExecutionCommand::Ready { respond_to }
+        | ExecutionCommand::StartProfiling { respond_to }
+        | ExecutionCommand::Reset { respond_to } => {
+            let _ = respond_to.send(Err(error.clone()));
+        }
+        ExecutionCommand::Shutdown => return false,
     }
+    true
 }
 
-impl Drop for DenoExecutionHandleInner {
-    fn drop(&mut self) {
-        self.signal_shutdown();
-        if let Some(join_handle) = self
-            .join_handle
-            .lock()
-            .expect("execution worker join handle lock should not be poisoned")
-            .take()
-        {
-            let _ = join_handle.join();
-        }
-    }
+/// Terminates JavaScript near the V8 heap limit so the isolate reports an error instead of
+/// aborting the process.
+struct HeapLimitGuard {
+    reached: Arc<AtomicBool>,
+    initial_limit: Arc<AtomicUsize>,
+    isolate_handle: v8::IsolateHandle,
 }
 
-fn run_worker_thread(
-    bound: BoundRuntime,
-    worker_factory_roots: LibWorkerFactoryRoots,
-    receiver: mpsc::Receiver<ExecutionCommand>,
-    isolate_handle: Arc<Mutex<Option<v8::IsolateHandle>>>,
-    shutdown: Arc<Notify>,
-) {
-    let runtime = create_basic_runtime();
-    let mut context = {
-        let _process_context = process_context::blocking_guard();
-        match runtime.block_on(DenoExecutionContext::new(bound, &worker_factory_roots)) {
-            Ok(context) => context,
-            Err(error) => {
-                while let Ok(command) = receiver.recv() {
-                    match command {
-                        ExecutionCommand::Invoke { respond_to, .. } => {
-                            let _ = respond_to.send(Err(error.clone()));
-                        }
-                        ExecutionCommand::Shutdown => break,
-                    }
-                }
-                return;
-            }
-        }
-    };
-    *isolate_handle
-        .lock()
-        .expect("execution isolate handle lock should not be poisoned") =
-        Some(context.js_runtime().v8_isolate().thread_safe_handle());
+impl HeapLimitGuard {
+    fn install(js_runtime: &mut JsRuntime, isolate_handle: v8::IsolateHandle) -> Self {
+        let guard = Self {
+            reached: Arc::default(),
+            initial_limit: Arc::default(),
+            isolate_handle,
+        };
+        guard.register(js_runtime);
+        guard
+    }
 
-    while let Ok(command) = receiver.recv() {
-        match command {
-            ExecutionCommand::Invoke {
-                arguments,
-                respond_to,
-            } => {
-                let _process_context = process_context::blocking_guard();
-                let result = runtime.block_on(async {
-                    tokio::select! {
-                        result = context.invoke(arguments) => result,
-                        () = shutdown.notified() => {
-                            Err(BindingError::runtime("Deno execution was cancelled"))
-                        }
-                    }
-                });
-                let cancelled = result
-                    .as_ref()
-                    .is_err_and(|error| error.message() == "Deno execution was cancelled");
-                let _ = respond_to.send(result);
-                // Leave terminate set until final teardown (Deno kill path). Reject any
-                // Invokes queued ahead of Shutdown so they cannot run after cancel.
-                if cancelled {
-                    while let Ok(command) = receiver.recv() {
-                        match command {
-                            ExecutionCommand::Invoke { respond_to, .. } => {
-                                let _ = respond_to.send(Err(BindingError::runtime(
-                                    "Deno execution was cancelled",
-                                )));
-                            }
-                            ExecutionCommand::Shutdown => break,
-                        }
-                    }
-                    break;
-                }
-            }
-            ExecutionCommand::Shutdown => break,
+    fn register(&self, js_runtime: &mut JsRuntime) {
+        let reached = self.reached.clone();
+        let initial_limit = self.initial_limit.clone();
+        let isolate_handle = self.isolate_handle.clone();
+        js_runtime.add_near_heap_limit_callback(move |current, initial| {
+            reached.store(true, Ordering::SeqCst);
+            initial_limit.store(initial, Ordering::SeqCst);
+            isolate_handle.terminate_execution();
+            // Leave room for the terminated script to unwind; `reset` restores the limit.
+            current * 2
+        });
+    }
+
+    /// Reports whether the limit was reached since the last call, restoring it if so.
+    fn reset(&self, js_runtime: &mut JsRuntime) -> bool {
+        if !self.reached.swap(false, Ordering::SeqCst) {
+            return false;
         }
+        js_runtime.remove_near_heap_limit_callback(self.initial_limit.load(Ordering::SeqCst));
+        self.register(js_runtime);
+        true
     }
+}
 
-    // signal_shutdown always terminates; clear before dropping Globals / shutting down.
-    context
-        .js_runtime()
-        .v8_isolate()
-        .cancel_terminate_execution();
-    runtime.shutdown_background();
+async fn invocation_deadline(timeout: Option<Duration>) {
+    match timeout {
+        Some(timeout) => tokio::time::sleep(timeout).await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn stream_abandoned(chunks: Option<&UnboundedSender<PyJsValue>>) {
+    match chunks {
+        Some(chunks) => chunks.closed().await,
+        None => std::future::pending().await,
+    }
 }
 
 enum ExecutionBackend {
@@ -287,9 +1553,14 @@ impl Drop for InvocationKeepalive {
 
 struct DenoExecutionContext {
     bound: BoundRuntime,
+    /// Declared before `backend` so the inspector session closes before its isolate.
+    profiler: Option<CpuProfiler>,
     backend: ExecutionBackend,
     main_module: ModuleSpecifier,
+    namespace: Option<v8::Global<v8::Object>>,
     run_function: Option<v8::Global<v8::Function>>,
+    /// Globals the runtime and belgie define, hidden from `globals()` unless builtins are asked for.
+    builtin_globals: HashSet<String>,
 }
 
 impl DenoExecutionContext {
@@ -298,15 +1569,30 @@ impl DenoExecutionContext {
         worker_factory_roots: &LibWorkerFactoryRoots,
     ) -> ExecutionResult<Self> {
         let main_module = main_module_specifier(&bound)?;
+        let memory_modules = script_memory_modules(&bound, &main_module)?;
+        let imports = script_imports(&bound, &main_module)?;
         let needs_package_worker = bound.package_environment().is_some()
-            || deno_snapshots::CLI_SNAPSHOT.is_some()
             || bound.script().needs_package_loader();
-        let backend = if needs_package_worker {
+        if needs_package_worker && bound.startup_snapshot().is_some() {
+            return Err(BindingError::runtime(
+                "Runtime(snapshot=...) runs scripts on the lightweight backend, which cannot load package imports",
+            ));
+        }
+        let backend = if needs_package_worker
+            || (deno_snapshots::CLI_SNAPSHOT.is_some() && bound.startup_snapshot().is_none())
+        {
             let package_environment = match bound.package_environment() {
                 Some(environment) => environment.clone(),
                 None => BoundPackageEnvironment::implicit_for_cwd(bound.cwd())?,
             };
-            let context = package_environment.embed_context_rc(bound.worker_options())?;
+            let context = package_environment.embed_context_for_script(
+                bound.worker_options(),
+                &main_module,
+                &imports,
+            )?;
+            for (specifier, source) in memory_modules {
+                context.insert_memory_file(specifier, source);
+            }
             ExecutionBackend::Package(Box::new(
                 package_worker::create_bound_package_worker(
                     context,
@@ -323,20 +1609,102 @@ impl DenoExecutionContext {
                             bound.script().media_type(),
                         ),
                         node_ipc_init: None,
+                        module_cache: Some(bound.module_cache().clone()),
                     },
                     worker_factory_roots,
                 )
                 .await?,
             ))
+        } else if bound.worker_options().inspector().is_some() {
+            return Err(BindingError::runtime(
+                "inspect requires the Deno worker backend, which this build does not include",
+            ));
         } else {
-            ExecutionBackend::Lightweight(Box::new(create_js_runtime(&bound)?))
+            ExecutionBackend::Lightweight(Box::new(create_js_runtime(
+                &bound,
+                memory_modules,
+                imports,
+            )?))
         };
-        Ok(Self {
+        let mut context = Self {
             bound,
+            profiler: None,
             backend,
             main_module,
+            namespace: None,
             run_function: None,
-        })
+            builtin_globals: HashSet::new(),
+        };
+        context.install_node_compat()?;
+        context.builtin_globals = context.global_types()?.into_keys().collect();
+        context.install_determinism()?;
+        context.install_env_vars()?;
+        context.run_setup()?;
+        context.install_restored_globals()?;
+        Ok(context)
+    }
+
+    fn install_determinism(&mut self) -> ExecutionResult<()> {
+        let Some(determinism) = self.bound.execution_options().determinism() else {
+            return Ok(());
+        };
+        let source = format!(
+            "{DETERMINISM_SOURCE}({}, {})",
+            determinism.generator_state(),
+            determinism.time_ms(),
+        );
+        self.js_runtime()
+            .execute_script("belgie:determinism", source)
+            .map_err(|error| {
+                BindingError::runtime(format!("Could not install deterministic mode: {error}"))
+            })?;
+        Ok(())
+    }
+
+    fn install_node_compat(&mut self) -> ExecutionResult<()> {
+        if !self.bound.execution_options().node_compat() {
+            return Ok(());
+        }
+        let platform = match std::env::consts::OS {
+            "macos" => "darwin",
+            "windows" => "win32",
+            os => os,
+        };
+        let source = format!("{NODE_COMPAT_SOURCE}({platform:?})");
+        self.js_runtime()
+            .execute_script("belgie:node-compat", source)
+            .map_err(|error| {
+                BindingError::runtime(format!("Could not install node_compat: {error}"))
+            })?;
+        Ok(())
+    }
+
+    fn install_env_vars(&mut self) -> ExecutionResult<()> {
+        let Some(env_vars) = self.bound.execution_options().env_vars() else {
+            return Ok(());
+        };
+        let source = format!(
+            "{ENV_VARS_SOURCE}({})",
+            serde_json::to_string(env_vars).expect("environment variables should serialize")
+        );
+        self.js_runtime()
+            .execute_script("belgie:env", source)
+            .map_err(|error| {
+                BindingError::runtime(format!("Could not install env_vars: {error}"))
+            })?;
+        Ok(())
+    }
+
+    /// Errors fail this context, so the runner rejects its calls with them.
+    fn run_setup(&mut self) -> ExecutionResult<()> {
+        let Some(setup) = self.bound.execution_options().setup() else {
+            return Ok(());
+        };
+        let setup = setup.to_string();
+        self.js_runtime()
+            .execute_script("belgie:setup", setup)
+            .map_err(|error| map_js_error(&error))?;
+        Ok(())
     }
 
     fn js_runtime(&mut self) -> &mut JsRuntime {
@@ -346,48 +1714,138 @@ impl DenoExecutionContext {
         }
     }
 
-    async fn invoke(&mut self, arguments: RunnerArguments) -> ExecutionResult<PyJsValue> {
-        self.ensure_loaded().await?;
+    async fn invoke(&mut self, target: InvokeTarget) -> ExecutionResult<Invocation> {
+        let mut timings = CallTimings::default();
+        if matches!(target, InvokeTarget::DefaultExport) {
+            let started = Instant::now();
+            let namespace = self.ensure_evaluated().await?;
+            timings.compile = started.elapsed();
+            return Ok(Invocation {
+                value: self.read_default_export(namespace)?,
+                timings,
+                diagnostics: Vec::new(),
+            });
+        }
+        if self.run_function.is_none() {
+            let started = Instant::now();
+            self.ensure_loaded().await?;
+            timings.compile = started.elapsed();
+        }
+        let InvokeTarget::Call(arguments) = target else {
+            return Ok(Invocation {
+                value: PyJsValue::from_json(serde_json::Value::Null),
+                timings,
+                diagnostics: Vec::new(),
+            });
+        };
         let run_function = self
             .run_function
             .clone()
             .ok_or_else(|| BindingError::missing_run_export(self.bound.description()))?;
         let run_signature = self.bound.run_signature().cloned();
+        let request_id = arguments.request_id();
         let args = {
             deno_core::scope!(scope, self.js_runtime());
+            set_request_id(scope, request_id)?;
             arguments.to_v8_globals(scope, run_signature.as_ref())?
         };
         let _keepalive = InvocationKeepalive::new(self.js_runtime());
+        let started = Instant::now();
+        // call_with_args runs the function eagerly; the returned future only awaits its result.
         let call = self.js_runtime().call_with_args(&run_function, &args);
+        timings.run = started.elapsed();
+        let started = Instant::now();
         let result = self
             .js_runtime()
             .with_event_loop_promise(call, PollEventLoopOptions::default())
             .await
-            .map_err(|error| BindingError::javascript(error.to_string()))?;
+            .map_err(|error| map_core_error(error).with_request_id(request_id))?;
+        timings.event_loop = started.elapsed();
+        let unserializable = self.bound.execution_options().unserializable();
         deno_core::scope!(scope, self.js_runtime());
         let result = v8::Local::new(scope, result);
-        PyJsValue::from_v8(scope, result)
+        Ok(Invocation {
+            value: PyJsValue::from_v8_with(scope, result, unserializable)?,
+            timings,
+            diagnostics: Vec::new(),
+        })
     }
 
     async fn ensure_loaded(&mut self) -> ExecutionResult<()> {
         if self.run_function.is_some() {
             return Ok(());
         }
+        let namespace = self.ensure_evaluated().await?;
+        let description = self.bound.description();
+        let run_function = resolve_run_function(self.js_runtime(), namespace, &description)?;
+
+        self.run_function = Some(run_function);
+        Ok(())
+    }
+
+    /// Loads and evaluates the module once, keeping its namespace for later calls.
+    async fn ensure_evaluated(&mut self) -> ExecutionResult<v8::Global<v8::Object>> {
+        if let Some(namespace) = &self.namespace {
+            return Ok(namespace.clone());
+        }
 
-        if self.bound.script().media_type() == deno_ast::MediaType::Tsx
-            || self.bound.script().content().contains("@belgie/render")
+        // Configured env_vars already replaced process.env with their own sandbox.
+        if (self.bound.script().media_type() == deno_ast::MediaType::Tsx
+            || self.bound.script().content().contains("@belgie/render"))
+            && self.bound.execution_options().env_vars().is_none()
         {
             self.install_safe_process_environment()?;
         }
+        let before_install = self.global_types()?;
         self.install_render_context()?;
+        self.install_script_globals()?;
+        {
+            let host_functions = self.bound.host_functions().clone();
+            deno_core::scope!(scope, self.js_runtime());
+            host::install_host_global(scope, &host_functions)?;
+            state::install_state_global(scope)?;
+        }
+        isolated_eval::install_isolated_eval(self.js_runtime())?;
+        if self.bound.storage().is_some() {
+            storage::install_storage_global(self.js_runtime())?;
+        }
+        if self.bound.execution_options().capture_console() {
+            self.install_console_capture()?;
+        }
+        self.install_rejection_capture()?;
+        let script_globals = self
+            .bound
+            .script()
+            .globals()
+            .and_then(PyJsValue::as_object)
+            .unwrap_or_default()
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        let installed = self
+            .global_types()?
+            .into_keys()
+            .filter(|name| !before_install.contains_key(name) && !script_globals.contains(name))
+            .collect::<Vec<_>>();
+        self.builtin_globals.extend(installed);
 
         let module_id = match &mut self.backend {
             ExecutionBackend::Package(worker) => {
                 let js_runtime = worker.js_runtime();
+                if self
+                    .bound
+                    .worker_options()
+                    .inspector()
+                    .is_some_and(|inspector| inspector.break_on_first_statement())
+                {
+                    js_runtime
+                        .inspector()
+                        .wait_for_session_and_break_on_next_statement();
+                }
                 js_runtime
                     .load_main_es_module(&self.main_module)
                     .await
-                    .map_err(|error| BindingError::module_load(error.to_string()))?
+                    .map_err(map_module_load_error)?
             }
             ExecutionBackend::Lightweight(js_runtime) => js_runtime
                 .load_main_es_module_from_code(
@@ -396,10 +1854,10 @@ impl DenoExecutionContext {
                         &self.main_module,
                         self.bound.script().execution_content(),
                     )
-                    .map_err(|error| BindingError::module_load(error.to_string()))?,
+                    .map_err(map_loader_error)?,
                 )
                 .await
-                .map_err(|error| BindingError::module_load(error.to_string()))?,
+                .map_err(map_module_load_error)?,
         };
 
         evaluate_loaded_module(self.js_runtime(), module_id).await?;
@@ -408,15 +1866,212 @@ impl DenoExecutionContext {
             .js_runtime()
             .get_module_namespace(module_id)
             .map_err(|error| BindingError::module_load(error.to_string()))?;
-        let description = self.bound.description();
-        let run_function = resolve_run_function(self.js_runtime(), namespace, &description)?;
+        self.namespace = Some(namespace.clone());
+        Ok(namespace)
+    }
 
-        self.run_function = Some(run_function);
+    /// A module without a default export reads as `null`.
+    fn read_default_export(
+        &mut self,
+        namespace: v8::Global<v8::Object>,
+    ) -> ExecutionResult<PyJsValue> {
+        let unserializable = self.bound.execution_options().unserializable();
+        deno_core::scope!(scope, self.js_runtime());
+        let namespace = v8::Local::new(scope, namespace);
+        let default_key = v8::String::new(scope, "default")
+            .ok_or_else(|| BindingError::runtime("Could not create default export key"))?;
+        match namespace.get(scope, default_key.into()) {
+            Some(value) if !value.is_undefined() => {
+                PyJsValue::from_v8_with(scope, value, unserializable)
+            }
+            _ => Ok(PyJsValue::from_json(serde_json::Value::Null)),
+        }
+    }
+
+    fn install_script_globals(&mut self) -> ExecutionResult<()> {
+        let Some(globals) = self
+            .bound
+            .script()
+            .globals()
+            .and_then(PyJsValue::as_object)
+            .map(<[_]>::to_vec)
+        else {
+            return Ok(());
+        };
+        self.install_globals(globals, "script global")
+    }
+
+    /// Runs after setup, so setup code sees the restored values only once the module loads.
+    fn install_restored_globals(&mut self) -> ExecutionResult<()> {
+        let Some(globals) = self.bound.restored_globals() else {
+            return Ok(());
+        };
+        let globals = globals
+            .iter()
+            .map(|(name, value)| (name.clone(), PyJsValue::from_json(value.clone())))
+            .collect();
+        self.install_globals(globals, "restored global")
+    }
+
+    fn install_globals(
+        &mut self,
+        globals: Vec<(String, PyJsValue)>,
+        kind: &str,
+    ) -> ExecutionResult<()> {
+        deno_core::scope!(scope, self.js_runtime());
+        let global = scope.get_current_context().global(scope);
+        for (name, value) in globals {
+            let key = v8::String::new(scope, &name)
+                .ok_or_else(|| BindingError::runtime(format!("Could not create {kind} key")))?;
+            let value = value.to_v8(scope)?;
+            if !global.set(scope, key.into(), value).unwrap_or(false) {
+                return Err(BindingError::runtime(format!(
+                    "Could not install {kind} {name}",
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn install_console_capture(&mut self) -> ExecutionResult<()> {
+        self.js_runtime()
+            .execute_script("belgie:console", CONSOLE_CAPTURE_SOURCE)
+            .map_err(|error| {
+                BindingError::runtime(format!("Could not install console capture: {error}"))
+            })?;
+        Ok(())
+    }
+
+    fn drain_console(&mut self) -> ExecutionResult<PyJsValue> {
+        let messages = self
+            .js_runtime()
+            .execute_script("belgie:console", CONSOLE_DRAIN_SOURCE)
+            .map_err(|error| map_js_error(&error))?;
+        deno_core::scope!(scope, self.js_runtime());
+        let messages = v8::Local::new(scope, messages);
+        PyJsValue::from_v8(scope, messages)
+    }
+
+    fn install_rejection_capture(&mut self) -> ExecutionResult<()> {
+        self.js_runtime()
+            .execute_script("belgie:rejections", REJECTION_CAPTURE_SOURCE)
+            .map_err(|error| {
+                BindingError::runtime(format!("Could not install rejection capture: {error}"))
+            })?;
+        Ok(())
+    }
+
+    fn drain_rejections(&mut self) -> ExecutionResult<PyJsValue> {
+        let rejections = self
+            .js_runtime()
+            .execute_script("belgie:rejections", REJECTION_DRAIN_SOURCE)
+            .map_err(|error| map_js_error(&error))?;
+        deno_core::scope!(scope, self.js_runtime());
+        let rejections = v8::Local::new(scope, rejections);
+        PyJsValue::from_v8(scope, rejections)
+    }
+
+    fn global_types(&mut self) -> ExecutionResult<serde_json::Map<String, serde_json::Value>> {
+        let types = self
+            .js_runtime()
+            .execute_script("belgie:globals", GLOBAL_TYPES_SOURCE)
+            .map_err(|error| map_js_error(&error))?;
+        deno_core::scope!(scope, self.js_runtime());
+        let types = v8::Local::new(scope, types);
+        match PyJsValue::from_v8(scope, types)?.to_json() {
+            serde_json::Value::Object(types) => Ok(types),
+            _ => Err(BindingError::runtime("Could not read the global scope")),
+        }
+    }
+
+    fn globals(&mut self, include_builtins: bool) -> ExecutionResult<PyJsValue> {
+        let globals = self
+            .global_types()?
+            .into_iter()
+            .filter(|(name, _)| include_builtins || !self.builtin_globals.contains(name))
+            .collect();
+        Ok(PyJsValue::from_json(serde_json::Value::Object(globals)))
+    }
+
+    /// Functions, symbols, and values that cannot be read are skipped; anything else that does
+    /// not cross the value boundary fails the capture with the global's name.
+    fn capture_state(&mut self) -> ExecutionResult<Vec<u8>> {
+        let names = self
+            .global_types()?
+            .into_iter()
+            .filter(|(name, kind)| {
+                !self.builtin_globals.contains(name)
+                    && !matches!(
+                        kind.as_str(),
+                        Some("function" | "symbol" | "undefined" | "unknown")
+                    )
+            })
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        deno_core::scope!(scope, self.js_runtime());
+        let global = scope.get_current_context().global(scope);
+        let mut captured = serde_json::Map::new();
+        for name in names {
+            let key = v8::String::new(scope, &name)
+                .ok_or_else(|| BindingError::runtime("Could not create global key"))?;
+            let Some(value) = global.get(scope, key.into()) else {
+                continue;
+            };
+            let value = PyJsValue::from_v8(scope, value).map_err(|error| {
+                BindingError::value_conversion(format!(
+                    "Could not capture global {name}: {}",
+                    error.message()
+                ))
+            })?;
+            captured.insert(name, value.to_json());
+        }
+        Ok(captured_state::encode(captured))
+    }
+
+    /// Aborts the requests a stopped call left in flight, so their connections close now instead
+    /// of finishing for a caller that is gone. Closing a cancel handle is how `AbortSignal`
+    /// aborts a fetch; lightweight runners have no fetch and no such resources.
+    fn cancel_pending_fetches(&mut self) {
+        let op_state = self.js_runtime().op_state();
+        let mut op_state = op_state.borrow_mut();
+        let handles = op_state
+            .resource_table
+            .names()
+            .filter(|(_, name)| name == FETCH_CANCEL_HANDLE)
+            .map(|(rid, _)| rid)
+            .collect::<Vec<_>>();
+        for rid in handles {
+            let _ = op_state.resource_table.close(rid);
+        }
+    }
+
+    fn heap_stats(&mut self) -> PyJsValue {
+        let stats = self.js_runtime().v8_isolate().get_heap_statistics();
+        PyJsValue::from_json(serde_json::json!({
+            "used_heap_size": stats.used_heap_size(),
+            "total_heap_size": stats.total_heap_size(),
+            "heap_size_limit": stats.heap_size_limit(),
+            "external_memory": stats.external_memory(),
+        }))
+    }
+
+    fn start_profiling(&mut self) -> ExecutionResult<()> {
+        if self.profiler.is_some() {
+            return Err(BindingError::runtime("CPU profiling is already running"));
+        }
+        self.profiler = Some(CpuProfiler::start(self.js_runtime())?);
         Ok(())
     }
 
+    fn stop_profiling(&mut self) -> ExecutionResult<Vec<u8>> {
+        self.profiler
+            .take()
+            .ok_or_else(|| BindingError::runtime("CPU profiling is not running"))?
+            .stop()
+    }
+
     fn install_render_context(&mut self) -> ExecutionResult<()> {
-        let source = self.bound.script().content().to_string();
+        let source = self.bound.script().shared_content();
         let url = self.main_module.to_string();
         deno_core::scope!(scope, self.js_runtime());
 
@@ -501,6 +2156,33 @@ impl DenoExecutionContext {
     }
 }
 
+/// Publishes the call's request id to console capture, clearing the previous call's.
+fn set_request_id(scope: &mut v8::PinScope, request_id: Option<&str>) -> ExecutionResult<()> {
+    let symbol_name = v8::String::new(scope, REQUEST_ID_SYMBOL)
+        .ok_or_else(|| BindingError::runtime("Could not create request id symbol"))?;
+    let symbol = v8::Symbol::for_key(scope, symbol_name);
+    let value: v8::Local<v8::Value> = match request_id {
+        Some(request_id) => v8::String::new(scope, request_id)
+            .ok_or_else(|| BindingError::runtime("Could not create request id"))?
+            .into(),
+        None => v8::undefined(scope).into(),
+    };
+    let global = scope.get_current_context().global(scope);
+    if global
+        .define_own_property(
+            scope,
+            symbol.into(),
+            value,
+            v8::PropertyAttribute::DONT_ENUM,
+        )
+        .unwrap_or(false)
+    {
+        Ok(())
+    } else {
+        Err(BindingError::runtime("Could not set the request id"))
+    }
+}
+
 fn define_context_property<'s, 'i>(
     scope: &mut v8::PinScope<'s, 'i>,
     object: v8::Local<'s, v8::Object>,
@@ -534,13 +2216,7 @@ async fn evaluate_loaded_module(
         .run_event_loop(Default::default())
         .await
         .map_err(map_core_error)?;
-    result
-        .await
-        .map_err(|error| BindingError::javascript(error.to_string()))
-}
-
-fn map_core_error(error: CoreError) -> BindingError {
-    BindingError::javascript(error.to_string())
+    result.await.map_err(map_core_error)
 }
 
 fn main_module_specifier(bound: &BoundRuntime) -> ExecutionResult<ModuleSpecifier> {
@@ -554,23 +2230,109 @@ fn main_module_specifier(bound: &BoundRuntime) -> ExecutionResult<ModuleSpecifie
 }
 
 fn inline_module_path(bound: &BoundRuntime) -> PathBuf {
-    let filename = if bound.script().media_type() == deno_ast::MediaType::Tsx {
-        "__deno_python_inline__.tsx"
+    let extension = if bound.script().media_type() == deno_ast::MediaType::Tsx {
+        ".tsx"
     } else {
-        "__deno_python_inline__.ts"
+        ".ts"
+    };
+    let filename = match bound.script().inline_name() {
+        Some(name) if name.ends_with(extension) => name.to_string(),
+        Some(name) => format!("{name}{extension}"),
+        None => format!("__deno_python_inline__{extension}"),
     };
     bound.cwd().join(filename)
 }
 
-fn create_js_runtime(bound: &BoundRuntime) -> ExecutionResult<JsRuntime> {
-    Ok(JsRuntime::new(RuntimeOptions {
-        module_loader: Some(Rc::new(module_loader::PythonModuleLoader)),
+fn script_memory_modules(
+    bound: &BoundRuntime,
+    main_module: &ModuleSpecifier,
+) -> ExecutionResult<HashMap<ModuleSpecifier, Arc<str>>> {
+    bound
+        .script()
+        .modules()
+        .iter()
+        .map(|(specifier, source)| {
+            let joined = main_module.join(specifier).map_err(|error| {
+                BindingError::module_load(format!(
+                    "Invalid script module specifier {specifier}: {error}"
+                ))
+            })?;
+            // Runs on the worker thread, so compressed bundles are only expanded per isolate.
+            Ok((joined, source.decode(specifier)?.into()))
+        })
+        .collect()
+}
+
+/// Relative targets resolve against the main module, matching how `modules` keys are joined.
+fn script_imports(
+    bound: &BoundRuntime,
+    main_module: &ModuleSpecifier,
+) -> ExecutionResult<BTreeMap<String, ModuleSpecifier>> {
+    bound
+        .script()
+        .imports()
+        .iter()
+        .map(|(specifier, target)| {
+            let resolved = if target.starts_with("./")
+                || target.starts_with("../")
+                || target.starts_with('/')
+            {
+                main_module.join(target)
+            } else {
+                ModuleSpecifier::parse(target)
+            };
+            resolved
+                .map(|target| (specifier.clone(), target))
+                .map_err(|error| {
+                    BindingError::module_load(format!(
+                        "Script import {specifier} must map to a relative path or URL, got {target}: {error}"
+                    ))
+                })
+        })
+        .collect()
+}
+
+fn create_js_runtime(
+    bound: &BoundRuntime,
+    memory_modules: HashMap<ModuleSpecifier, Arc<str>>,
+    imports: BTreeMap<String, ModuleSpecifier>,
+) -> ExecutionResult<JsRuntime> {
+    let mut runtime = JsRuntime::new(RuntimeOptions {
+        module_loader: Some(Rc::new(
+            module_loader::PythonModuleLoader::with_memory_modules(memory_modules)
+                .with_imports(imports)
+                .with_cache(bound.module_cache().clone())
+                .with_module_root(
+                    bound
+                        .js_runtime_options()
+                        .module_root()
+                        .map(Path::to_path_buf),
+                ),
+        )),
         create_params: bound
             .js_runtime_options()
             .to_create_params()
             .map_err(BindingError::runtime)?,
+        startup_snapshot: bound.startup_snapshot(),
+        // Backs `start_profiling`; no inspector server is started.
+        inspector: true,
+        op_metrics_factory_fn: bound.op_metrics().cloned().map(OpMetrics::factory),
         ..Default::default()
-    }))
+    });
+    for extension in bound.js_runtime_options().extensions() {
+        match extension {
+            BuiltinExtension::TextEncoding => text_encoding::install_text_encoding(&mut runtime)?,
+            BuiltinExtension::Crypto => web_crypto::install_web_crypto(&mut runtime)?,
+            BuiltinExtension::Performance => performance::install_performance(&mut runtime)?,
+            BuiltinExtension::Url => web_url::install_web_url(&mut runtime)?,
+            BuiltinExtension::Base64 => web_base64::install_web_base64(&mut runtime)?,
+            BuiltinExtension::StructuredClone => {
+                web_structured_clone::install_web_structured_clone(&mut runtime)?;
+            }
+            BuiltinExtension::Abort => web_abort::install_web_abort(&mut runtime)?,
+        }
+    }
+    Ok(runtime)
 }
 
 #[cfg(test)]
@@ -579,7 +2341,7 @@ where
     F: FnOnce(&mut JsRuntime) -> R + Send + 'static,
     R: Send + 'static,
 {
-    spawn_v8_worker(move || {
+    spawn_v8_worker("belgie-test".to_string(), move || {
         if deno_snapshots::CLI_SNAPSHOT.is_some() {
             create_and_run_current_thread(async move {
                 // Serialize with DenoExecutionHandle create/invoke so parallel tests do not
@@ -603,12 +2365,13 @@ where
                         argv0: None,
                         js_runtime_options: Default::default(),
                         runtime_worker_options: Default::default(),
-                        main_source: Some("export {}".to_string()),
+                        main_source: Some("export {}".into()),
                         header_overrides: content_type_header_overrides(
                             main_module,
                             deno_ast::MediaType::TypeScript,
                         ),
                         node_ipc_init: None,
+                        module_cache: None,
                     },
                     &LibWorkerFactoryRoots::default(),
                 )
@@ -658,3 +2421,61 @@ fn resolve_run_function(
         .map_err(|_| BindingError::non_function_run_export(context.to_string()))?;
     Ok(v8::Global::new(scope, function))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{panic::catch_unwind, sync::atomic::AtomicBool, sync::mpsc};
+
+    use tokio::sync::oneshot;
+
+    use super::{
+        ExecutionCommand, ExecutionResult, reject_commands, shutdown_error, worker_panic,
+        worker_panic_error,
+    };
+    use crate::types::error::BindingError;
+
+    #[test]
+    fn reports_worker_panics_with_their_reason() {
+        let payload =
+            catch_unwind(|| panic!("op exploded: {}", 42)).expect_err("closure should panic");
+
+        let error = worker_panic_error(payload.as_ref());
+        let result: ExecutionResult<()> = Err(error);
+
+        assert_eq!(
+            worker_panic(&result).map(|error| error.message()),
+            Some("Deno execution worker panicked: op exploded: 42".to_string()),
+        );
+        assert!(worker_panic(&ExecutionResult::Ok(())).is_none());
+    }
+
+    #[test]
+    fn answers_commands_queued_behind_shutdown() {
+        let (sender, receiver) = mpsc::sync_channel(4);
+        let (ahead, ahead_response) = oneshot::channel();
+        let (behind, behind_response) = oneshot::channel();
+        sender
+            .send(ExecutionCommand::Reset { respond_to: ahead })
+            .expect("queue should accept commands");
+        sender
+            .send(ExecutionCommand::Shutdown)
+            .expect("queue should accept commands");
+        sender
+            .send(ExecutionCommand::Reset { respond_to: behind })
+            .expect("queue should accept commands");
+
+        let serving = AtomicBool::new(true);
+        reject_commands(&receiver, &serving, &BindingError::runtime("stopped"));
+
+        let message = |response: oneshot::Receiver<ExecutionResult<()>>| {
+            response
+                .blocking_recv()
+                .expect("every command should be answered")
+                .expect_err("commands should be rejected")
+                .message()
+        };
+        assert_eq!(message(ahead_response), "stopped");
+        assert_eq!(message(behind_response), shutdown_error().message());
+        assert!(!serving.into_inner());
+    }
+}
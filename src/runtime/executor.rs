@@ -1,9 +1,16 @@
 use crate::{
-    runtime::DenoExecutionHandle,
-    types::runner::{AsyncRunnerResult, RunnerArguments, SyncRunnerResult},
-    utils::{cancel_guard::CancelGuard, py_error},
+    runtime::{DenoExecutionHandle, execution::Invocation},
+    types::{
+        error::BindingError,
+        runner::{AsyncRunnerResult, RunnerArguments, SyncRunnerResult},
+        value::PyJsValue,
+    },
+    utils::py_error,
+};
+use pyo3::{
+    PyResult, Python,
+    types::{PyBytes, PyList, PyTuple},
 };
-use pyo3::Python;
 
 pub(crate) fn execute_sync(
     py: Python<'_>,
@@ -20,14 +27,349 @@ pub(crate) async fn execute_async(
     handle: DenoExecutionHandle,
     arguments: RunnerArguments,
 ) -> AsyncRunnerResult {
-    let mut guard = CancelGuard::new(handle);
-    let result = guard.get().invoke_async(arguments).await;
-    guard.disarm();
-    result
+    handle
+        .invoke_async(arguments)
+        .await
+        .map_err(py_error::from_binding_error)
+        .and_then(|value| Python::attach(|py| value.to_py(py)))
+}
+
+/// Encodes the result as MessagePack with the GIL released, skipping Python object conversion.
+pub(crate) fn execute_msgpack_sync(
+    py: Python<'_>,
+    handle: &DenoExecutionHandle,
+    arguments: RunnerArguments,
+) -> SyncRunnerResult {
+    let encoded = py
+        .detach(|| {
+            handle
+                .invoke_blocking(arguments)
+                .and_then(|value| value.to_msgpack())
+        })
+        .map_err(py_error::from_binding_error)?;
+    Ok(PyBytes::new(py, &encoded).into_any().unbind())
+}
+
+pub(crate) async fn execute_msgpack_async(
+    handle: DenoExecutionHandle,
+    arguments: RunnerArguments,
+) -> AsyncRunnerResult {
+    let encoded = handle
+        .invoke_async(arguments)
+        .await
+        .and_then(|value| value.to_msgpack())
+        .map_err(py_error::from_binding_error)?;
+    Python::attach(|py| Ok(PyBytes::new(py, &encoded).into_any().unbind()))
+}
+
+pub(crate) fn execute_timed_sync(
+    py: Python<'_>,
+    handle: &DenoExecutionHandle,
+    arguments: RunnerArguments,
+) -> SyncRunnerResult {
+    let invocation = py
+        .detach(|| handle.invoke_timed_blocking(arguments))
+        .map_err(py_error::from_binding_error)?;
+    timed_result_to_py(py, invocation)
+}
+
+pub(crate) async fn execute_timed_async(
+    handle: DenoExecutionHandle,
+    arguments: RunnerArguments,
+) -> AsyncRunnerResult {
+    handle
+        .invoke_timed_async(arguments)
+        .await
+        .map_err(py_error::from_binding_error)
+        .and_then(|invocation| Python::attach(|py| timed_result_to_py(py, invocation)))
+}
+
+fn timed_result_to_py(py: Python<'_>, invocation: Invocation) -> SyncRunnerResult {
+    let value = invocation.value.to_py(py)?;
+    let timings = invocation.timings.to_json().to_py(py)?;
+    Ok(PyTuple::new(py, [value, timings])?.into_any().unbind())
+}
+
+pub(crate) fn execute_diagnosed_sync(
+    py: Python<'_>,
+    handle: &DenoExecutionHandle,
+    arguments: RunnerArguments,
+) -> SyncRunnerResult {
+    let invocation = py
+        .detach(|| handle.invoke_timed_blocking(arguments))
+        .map_err(py_error::from_binding_error)?;
+    diagnosed_result_to_py(py, invocation)
+}
+
+pub(crate) async fn execute_diagnosed_async(
+    handle: DenoExecutionHandle,
+    arguments: RunnerArguments,
+) -> AsyncRunnerResult {
+    handle
+        .invoke_timed_async(arguments)
+        .await
+        .map_err(py_error::from_binding_error)
+        .and_then(|invocation| Python::attach(|py| diagnosed_result_to_py(py, invocation)))
+}
+
+fn diagnosed_result_to_py(py: Python<'_>, invocation: Invocation) -> SyncRunnerResult {
+    let value = invocation.value.to_py(py)?;
+    let diagnostics = invocation
+        .diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.to_py(py))
+        .collect::<PyResult<Vec<_>>>()?;
+    let diagnostics = PyList::new(py, diagnostics)?.into_any().unbind();
+    Ok(PyTuple::new(py, [value, diagnostics])?.into_any().unbind())
+}
+
+pub(crate) fn execute_batch_sync(
+    py: Python<'_>,
+    handle: &DenoExecutionHandle,
+    calls: Vec<RunnerArguments>,
+) -> SyncRunnerResult {
+    let results = py
+        .detach(|| handle.batch_blocking(calls))
+        .map_err(py_error::from_binding_error)?;
+    batch_results_to_py(py, results)
+}
+
+pub(crate) async fn execute_batch_async(
+    handle: DenoExecutionHandle,
+    calls: Vec<RunnerArguments>,
+) -> AsyncRunnerResult {
+    handle
+        .batch_async(calls)
+        .await
+        .map_err(py_error::from_binding_error)
+        .and_then(|results| Python::attach(|py| batch_results_to_py(py, results)))
+}
+
+/// Failed calls become exception instances in place, like `asyncio.gather(return_exceptions=True)`.
+fn batch_results_to_py(
+    py: Python<'_>,
+    results: Vec<Result<PyJsValue, BindingError>>,
+) -> SyncRunnerResult {
+    let items = results
+        .into_iter()
+        .map(|result| match result {
+            Ok(value) => value.to_py(py),
+            Err(error) => Ok(py_error::from_binding_error(error)
+                .into_value(py)
+                .into_any()),
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(py, items)?.into_any().unbind())
+}
+
+pub(crate) fn warmup_sync(
+    py: Python<'_>,
+    handle: &DenoExecutionHandle,
+    arguments: RunnerArguments,
+    iterations: usize,
+) -> PyResult<()> {
+    py.detach(|| handle.warmup_blocking(arguments, iterations))
+        .map_err(py_error::from_binding_error)
+}
+
+pub(crate) async fn warmup_async(
+    handle: DenoExecutionHandle,
+    arguments: RunnerArguments,
+    iterations: usize,
+) -> PyResult<()> {
+    handle
+        .warmup_async(arguments, iterations)
+        .await
+        .map_err(py_error::from_binding_error)
+}
+
+pub(crate) fn drain_logs_sync(py: Python<'_>, handle: &DenoExecutionHandle) -> SyncRunnerResult {
+    let messages = py
+        .detach(|| handle.drain_console_blocking())
+        .map_err(py_error::from_binding_error)?;
+    messages.to_py(py)
+}
+
+pub(crate) async fn drain_logs_async(handle: DenoExecutionHandle) -> AsyncRunnerResult {
+    handle
+        .drain_console_async()
+        .await
+        .map_err(py_error::from_binding_error)
+        .and_then(|messages| Python::attach(|py| messages.to_py(py)))
+}
+
+pub(crate) fn drain_rejections_sync(
+    py: Python<'_>,
+    handle: &DenoExecutionHandle,
+) -> SyncRunnerResult {
+    let rejections = py
+        .detach(|| handle.drain_rejections_blocking())
+        .map_err(py_error::from_binding_error)?;
+    rejections.to_py(py)
+}
+
+pub(crate) async fn drain_rejections_async(handle: DenoExecutionHandle) -> AsyncRunnerResult {
+    handle
+        .drain_rejections_async()
+        .await
+        .map_err(py_error::from_binding_error)
+        .and_then(|rejections| Python::attach(|py| rejections.to_py(py)))
+}
+
+pub(crate) fn globals_sync(
+    py: Python<'_>,
+    handle: &DenoExecutionHandle,
+    include_builtins: bool,
+) -> SyncRunnerResult {
+    let globals = py
+        .detach(|| handle.globals_blocking(include_builtins))
+        .map_err(py_error::from_binding_error)?;
+    globals.to_py(py)
+}
+
+pub(crate) async fn globals_async(
+    handle: DenoExecutionHandle,
+    include_builtins: bool,
+) -> AsyncRunnerResult {
+    handle
+        .globals_async(include_builtins)
+        .await
+        .map_err(py_error::from_binding_error)
+        .and_then(|globals| Python::attach(|py| globals.to_py(py)))
+}
+
+pub(crate) fn heap_stats_sync(py: Python<'_>, handle: &DenoExecutionHandle) -> SyncRunnerResult {
+    let stats = py
+        .detach(|| handle.heap_stats_blocking())
+        .map_err(py_error::from_binding_error)?;
+    stats.to_py(py)
+}
+
+pub(crate) async fn heap_stats_async(handle: DenoExecutionHandle) -> AsyncRunnerResult {
+    handle
+        .heap_stats_async()
+        .await
+        .map_err(py_error::from_binding_error)
+        .and_then(|stats| Python::attach(|py| stats.to_py(py)))
+}
+
+pub(crate) fn load_sync(py: Python<'_>, handle: &DenoExecutionHandle) -> PyResult<()> {
+    py.detach(|| handle.load_blocking())
+        .map_err(py_error::from_binding_error)
+}
+
+pub(crate) async fn load_async(handle: DenoExecutionHandle) -> PyResult<()> {
+    handle
+        .load_async()
+        .await
+        .map_err(py_error::from_binding_error)
+}
+
+pub(crate) fn ready_sync(py: Python<'_>, handle: &DenoExecutionHandle) -> PyResult<()> {
+    py.detach(|| handle.ready_blocking())
+        .map_err(py_error::from_binding_error)
+}
+
+pub(crate) async fn ready_async(handle: DenoExecutionHandle) -> PyResult<()> {
+    handle
+        .ready_async()
+        .await
+        .map_err(py_error::from_binding_error)
+}
+
+pub(crate) fn evaluate_sync(py: Python<'_>, handle: &DenoExecutionHandle) -> SyncRunnerResult {
+    let value = py
+        .detach(|| handle.default_export_blocking())
+        .map_err(py_error::from_binding_error)?;
+    value.to_py(py)
+}
+
+pub(crate) async fn evaluate_async(handle: DenoExecutionHandle) -> AsyncRunnerResult {
+    handle
+        .default_export_async()
+        .await
         .map_err(py_error::from_binding_error)
         .and_then(|value| Python::attach(|py| value.to_py(py)))
 }
 
+pub(crate) fn start_profiling_sync(py: Python<'_>, handle: &DenoExecutionHandle) -> PyResult<()> {
+    py.detach(|| handle.start_profiling_blocking())
+        .map_err(py_error::from_binding_error)
+}
+
+pub(crate) async fn start_profiling_async(handle: DenoExecutionHandle) -> PyResult<()> {
+    handle
+        .start_profiling_async()
+        .await
+        .map_err(py_error::from_binding_error)
+}
+
+pub(crate) fn stop_profiling_sync(
+    py: Python<'_>,
+    handle: &DenoExecutionHandle,
+) -> SyncRunnerResult {
+    let profile = py
+        .detach(|| handle.stop_profiling_blocking())
+        .map_err(py_error::from_binding_error)?;
+    Ok(PyBytes::new(py, &profile).into_any().unbind())
+}
+
+pub(crate) async fn stop_profiling_async(handle: DenoExecutionHandle) -> AsyncRunnerResult {
+    let profile = handle
+        .stop_profiling_async()
+        .await
+        .map_err(py_error::from_binding_error)?;
+    Ok(Python::attach(|py| {
+        PyBytes::new(py, &profile).into_any().unbind()
+    }))
+}
+
+pub(crate) fn heap_snapshot_sync(py: Python<'_>, handle: &DenoExecutionHandle) -> SyncRunnerResult {
+    let snapshot = py
+        .detach(|| handle.heap_snapshot_blocking())
+        .map_err(py_error::from_binding_error)?;
+    Ok(PyBytes::new(py, &snapshot).into_any().unbind())
+}
+
+pub(crate) async fn heap_snapshot_async(handle: DenoExecutionHandle) -> AsyncRunnerResult {
+    let snapshot = handle
+        .heap_snapshot_async()
+        .await
+        .map_err(py_error::from_binding_error)?;
+    Ok(Python::attach(|py| {
+        PyBytes::new(py, &snapshot).into_any().unbind()
+    }))
+}
+
+pub(crate) fn capture_state_sync(py: Python<'_>, handle: &DenoExecutionHandle) -> SyncRunnerResult {
+    let state = py
+        .detach(|| handle.capture_state_blocking())
+        .map_err(py_error::from_binding_error)?;
+    Ok(PyBytes::new(py, &state).into_any().unbind())
+}
+
+pub(crate) async fn capture_state_async(handle: DenoExecutionHandle) -> AsyncRunnerResult {
+    let state = handle
+        .capture_state_async()
+        .await
+        .map_err(py_error::from_binding_error)?;
+    Ok(Python::attach(|py| {
+        PyBytes::new(py, &state).into_any().unbind()
+    }))
+}
+
+pub(crate) fn reset_sync(py: Python<'_>, handle: &DenoExecutionHandle) -> PyResult<()> {
+    py.detach(|| handle.reset_blocking())
+        .map_err(py_error::from_binding_error)
+}
+
+pub(crate) async fn reset_async(handle: DenoExecutionHandle) -> PyResult<()> {
+    handle
+        .reset_async()
+        .await
+        .map_err(py_error::from_binding_error)
+}
+
 #[cfg(test)]
 mod tests {
     use super::{execute_async, execute_sync};
@@ -192,6 +534,7 @@ mod tests {
         let transpiled =
             module_loader::maybe_transpile_source(&specifier, bound.script().execution_content())
                 .expect("inline TSX should transpile");
+        let transpiled = transpiled.as_str();
 
         assert!(
             transpiled.contains("npm:react@19.2.6/jsx-runtime"),
@@ -329,6 +672,29 @@ mod tests {
             .expect("execution handle should close cleanly after cancel");
     }
 
+    #[test]
+    fn aborted_async_invoke_interrupts_the_call_and_keeps_the_worker() {
+        let bound = bound_inline(
+            "export default function run(value) { if (value === undefined) { while (true) {} } return value + 1; }",
+        );
+        let handle = handle(bound);
+        let runtime = pyo3_async_runtimes::tokio::get_runtime();
+        let invoke = handle.clone();
+        let task = runtime.spawn(async move { invoke.invoke_async(empty_arguments()).await });
+
+        std::thread::sleep(Duration::from_millis(50));
+        task.abort();
+        let _ = runtime.block_on(task);
+
+        let value = handle
+            .invoke_blocking(scalar_input_arguments())
+            .expect("the worker should serve calls after an interrupted one");
+        assert_eq!(value.to_json(), deno_core::serde_json::json!(42));
+        handle
+            .close_blocking()
+            .expect("execution handle should close cleanly after an interrupt");
+    }
+
     #[test]
     fn queued_invoke_after_cancel_is_rejected() {
         let bound =
@@ -0,0 +1,418 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::Arc,
+    time::Instant,
+};
+
+use deno_core::{ExternalOpsTracker, JsRuntime, V8CrossThreadTaskSpawner, v8};
+use pyo3::{
+    Py, PyAny, PyErr, PyResult, Python,
+    types::{PyAnyMethods, PyCFunction, PyTuple, PyTupleMethods},
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::runtime::OpMetrics;
+use crate::runtime::native_error::{native_error, panic_reason, throw_native_error};
+use crate::types::{error::BindingError, value::PyJsValue};
+
+const HOST_GLOBAL: &str = "host";
+/// Built into every `host` object, so it cannot be registered as a host function.
+pub(crate) const EMIT_FUNCTION: &str = "emit";
+/// Records a diagnostic for the running call; also built in and reserved.
+pub(crate) const WARN_FUNCTION: &str = "warn";
+
+thread_local! {
+    static WORKER_HOST_FUNCTIONS: RefCell<HostFunctions> = RefCell::new(HostFunctions::default());
+    static WORKER_STREAM: RefCell<Option<UnboundedSender<PyJsValue>>> = const { RefCell::new(None) };
+    static WORKER_DIAGNOSTICS: RefCell<Vec<PyJsValue>> = const { RefCell::new(Vec::new()) };
+    static WORKER_OP_METRICS: RefCell<Option<Arc<OpMetrics>>> = const { RefCell::new(None) };
+    static WORKER_PENDING_CALLS: RefCell<PendingCalls> = RefCell::new(PendingCalls::default());
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct HostFunctions {
+    functions: BTreeMap<String, Arc<HostFunction>>,
+}
+
+#[derive(Debug)]
+pub(crate) struct HostFunction {
+    callable: Py<PyAny>,
+    event_loop: Option<Py<PyAny>>,
+}
+
+/// What a host function returned: a value JavaScript gets now, or an awaitable whose result
+/// settles a promise later.
+enum HostReturn {
+    Value(PyJsValue),
+    Awaitable(Py<PyAny>),
+}
+
+/// Promises of host calls whose awaitables are still running, by call id. Resolvers never leave
+/// the worker thread; the thread finishing an awaitable only sends its outcome back.
+#[derive(Default)]
+struct PendingCalls {
+    next_id: u64,
+    resolvers: HashMap<u64, PendingResolver>,
+}
+
+struct PendingResolver {
+    origin: String,
+    started: Instant,
+    resolver: v8::Global<v8::PromiseResolver>,
+}
+
+/// One awaitable host call, which keeps its isolate's event loop alive until it settles.
+#[derive(Clone)]
+struct PendingHostCall {
+    id: u64,
+    spawner: V8CrossThreadTaskSpawner,
+    tracker: ExternalOpsTracker,
+}
+
+impl HostFunctions {
+    pub(crate) fn insert(&mut self, name: String, function: HostFunction) {
+        self.functions.insert(name, Arc::new(function));
+    }
+
+    fn get(&self, name: &str) -> Option<Arc<HostFunction>> {
+        self.functions.get(name).cloned()
+    }
+}
+
+impl HostFunction {
+    /// `event_loop` runs awaitable results; without one they run on a background loop that
+    /// belgie starts on first use.
+    pub(crate) fn new(callable: Py<PyAny>, event_loop: Option<Py<PyAny>>) -> Self {
+        Self {
+            callable,
+            event_loop,
+        }
+    }
+
+    fn call(&self, name: &str, arguments: Vec<PyJsValue>) -> Result<HostReturn, BindingError> {
+        Python::attach(|py| -> PyResult<HostReturn> {
+            let arguments = arguments
+                .iter()
+                .map(|argument| argument.to_py(py))
+                .collect::<PyResult<Vec<_>>>()?;
+            let result = self.callable.bind(py).call1(PyTuple::new(py, arguments)?)?;
+            if py
+                .import("inspect")?
+                .call_method1("isawaitable", (&result,))?
+                .is_truthy()?
+            {
+                return Ok(HostReturn::Awaitable(result.unbind()));
+            }
+            PyJsValue::from_py(&result).map(HostReturn::Value)
+        })
+        .map_err(|error| host_call_error(name, &error))
+    }
+
+    /// Starts `awaitable` on its loop and returns at once, so the worker keeps running
+    /// JavaScript, including calls the awaitable makes into other runners, while it runs.
+    fn schedule(
+        &self,
+        name: &str,
+        awaitable: Py<PyAny>,
+        call: PendingHostCall,
+    ) -> Result<(), BindingError> {
+        Python::attach(|py| -> PyResult<()> {
+            let name = name.to_string();
+            let done = PyCFunction::new_closure(py, None, None, move |args, _kwargs| {
+                let result = args
+                    .get_item(0)?
+                    .call_method0("result")
+                    .and_then(|value| PyJsValue::from_py(&value))
+                    .map_err(|error| host_call_error(&name, &error));
+                call.settle(result);
+                PyResult::Ok(())
+            })?;
+            let event_loop = self
+                .event_loop
+                .as_ref()
+                .map(|event_loop| event_loop.bind(py));
+            py.import("belgie._awaitable")?
+                .getattr("run_host_awaitable")?
+                .call1((awaitable, event_loop, done))?;
+            Ok(())
+        })
+        .map_err(|error| host_call_error(name, &error))
+    }
+}
+
+impl PendingHostCall {
+    /// Records a pending call for the isolate running `scope` and returns its promise.
+    fn start<'s>(
+        scope: &mut v8::PinScope<'s, '_>,
+        origin: &str,
+        started: Instant,
+    ) -> Option<(Self, v8::Local<'s, v8::Promise>)> {
+        let resolver = v8::PromiseResolver::new(scope)?;
+        let promise = resolver.get_promise(scope);
+        let (spawner, tracker) = {
+            let op_state = JsRuntime::op_state_from(scope);
+            let op_state = op_state.borrow();
+            (
+                op_state.borrow::<V8CrossThreadTaskSpawner>().clone(),
+                op_state.external_ops_tracker.clone(),
+            )
+        };
+        let resolver = PendingResolver {
+            origin: origin.to_string(),
+            started,
+            resolver: v8::Global::new(scope, resolver),
+        };
+        let id = WORKER_PENDING_CALLS.with(|pending| {
+            let mut pending = pending.borrow_mut();
+            pending.next_id += 1;
+            let id = pending.next_id;
+            pending.resolvers.insert(id, resolver);
+            id
+        });
+        tracker.ref_op();
+        Some((
+            Self {
+                id,
+                spawner,
+                tracker,
+            },
+            promise,
+        ))
+    }
+
+    /// Callable from any thread: the promise settles on the worker's next event loop turn.
+    fn settle(&self, result: Result<PyJsValue, BindingError>) {
+        let id = self.id;
+        let tracker = self.tracker.clone();
+        self.spawner.spawn(move |scope| {
+            settle_host_call(scope, id, result);
+            tracker.unref_op();
+        });
+    }
+
+    /// Forgets a call whose awaitable never started, releasing the event loop it held.
+    fn abandon(self) {
+        WORKER_PENDING_CALLS.with(|pending| pending.borrow_mut().resolvers.remove(&self.id));
+        self.tracker.unref_op();
+    }
+}
+
+fn host_call_error(name: &str, error: &PyErr) -> BindingError {
+    BindingError::javascript(format!("host.{name} failed: {error}"))
+}
+
+/// Resolves call `id`'s promise, unless its isolate was replaced since the call started.
+fn settle_host_call(scope: &mut v8::PinScope, id: u64, result: Result<PyJsValue, BindingError>) {
+    let Some(pending) =
+        WORKER_PENDING_CALLS.with(|pending| pending.borrow_mut().resolvers.remove(&id))
+    else {
+        return;
+    };
+    record_op_metric(&pending.origin, pending.started);
+    let resolver = v8::Local::new(scope, pending.resolver);
+    match result.and_then(|value| value.to_v8(scope)) {
+        Ok(value) => {
+            resolver.resolve(scope, value);
+        }
+        Err(error) => {
+            let exception = native_error(scope, &pending.origin, &error.message());
+            resolver.reject(scope, exception);
+        }
+    }
+}
+
+fn record_op_metric(origin: &str, started: Instant) {
+    WORKER_OP_METRICS.with(|metrics| {
+        if let Some(metrics) = metrics.borrow().as_ref() {
+            metrics.record(origin, started.elapsed());
+        }
+    });
+}
+
+/// Drops the promises of calls still awaiting Python; must run before their isolate is dropped.
+pub(crate) fn clear_worker_pending_calls() {
+    WORKER_PENDING_CALLS.with(|pending| pending.borrow_mut().resolvers.clear());
+}
+
+/// Makes `functions` callable from JavaScript running on the current worker thread.
+pub(crate) fn set_worker_host_functions(functions: HostFunctions) {
+    WORKER_HOST_FUNCTIONS.with(|current| *current.borrow_mut() = functions);
+}
+
+/// Counts host function calls on the current worker thread as `host.<name>` ops.
+pub(crate) fn set_worker_op_metrics(metrics: Option<Arc<OpMetrics>>) {
+    WORKER_OP_METRICS.with(|current| *current.borrow_mut() = metrics);
+}
+
+/// Routes `host.emit` chunks on the current worker thread to `stream` until it is replaced.
+pub(crate) fn set_worker_stream(stream: Option<UnboundedSender<PyJsValue>>) {
+    WORKER_STREAM.with(|current| *current.borrow_mut() = stream);
+}
+
+/// Returns the diagnostics `host.warn` recorded on the current worker thread since the last take.
+pub(crate) fn take_worker_diagnostics() -> Vec<PyJsValue> {
+    WORKER_DIAGNOSTICS.with(|diagnostics| diagnostics.take())
+}
+
+pub(crate) fn install_host_global(
+    scope: &mut v8::PinScope<'_, '_>,
+    functions: &HostFunctions,
+) -> Result<(), BindingError> {
+    let host = v8::Object::new(scope);
+    let emit_key = v8::String::new(scope, EMIT_FUNCTION)
+        .ok_or_else(|| BindingError::runtime("Could not create host function name"))?;
+    let emit = v8::Function::new(scope, emit_callback)
+        .ok_or_else(|| BindingError::runtime("Could not create host.emit"))?;
+    if !host
+        .set(scope, emit_key.into(), emit.into())
+        .unwrap_or(false)
+    {
+        return Err(BindingError::runtime("Could not install host.emit"));
+    }
+    let warn_key = v8::String::new(scope, WARN_FUNCTION)
+        .ok_or_else(|| BindingError::runtime("Could not create host function name"))?;
+    let warn = v8::Function::new(scope, warn_callback)
+        .ok_or_else(|| BindingError::runtime("Could not create host.warn"))?;
+    if !host
+        .set(scope, warn_key.into(), warn.into())
+        .unwrap_or(false)
+    {
+        return Err(BindingError::runtime("Could not install host.warn"));
+    }
+    for name in functions.functions.keys() {
+        let key = v8::String::new(scope, name)
+            .ok_or_else(|| BindingError::runtime("Could not create host function name"))?;
+        let function = v8::Function::builder(host_function_callback)
+            .data(key.into())
+            .build(scope)
+            .ok_or_else(|| {
+                BindingError::runtime(format!("Could not create host function {name}"))
+            })?;
+        if !host
+            .set(scope, key.into(), function.into())
+            .unwrap_or(false)
+        {
+            return Err(BindingError::runtime(format!(
+                "Could not install host function {name}",
+            )));
+        }
+    }
+    if !host
+        .set_integrity_level(scope, v8::IntegrityLevel::Frozen)
+        .unwrap_or(false)
+    {
+        return Err(BindingError::runtime("Could not freeze host functions"));
+    }
+
+    let key = v8::String::new(scope, HOST_GLOBAL)
+        .ok_or_else(|| BindingError::runtime("Could not create host global key"))?;
+    let global = scope.get_current_context().global(scope);
+    if !global
+        .define_own_property(
+            scope,
+            key.into(),
+            host.into(),
+            v8::PropertyAttribute::READ_ONLY | v8::PropertyAttribute::DONT_DELETE,
+        )
+        .unwrap_or(false)
+    {
+        return Err(BindingError::runtime("Could not install host functions"));
+    }
+    Ok(())
+}
+
+fn host_function_callback(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let name = args.data().to_rust_string_lossy(scope);
+    let origin = format!("host.{name}");
+    let arguments = (0..args.length())
+        .map(|index| PyJsValue::from_v8(scope, args.get(index)))
+        .collect::<Result<Vec<_>, _>>();
+    let started = Instant::now();
+    // A panic must not unwind into V8, so it becomes a JavaScript error like any other failure.
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        arguments.and_then(|arguments| {
+            let function = WORKER_HOST_FUNCTIONS
+                .with(|functions| functions.borrow().get(&name))
+                .ok_or_else(|| BindingError::runtime(format!("{origin} is not registered")))?;
+            function
+                .call(&name, arguments)
+                .map(|returned| (function, returned))
+        })
+    }))
+    .unwrap_or_else(|payload| {
+        Err(BindingError::javascript(format!(
+            "{origin} panicked: {}",
+            panic_reason(payload.as_ref())
+        )))
+    });
+    let result = match result {
+        Ok((function, HostReturn::Awaitable(awaitable))) => {
+            // Counted once the awaitable settles, so the op time covers the whole call.
+            let Some((call, promise)) = PendingHostCall::start(scope, &origin, started) else {
+                throw_native_error(scope, &origin, "Could not create a host call promise");
+                return;
+            };
+            if let Err(error) = function.schedule(&name, awaitable, call.clone()) {
+                call.abandon();
+                throw_native_error(scope, &origin, &error.message());
+                return;
+            }
+            return_value.set(promise.into());
+            return;
+        }
+        Ok((_, HostReturn::Value(value))) => {
+            record_op_metric(&origin, started);
+            value.to_v8(scope)
+        }
+        Err(error) => {
+            record_op_metric(&origin, started);
+            Err(error)
+        }
+    };
+    match result {
+        Ok(value) => return_value.set(value),
+        Err(error) => throw_native_error(scope, &origin, &error.message()),
+    }
+}
+
+/// Throws outside `runner.stream()` and once the consumer stops reading, so a loop left running
+/// by an abandoned stream ends at its next `emit` instead of leaking chunks into later calls.
+fn emit_callback(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    _return_value: v8::ReturnValue,
+) {
+    let result = PyJsValue::from_v8(scope, args.get(0)).and_then(|chunk| {
+        WORKER_STREAM.with(|stream| match stream.borrow().as_ref() {
+            Some(stream) => stream
+                .send(chunk)
+                .map_err(|_| BindingError::javascript("host.emit failed: the stream was closed")),
+            None => Err(BindingError::javascript(
+                "host.emit can only be called while the runner is streaming",
+            )),
+        })
+    });
+    if let Err(error) = result {
+        throw_native_error(scope, "host.emit", &error.message());
+    }
+}
+
+/// Diagnostics stay out of the call's result; `runner.diagnose()` returns them next to it.
+fn warn_callback(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    _return_value: v8::ReturnValue,
+) {
+    match PyJsValue::from_v8(scope, args.get(0)) {
+        Ok(diagnostic) => {
+            WORKER_DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().push(diagnostic));
+        }
+        Err(error) => throw_native_error(scope, "host.warn", &error.message()),
+    }
+}
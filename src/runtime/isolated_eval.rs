@@ -0,0 +1,98 @@
+use deno_core::{CONTEXT_STATE_SLOT_INDEX, JsRuntime, MODULE_MAP_SLOT_INDEX, v8};
+
+use crate::{runtime::native_error::throw_native_error, types::error::BindingError};
+
+/// Where the scratch runner finds the isolated `eval`; symbol-keyed so `globals()` skips it.
+const ISOLATED_EVAL_KEY: &str = "@belgie/eval/isolated";
+
+/// Installs a function that evaluates a classic script in a new context of the runner's
+/// isolate, so `eval(..., isolate=True)` sees none of the runner's globals, prototypes, or
+/// `host`.
+pub(crate) fn install_isolated_eval(runtime: &mut JsRuntime) -> Result<(), BindingError> {
+    deno_core::scope!(scope, runtime);
+    let key = v8::String::new(scope, ISOLATED_EVAL_KEY)
+        .ok_or_else(|| BindingError::runtime("Could not create isolated eval key"))?;
+    let key = v8::Symbol::for_key(scope, key);
+    let function = v8::Function::new(scope, isolated_eval_callback)
+        .ok_or_else(|| BindingError::runtime("Could not create isolated eval"))?;
+    let global = scope.get_current_context().global(scope);
+    if global
+        .set(scope, key.into(), function.into())
+        .unwrap_or(false)
+    {
+        Ok(())
+    } else {
+        Err(BindingError::runtime("Could not install isolated eval"))
+    }
+}
+
+fn isolated_eval_callback(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let Ok(source) = v8::Local::<v8::String>::try_from(args.get(0)) else {
+        throw_native_error(scope, "eval", "isolated eval source must be a string");
+        return;
+    };
+    let caller = scope.get_current_context();
+    let context = v8::Context::new(scope, Default::default());
+    // Same token, so values cross between the contexts without access checks.
+    context.set_security_token(caller.get_security_token(scope));
+    // deno_core's promise rejection and dynamic import hooks read these slots from whichever
+    // context is current, so the new context shares the caller's rather than leaving them empty.
+    for slot in [CONTEXT_STATE_SLOT_INDEX, MODULE_MAP_SLOT_INDEX] {
+        // SAFETY: both slots hold the caller realm's state, which lives until the isolate is torn
+        // down, and no script runs in either context after that.
+        unsafe {
+            context.set_aligned_pointer_in_embedder_data(
+                slot,
+                caller.get_aligned_pointer_from_embedder_data(slot),
+            );
+        }
+    }
+    let scope = &mut v8::ContextScope::new(scope, context);
+    // A compile or runtime error is already thrown to the caller.
+    let Some(script) = v8::Script::compile(scope, source, None) else {
+        return;
+    };
+    if let Some(value) = script.run(scope) {
+        return_value.set(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::with_test_js_runtime;
+
+    #[test]
+    fn evaluates_in_a_context_without_the_callers_globals() {
+        let result = with_test_js_runtime(|runtime| {
+            install_isolated_eval(runtime).expect("isolated eval should install");
+            let value = runtime
+                .execute_script(
+                    "belgie:test",
+                    r#"(() => {
+                      globalThis.shared = 1;
+                      Array.prototype.extra = "leaked";
+                      const isolated = globalThis[Symbol.for("@belgie/eval/isolated")];
+                      const inner = isolated("[typeof shared, typeof [].extra, this === globalThis]");
+                      let thrown = "";
+                      try {
+                        isolated("throw new RangeError('inside')");
+                      } catch (error) {
+                        thrown = error.message;
+                      }
+                      isolated("Promise.reject(new Error('unhandled')).catch(() => {})");
+                      return JSON.stringify([...inner, thrown, typeof shared]);
+                    })()"#,
+                )
+                .expect("isolated eval should evaluate");
+            deno_core::scope!(scope, runtime);
+            v8::Local::new(scope, value).to_rust_string_lossy(scope)
+        });
+
+        assert_eq!(result, r#"["undefined","undefined",false,"inside","number"]"#);
+    }
+}
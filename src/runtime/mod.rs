@@ -2,21 +2,48 @@ pub(crate) mod executor;
 pub(crate) mod module_loader;
 
 mod bound_runtime;
+pub(crate) mod captured_state;
 pub(crate) mod child_process;
 mod command_execution;
+pub(crate) mod cpu_affinity;
+pub(crate) mod cpu_budget;
 mod deno_runtime;
 mod error;
 mod execution;
+mod host;
+mod isolated_eval;
 mod native_addon_host;
+mod native_error;
+mod op_metrics;
 mod package_worker;
+mod performance;
+mod pool;
 mod process_context;
+mod profiler;
 mod session;
+pub(crate) mod startup_snapshot;
+mod state;
+mod storage;
+mod text_encoding;
+mod watch;
+mod watchdog;
+mod web_abort;
+mod web_base64;
+mod web_crypto;
+mod web_structured_clone;
+mod web_url;
 
 pub(crate) use bound_runtime::{BoundPackageEnvironment, BoundRuntime};
 pub(crate) use command_execution::{CommandExecutionHandle, CommandExecutionOptions};
 pub(crate) use deno_runtime::DenoRuntime;
-pub(crate) use execution::DenoExecutionHandle;
+pub(crate) use execution::{DenoExecutionHandle, InvocationStream};
+pub(crate) use host::{EMIT_FUNCTION, HostFunction, HostFunctions, WARN_FUNCTION};
+pub(crate) use op_metrics::OpMetrics;
+pub(crate) use pool::RunnerPool;
+pub(crate) use state::SharedState;
+pub(crate) use storage::WebStorage;
+pub(crate) use watch::{ScriptWatch, WatchCallback};
 
 #[cfg(test)]
 pub(crate) use execution::with_test_js_runtime;
-pub(crate) use session::RuntimeSession;
+pub(crate) use session::{BorrowedRunner, RuntimeSession};
@@ -1,10 +1,18 @@
-use std::{borrow::Cow, fs, future::Future, path::PathBuf, pin::Pin, sync::Arc};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fs,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
 
 use deno_ast::{MediaType, ParseParams, SourceMapOption};
 use deno_cache_dir::file_fetcher::MemoryFiles as _;
 use deno_core::{
-    ModuleLoadOptions, ModuleLoadReferrer, ModuleLoadResponse, ModuleLoader, ModuleSource,
-    ModuleSourceCode, ModuleSpecifier, ModuleType, RequestedModuleType, ResolutionKind,
+    ModuleCodeString, ModuleLoadOptions, ModuleLoadReferrer, ModuleLoadResponse, ModuleLoader,
+    ModuleSource, ModuleSourceCode, ModuleSpecifier, ModuleType, RequestedModuleType, ResolutionKind,
     error::ModuleLoaderError, serde_json,
 };
 use deno_error::JsErrorBox;
@@ -28,7 +36,86 @@ use crate::embed::PackageRuntimeState;
 use crate::embed::insert_memory_file;
 
 #[derive(Debug, Default)]
-pub(crate) struct PythonModuleLoader;
+pub(crate) struct PythonModuleLoader {
+    memory_modules: HashMap<ModuleSpecifier, Arc<str>>,
+    imports: BTreeMap<String, ModuleSpecifier>,
+    cache: Option<Arc<ModuleCache>>,
+    module_root: Option<PathBuf>,
+}
+
+/// Transpiled file modules shared by the runners of one runtime, so binding or resetting a
+/// runner reuses its TypeScript imports instead of parsing and transpiling them again. Entries
+/// are never revalidated against the file; `clear` picks up edited sources.
+#[derive(Debug, Default)]
+pub(crate) struct ModuleCache {
+    modules: Mutex<HashMap<ModuleSpecifier, Arc<str>>>,
+}
+
+impl ModuleCache {
+    pub(crate) fn clear(&self) {
+        self.modules().clear();
+    }
+
+    fn transpiled(
+        &self,
+        module_specifier: &ModuleSpecifier,
+        path: &std::path::Path,
+        media_type: MediaType,
+    ) -> Result<Arc<str>, ModuleLoaderError> {
+        if let Some(code) = self.modules().get(module_specifier) {
+            return Ok(code.clone());
+        }
+        // Transpile outside the lock so runners loading different modules do not wait in turn.
+        let code = Arc::<str>::from(transpile_module(module_specifier, path, media_type)?);
+        self.modules()
+            .insert(module_specifier.clone(), code.clone());
+        Ok(code)
+    }
+
+    fn modules(&self) -> std::sync::MutexGuard<'_, HashMap<ModuleSpecifier, Arc<str>>> {
+        self.modules
+            .lock()
+            .expect("module cache lock should not be poisoned")
+    }
+}
+
+impl PythonModuleLoader {
+    pub(crate) fn with_memory_modules(memory_modules: HashMap<ModuleSpecifier, Arc<str>>) -> Self {
+        Self {
+            memory_modules,
+            imports: BTreeMap::new(),
+            cache: None,
+            module_root: None,
+        }
+    }
+
+    pub(crate) fn with_imports(mut self, imports: BTreeMap<String, ModuleSpecifier>) -> Self {
+        self.imports = imports;
+        self
+    }
+
+    pub(crate) fn with_cache(mut self, cache: Arc<ModuleCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub(crate) fn with_module_root(mut self, module_root: Option<PathBuf>) -> Self {
+        self.module_root = module_root;
+        self
+    }
+
+    /// Exact entries win; otherwise the longest `prefix/` entry maps the remainder.
+    fn resolve_mapped(&self, specifier: &str) -> Option<Result<ModuleSpecifier, url::ParseError>> {
+        if let Some(target) = self.imports.get(specifier) {
+            return Some(Ok(target.clone()));
+        }
+        self.imports
+            .iter()
+            .filter(|(prefix, _)| prefix.ends_with('/') && specifier.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, target)| target.join(&specifier[prefix.len()..]))
+    }
+}
 
 impl ModuleLoader for PythonModuleLoader {
     fn resolve(
@@ -37,6 +124,9 @@ impl ModuleLoader for PythonModuleLoader {
         referrer: &str,
         _kind: ResolutionKind,
     ) -> Result<ModuleSpecifier, ModuleLoaderError> {
+        if let Some(resolved) = self.resolve_mapped(specifier) {
+            return resolved.map_err(JsErrorBox::from_err);
+        }
         deno_core::resolve_import(specifier, referrer).map_err(JsErrorBox::from_err)
     }
 
@@ -46,18 +136,64 @@ impl ModuleLoader for PythonModuleLoader {
         _maybe_referrer: Option<&ModuleLoadReferrer>,
         options: ModuleLoadOptions,
     ) -> ModuleLoadResponse {
-        ModuleLoadResponse::Sync(load_module_source(
-            module_specifier,
-            options.requested_module_type,
-        ))
+        if let Some(source) = self.memory_modules.get(module_specifier) {
+            return ModuleLoadResponse::Sync(
+                maybe_transpile_source(module_specifier, source.clone()).map(|code| {
+                    ModuleSource::new(
+                        ModuleType::JavaScript,
+                        ModuleSourceCode::String(code),
+                        module_specifier,
+                        None,
+                    )
+                }),
+            );
+        }
+        ModuleLoadResponse::Sync(
+            ensure_within_root(self.module_root.as_deref(), module_specifier).and_then(|()| {
+                load_module_source_with_media_type(
+                    module_specifier,
+                    options.requested_module_type,
+                    None,
+                    false,
+                    self.cache.as_deref(),
+                )
+            }),
+        )
     }
 }
 
+/// Rejects a file module whose real path, once `..` segments and symlinks are resolved, is
+/// outside `root`. Missing files are left to the loader's own not-found error.
+fn ensure_within_root(
+    root: Option<&Path>,
+    module_specifier: &ModuleSpecifier,
+) -> Result<(), ModuleLoaderError> {
+    let Some(root) = root else {
+        return Ok(());
+    };
+    let Some(path) = module_specifier
+        .to_file_path()
+        .ok()
+        .and_then(|path| fs::canonicalize(path).ok())
+    else {
+        return Ok(());
+    };
+    if path.starts_with(root) {
+        Ok(())
+    } else {
+        Err(JsErrorBox::generic(format!(
+            "Module {module_specifier} is outside the module root {}",
+            root.display()
+        )))
+    }
+}
+
+#[cfg(test)]
 fn load_module_source(
     module_specifier: &ModuleSpecifier,
     requested_module_type: RequestedModuleType,
 ) -> Result<ModuleSource, ModuleLoaderError> {
-    load_module_source_with_media_type(module_specifier, requested_module_type, None, false)
+    load_module_source_with_media_type(module_specifier, requested_module_type, None, false, None)
 }
 
 fn load_module_source_with_media_type(
@@ -65,6 +201,7 @@ fn load_module_source_with_media_type(
     requested_module_type: RequestedModuleType,
     media_type_override: Option<MediaType>,
     allow_json_without_attribute: bool,
+    cache: Option<&ModuleCache>,
 ) -> Result<ModuleSource, ModuleLoaderError> {
     let path = module_specifier
         .to_file_path()
@@ -104,7 +241,13 @@ fn load_module_source_with_media_type(
         ));
     }
 
-    let code = if should_transpile {
+    let code = if let Some(cache) = cache.filter(|_| should_transpile) {
+        ModuleSourceCode::String(
+            cache
+                .transpiled(module_specifier, &path, media_type)?
+                .into(),
+        )
+    } else if should_transpile {
         ModuleSourceCode::String(transpile_module(module_specifier, &path, media_type)?.into())
     } else {
         ModuleSourceCode::Bytes(
@@ -118,8 +261,8 @@ fn load_module_source_with_media_type(
 
 pub(crate) fn maybe_transpile_source(
     module_specifier: &ModuleSpecifier,
-    source: String,
-) -> Result<String, ModuleLoaderError> {
+    source: Arc<str>,
+) -> Result<ModuleCodeString, ModuleLoaderError> {
     let path = module_specifier
         .to_file_path()
         .map_err(|_| JsErrorBox::generic("Only file:// URLs are supported."))?;
@@ -132,9 +275,9 @@ pub(crate) fn maybe_transpile_source(
         )));
     }
     if should_transpile {
-        transpile_source(module_specifier, source, media_type)
+        transpile_source(module_specifier, source, media_type).map(Into::into)
     } else {
-        Ok(source)
+        Ok(source.into())
     }
 }
 
@@ -179,17 +322,17 @@ fn transpile_module(
     let source = fs::read_to_string(path).map_err(|error| {
         JsErrorBox::generic(format!("Failed to load {module_specifier}: {error}"))
     })?;
-    transpile_source(module_specifier, source, media_type)
+    transpile_source(module_specifier, source.into(), media_type)
 }
 
 fn transpile_source(
     module_specifier: &ModuleSpecifier,
-    source: String,
+    source: Arc<str>,
     media_type: MediaType,
 ) -> Result<String, ModuleLoaderError> {
     let parsed = deno_ast::parse_module(ParseParams {
         specifier: module_specifier.clone(),
-        text: source.into(),
+        text: source,
         media_type,
         capture_tokens: false,
         scope_analysis: false,
@@ -205,7 +348,7 @@ fn transpile_source(
             },
             &deno_ast::TranspileModuleOptions { module_kind: None },
             &deno_ast::EmitOptions {
-                source_map: SourceMapOption::None,
+                source_map: SourceMapOption::Inline,
                 ..Default::default()
             },
         )
@@ -218,11 +361,30 @@ fn transpile_source(
 pub(crate) struct PackageAwareModuleLoader {
     state: Arc<PackageRuntimeState>,
     initial_cwd: PathBuf,
+    module_root: Option<PathBuf>,
+    cache: Option<Arc<ModuleCache>>,
 }
 
 impl PackageAwareModuleLoader {
     pub(crate) fn new(state: Arc<PackageRuntimeState>, initial_cwd: PathBuf) -> Self {
-        Self { state, initial_cwd }
+        Self {
+            state,
+            initial_cwd,
+            module_root: None,
+            cache: None,
+        }
+    }
+
+    /// Confines local file imports; npm packages and remote modules load as before.
+    pub(crate) fn with_module_root(mut self, module_root: Option<PathBuf>) -> Self {
+        self.module_root = module_root;
+        self
+    }
+
+    /// Shares transpiled local files; npm packages and remote modules are cached by Deno.
+    pub(crate) fn with_cache(mut self, cache: Option<Arc<ModuleCache>>) -> Self {
+        self.cache = cache;
+        self
     }
 
     fn resolve_referrer(&self, referrer: &str) -> Result<ModuleSpecifier, ModuleLoaderError> {
@@ -356,7 +518,7 @@ impl PackageAwareModuleLoader {
         source: String,
     ) -> Result<(), ModuleLoaderError> {
         let url = Url::parse(module_specifier.as_str()).map_err(JsErrorBox::from_err)?;
-        insert_memory_file(&self.state.memory_files, url, source);
+        insert_memory_file(&self.state.memory_files, url, source.into());
         Ok(())
     }
 
@@ -390,10 +552,10 @@ impl PackageAwareModuleLoader {
                 None,
             ));
         }
-        let code = maybe_transpile_source(module_specifier, source)?;
+        let code = maybe_transpile_source(module_specifier, source.into())?;
         Ok(ModuleSource::new(
             ModuleType::JavaScript,
-            ModuleSourceCode::String(code.into()),
+            ModuleSourceCode::String(code),
             module_specifier,
             None,
         ))
@@ -554,23 +716,35 @@ impl ModuleLoader for PackageAwareModuleLoader {
                 .ok()
                 .is_some_and(|path| path.exists())
         {
-            return ModuleLoadResponse::Sync(load_module_source_with_media_type(
-                module_specifier,
-                options.requested_module_type,
-                None,
-                matches!(self.state.allow_json_imports, AllowJsonImports::Always),
-            ));
+            return ModuleLoadResponse::Sync(
+                ensure_within_root(self.module_root.as_deref(), module_specifier).and_then(|()| {
+                    load_module_source_with_media_type(
+                        module_specifier,
+                        options.requested_module_type,
+                        None,
+                        matches!(self.state.allow_json_imports, AllowJsonImports::Always),
+                        self.cache.as_deref(),
+                    )
+                }),
+            );
         }
 
         let state = self.state.clone();
         let initial_cwd = self.initial_cwd.clone();
+        let module_root = self.module_root.clone();
+        let cache = self.cache.clone();
         let module_specifier = module_specifier.clone();
         let maybe_referrer = maybe_referrer.map(|referrer| referrer.specifier.clone());
         let requested_module_type = options.requested_module_type;
 
         ModuleLoadResponse::Async(
             async move {
-                let loader = PackageAwareModuleLoader { state, initial_cwd };
+                let loader = PackageAwareModuleLoader {
+                    state,
+                    initial_cwd,
+                    module_root,
+                    cache,
+                };
                 loader
                     .load_package_module(
                         &module_specifier,
@@ -601,9 +775,16 @@ impl ModuleLoader for PackageAwareModuleLoader {
 
 #[cfg(test)]
 mod tests {
-    use super::{load_module_source, load_module_source_with_media_type};
-    use deno_core::{ModuleSourceCode, ModuleSpecifier, ModuleType, RequestedModuleType};
+    use super::{
+        ModuleCache, PythonModuleLoader, ensure_within_root, load_module_source,
+        load_module_source_with_media_type,
+    };
+    use deno_core::{
+        ModuleLoader, ModuleSourceCode, ModuleSpecifier, ModuleType, RequestedModuleType,
+        ResolutionKind,
+    };
     use std::{
+        collections::{BTreeMap, HashMap},
         fs, io,
         path::{Path, PathBuf},
         time::{SystemTime, UNIX_EPOCH},
@@ -645,6 +826,43 @@ mod tests {
             panic!("typescript modules should be loaded as transpiled string source");
         };
         assert!(!code.as_str().contains(": number"));
+        // deno_core reads inline maps when it compiles the module, which maps error frames back.
+        assert!(
+            code.as_str()
+                .contains("//# sourceMappingURL=data:application/json;base64,")
+        );
+    }
+
+    #[test]
+    fn reuses_cached_transpiled_modules_until_cleared() {
+        let root = temp_dir("cache").expect("temp dir should be created");
+        let path = root.join("dep.ts");
+        let load = |cache: &ModuleCache| {
+            let module = load_module_source_with_media_type(
+                &specifier(&path),
+                RequestedModuleType::None,
+                None,
+                false,
+                Some(cache),
+            )
+            .expect("typescript module should load");
+            let ModuleSourceCode::String(code) = module.code else {
+                panic!("typescript modules should be loaded as transpiled string source");
+            };
+            code.as_str().to_string()
+        };
+        let cache = ModuleCache::default();
+
+        fs::write(&path, "export const value: number = 1;\n").expect("module should be written");
+        let first = load(&cache);
+        fs::write(&path, "export const value: number = 2;\n").expect("module should be written");
+        let cached = load(&cache);
+        cache.clear();
+        let reloaded = load(&cache);
+
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!(first, cached);
+        assert!(reloaded.contains("= 2"));
     }
 
     #[test]
@@ -670,6 +888,7 @@ mod tests {
             RequestedModuleType::None,
             None,
             true,
+            None,
         )
         .expect("json module should load without an import attribute when configured");
 
@@ -680,4 +899,56 @@ mod tests {
         };
         assert_eq!(code.as_str(), "export default {\"answer\":42};\n");
     }
+
+    #[test]
+    fn resolves_bare_specifiers_through_script_imports() {
+        let target = |url: &str| ModuleSpecifier::parse(url).expect("target should parse");
+        let loader =
+            PythonModuleLoader::with_memory_modules(HashMap::new()).with_imports(BTreeMap::from([
+                ("react".to_string(), target("file:///app/vendor/react.js")),
+                ("lib/".to_string(), target("file:///app/lib/")),
+            ]));
+        let resolve = |specifier: &str| {
+            loader
+                .resolve(specifier, "file:///app/main.ts", ResolutionKind::Import)
+                .expect("specifier should resolve")
+                .to_string()
+        };
+
+        assert_eq!(resolve("react"), "file:///app/vendor/react.js");
+        assert_eq!(resolve("lib/math.js"), "file:///app/lib/math.js");
+        assert_eq!(resolve("./local.js"), "file:///app/local.js");
+        assert!(
+            loader
+                .resolve("react-dom", "file:///app/main.ts", ResolutionKind::Import)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn confines_file_modules_to_the_module_root() {
+        let outer = temp_dir("module-root").expect("temp dir should be created");
+        let root = outer.join("app");
+        fs::create_dir_all(root.join("components")).expect("root should be created");
+        fs::write(root.join("components/App.tsx"), "export default 1;")
+            .expect("component should be written");
+        fs::write(outer.join("secret.ts"), "export default 2;").expect("secret should be written");
+        let root = fs::canonicalize(&root).expect("root should canonicalize");
+        let escape = ModuleSpecifier::parse(&format!("{}/../secret.ts", specifier(&root)))
+            .expect("escape should parse");
+
+        let inside = ensure_within_root(Some(&root), &specifier(&root.join("components/App.tsx")));
+        let outside = ensure_within_root(Some(&root), &escape);
+        let unconfined = ensure_within_root(None, &escape);
+
+        let _ = fs::remove_dir_all(&outer);
+        assert!(inside.is_ok());
+        assert!(
+            outside
+                .expect_err("escaping import should be rejected")
+                .to_string()
+                .contains("outside the module root")
+        );
+        assert!(unconfined.is_ok());
+    }
 }
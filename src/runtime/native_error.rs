@@ -0,0 +1,66 @@
+use std::{any::Any, backtrace::Backtrace, cell::Cell};
+
+use deno_core::v8;
+
+thread_local! {
+    static WORKER_BACKTRACES: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether native callbacks on the current worker thread attach Rust backtraces to their errors.
+pub(crate) fn set_worker_backtraces(enabled: bool) {
+    WORKER_BACKTRACES.with(|current| current.set(enabled));
+}
+
+/// Throws `message` as a JavaScript `Error` from a Rust callback. With
+/// `RuntimeOptions(native_backtraces=True)` the message also names `origin` and carries the
+/// callback's Rust backtrace, so it is clear the failure came from native code.
+pub(crate) fn throw_native_error(scope: &mut v8::PinScope, origin: &str, message: &str) {
+    let exception = native_error(scope, origin, message);
+    scope.throw_exception(exception);
+}
+
+/// The `Error` `throw_native_error` throws, for callbacks that reject a promise instead.
+pub(crate) fn native_error<'s>(
+    scope: &mut v8::PinScope<'s, '_>,
+    origin: &str,
+    message: &str,
+) -> v8::Local<'s, v8::Value> {
+    let message = native_error_message(origin, message);
+    let message = v8::String::new(scope, &message).unwrap_or_else(|| v8::String::empty(scope));
+    v8::Exception::error(scope, message)
+}
+
+/// Describes a panic caught at a callback boundary, before it could unwind into V8.
+pub(crate) fn panic_reason(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic payload")
+}
+
+fn native_error_message(origin: &str, message: &str) -> String {
+    if !WORKER_BACKTRACES.with(Cell::get) {
+        return message.to_string();
+    }
+    format!(
+        "{message}\n\nNative backtrace ({origin}):\n{}",
+        Backtrace::force_capture()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attaches_backtraces_only_when_enabled() {
+        assert_eq!(native_error_message("host.fail", "boom"), "boom");
+
+        set_worker_backtraces(true);
+        let message = native_error_message("host.fail", "boom");
+        set_worker_backtraces(false);
+
+        assert!(message.starts_with("boom\n\nNative backtrace (host.fail):\n"));
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    rc::Rc,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use deno_core::{OpMetricsEvent, OpMetricsFactoryFn, OpMetricsFn};
+
+use crate::types::value::PyJsValue;
+
+/// Call counts and time spent per op, shared by every runner created from one runtime.
+#[derive(Debug, Default)]
+pub(crate) struct OpMetrics {
+    ops: Mutex<BTreeMap<String, OpTotals>>,
+}
+
+#[derive(Debug, Default)]
+struct OpTotals {
+    calls: u64,
+    total: Duration,
+    /// Dispatch times of calls that have not completed; async ops may overlap.
+    pending: VecDeque<Instant>,
+}
+
+impl OpMetrics {
+    /// Records a call that has already finished, such as a Python host function.
+    pub(crate) fn record(&self, name: &str, elapsed: Duration) {
+        self.update(name, |totals| {
+            totals.calls += 1;
+            totals.total += elapsed;
+        });
+    }
+
+    /// Reports every deno_core op of a lightweight runner to `metrics`.
+    pub(crate) fn factory(metrics: Arc<Self>) -> OpMetricsFactoryFn {
+        Box::new(move |_id, _count, decl| {
+            let metrics = metrics.clone();
+            let name = decl.name;
+            let record: OpMetricsFn = Rc::new(move |_ctx, event, _source| match event {
+                OpMetricsEvent::Dispatched => metrics.dispatched(name),
+                _ => metrics.completed(name),
+            });
+            Some(record)
+        })
+    }
+
+    /// Maps each op name to `{"calls": int, "total_ms": float}`.
+    pub(crate) fn to_json(&self) -> PyJsValue {
+        let ops = self.ops.lock().unwrap_or_else(PoisonError::into_inner);
+        PyJsValue::from_json(serde_json::Value::Object(
+            ops.iter()
+                .map(|(name, totals)| {
+                    let total_ms = totals.total.as_secs_f64() * 1000.0;
+                    (
+                        name.clone(),
+                        serde_json::json!({ "calls": totals.calls, "total_ms": total_ms }),
+                    )
+                })
+                .collect(),
+        ))
+    }
+
+    fn dispatched(&self, name: &str) {
+        let now = Instant::now();
+        self.update(name, |totals| totals.pending.push_back(now));
+    }
+
+    /// Overlapping calls of one async op may complete out of order; pairing them in dispatch
+    /// order still gives the right total once all of them have completed.
+    fn completed(&self, name: &str) {
+        let now = Instant::now();
+        self.update(name, |totals| {
+            if let Some(started) = totals.pending.pop_front() {
+                totals.calls += 1;
+                totals.total += now - started;
+            }
+        });
+    }
+
+    fn update(&self, name: &str, update: impl FnOnce(&mut OpTotals)) {
+        let mut ops = self.ops.lock().unwrap_or_else(PoisonError::into_inner);
+        match ops.get_mut(name) {
+            Some(totals) => update(totals),
+            None => update(ops.entry(name.to_string()).or_default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs_completions_with_dispatches_per_op() {
+        let metrics = OpMetrics::default();
+
+        metrics.dispatched("op_sleep");
+        metrics.dispatched("op_sleep");
+        metrics.completed("op_sleep");
+        metrics.record("host.lookup", Duration::from_millis(4));
+        metrics.record("host.lookup", Duration::from_millis(6));
+
+        let json = metrics.to_json().to_json();
+        assert_eq!(json["op_sleep"]["calls"], 1);
+        assert_eq!(json["host.lookup"]["calls"], 2);
+        assert_eq!(json["host.lookup"]["total_ms"], 10.0);
+    }
+}
@@ -1,8 +1,9 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use deno_cache_dir::file_fetcher::MemoryFiles;
 use deno_core::{FastString, ModuleSpecifier};
@@ -23,6 +24,7 @@ use deno_runtime::deno_permissions::PermissionsContainer;
 use deno_runtime::deno_tls::RootCertStoreProvider;
 use deno_runtime::deno_tls::rustls::RootCertStore;
 use deno_runtime::deno_web::{Blob, BlobStore, BlobStoreTrait};
+use deno_runtime::inspector_server::InspectorServer;
 use deno_runtime::permissions::RuntimePermissionDescriptorParser;
 use deno_runtime::{
     FeatureChecker, UnconfiguredRuntimeOptions, WorkerExecutionMode, WorkerLogLevel,
@@ -34,7 +36,7 @@ use crate::embed::sys::EmbedSys;
 use crate::embed::{EmbedContext, PackageRuntimeState, prepare_package_runtime};
 use crate::options::{JsRuntimeOptions, RuntimeWorkerOptions};
 use crate::runtime::error::map_package_environment_error;
-use crate::runtime::module_loader::PackageAwareModuleLoader;
+use crate::runtime::module_loader::{ModuleCache, PackageAwareModuleLoader};
 use crate::runtime::native_addon_host;
 use crate::types::error::BindingError;
 
@@ -43,9 +45,10 @@ pub(crate) struct BoundPackageWorkerOptions {
     pub argv0: Option<String>,
     pub js_runtime_options: JsRuntimeOptions,
     pub runtime_worker_options: RuntimeWorkerOptions,
-    pub main_source: Option<String>,
+    pub main_source: Option<Arc<str>>,
     pub header_overrides: HashMap<ModuleSpecifier, HashMap<String, String>>,
     pub node_ipc_init: Option<(i64, ChildIpcSerialization)>,
+    pub module_cache: Option<Arc<ModuleCache>>,
 }
 
 struct PackageWorkerRunOptions {
@@ -54,6 +57,7 @@ struct PackageWorkerRunOptions {
     js_runtime_options: JsRuntimeOptions,
     runtime_worker_options: RuntimeWorkerOptions,
     node_ipc_init: Option<(i64, ChildIpcSerialization)>,
+    module_cache: Option<Arc<ModuleCache>>,
 }
 
 pub(crate) async fn create_bound_package_worker(
@@ -71,6 +75,7 @@ pub(crate) async fn create_bound_package_worker(
         main_source,
         header_overrides,
         node_ipc_init,
+        module_cache,
     } = options;
     let state = Arc::new(
         prepare_package_runtime(
@@ -93,6 +98,7 @@ pub(crate) async fn create_bound_package_worker(
             js_runtime_options,
             runtime_worker_options,
             node_ipc_init,
+            module_cache,
         },
         roots,
     )
@@ -114,10 +120,12 @@ fn default_lib_main_worker_options(
         enable_raw_imports: runtime_worker_options.enable_raw_imports(),
         enable_testing_features: runtime_worker_options.enable_testing_features(),
         has_node_modules_dir: true,
-        inspect_brk: false,
+        inspect_brk: runtime_worker_options
+            .inspector()
+            .is_some_and(|inspector| inspector.break_on_first_statement()),
         inspect_wait: false,
         trace_ops: runtime_worker_options.trace_ops(),
-        is_inspecting: false,
+        is_inspecting: runtime_worker_options.inspector().is_some(),
         is_standalone: false,
         auto_serve: false,
         location: runtime_worker_options.location(),
@@ -161,6 +169,7 @@ fn create_package_worker(
         js_runtime_options,
         runtime_worker_options,
         node_ipc_init,
+        module_cache,
     } = options;
     let resolver_factory = context.resolver_factory();
     let npm_resolver = resolver_factory
@@ -173,6 +182,8 @@ fn create_package_worker(
     let module_loader_factory = Box::new(BelgieModuleLoaderFactory {
         state,
         initial_cwd: cwd.clone(),
+        module_root: js_runtime_options.module_root().map(Path::to_path_buf),
+        module_cache,
         cjs_tracker: resolver_factory
             .cjs_tracker()
             .map_err(map_package_environment_error)?
@@ -187,6 +198,10 @@ fn create_package_worker(
             .to_permissions()
             .map_err(BindingError::runtime)?,
     );
+    let inspector_server = runtime_worker_options
+        .inspector()
+        .map(|inspector| inspector_server(inspector.address()))
+        .transpose()?;
     let unconfigured_runtime = create_unconfigured_runtime(&js_runtime_options, roots)?;
     let main_module_url = url::Url::parse(main_module.as_str())
         .map_err(|error| BindingError::runtime(error.to_string()))?;
@@ -198,7 +213,7 @@ fn create_package_worker(
         Arc::new(FeatureChecker::default()),
         Arc::new(RealFs),
         None,
-        None,
+        inspector_server,
         module_loader_factory,
         node_resolver,
         create_npm_process_state_provider(npm_resolver),
@@ -230,6 +245,23 @@ fn create_package_worker(
     .map_err(|error| BindingError::runtime(error.to_string()))
 }
 
+/// Every worker inspected on one address registers with the same server, so runners and
+/// runtimes sharing `inspect` appear as separate targets instead of failing to bind.
+fn inspector_server(address: SocketAddr) -> Result<Arc<InspectorServer>, BindingError> {
+    static SERVERS: Mutex<BTreeMap<SocketAddr, Arc<InspectorServer>>> = Mutex::new(BTreeMap::new());
+    let mut servers = SERVERS
+        .lock()
+        .expect("inspector server registry lock should not be poisoned");
+    if let Some(server) = servers.get(&address) {
+        return Ok(server.clone());
+    }
+    let server = Arc::new(InspectorServer::new(address, "belgie").map_err(|error| {
+        BindingError::runtime(format!("Could not start inspector on {address}: {error}"))
+    })?);
+    servers.insert(address, server.clone());
+    Ok(server)
+}
+
 fn create_unconfigured_runtime(
     js_runtime_options: &JsRuntimeOptions,
     roots: &LibWorkerFactoryRoots,
@@ -268,6 +300,8 @@ fn create_unconfigured_runtime(
 struct BelgieModuleLoaderFactory {
     state: Arc<PackageRuntimeState>,
     initial_cwd: PathBuf,
+    module_root: Option<PathBuf>,
+    module_cache: Option<Arc<ModuleCache>>,
     cjs_tracker: CjsTrackerRc<DenoInNpmPackageChecker, EmbedSys>,
     npm_resolver: NpmResolver<EmbedSys>,
     memory_files: deno_resolver::loader::MemoryFilesRc,
@@ -296,10 +330,11 @@ impl BelgieModuleLoaderFactory {
             self.state.clone()
         };
         CreateModuleLoaderResult {
-            module_loader: Rc::new(PackageAwareModuleLoader::new(
-                state,
-                self.initial_cwd.clone(),
-            )),
+            module_loader: Rc::new(
+                PackageAwareModuleLoader::new(state, self.initial_cwd.clone())
+                    .with_module_root(self.module_root.clone())
+                    .with_cache(self.module_cache.clone()),
+            ),
             node_require_loader: Rc::new(BelgieNodeRequireLoader {
                 cjs_tracker: self.cjs_tracker.clone(),
                 npm_resolver: self.npm_resolver.clone(),
@@ -0,0 +1,123 @@
+use std::{sync::OnceLock, time::Instant};
+
+use deno_core::{JsRuntime, v8};
+
+use crate::types::error::BindingError;
+
+/// `performance.now()`/`performance.timeOrigin` for the lightweight backend, backed by a
+/// monotonic Rust clock. Deno workers ship the full User Timing API, so it is left alone.
+///
+/// Evaluates to a function that takes the clock and returns one that defines `performance` on
+/// its target.
+const PERFORMANCE_SOURCE: &str = r#"((monotonicNow) => (target) => {
+  if (typeof target.performance?.now === "function") {
+    return;
+  }
+  const origin = monotonicNow();
+  const timeOrigin = Date.now();
+  const performance = Object.freeze({
+    now: () => monotonicNow() - origin,
+    timeOrigin,
+    toJSON: () => ({ timeOrigin }),
+  });
+  Object.defineProperty(target, "performance", {
+    value: performance,
+    writable: true,
+    configurable: true,
+  });
+})"#;
+
+pub(crate) fn install_performance(runtime: &mut JsRuntime) -> Result<(), BindingError> {
+    let installer = performance_installer(runtime)?;
+    deno_core::scope!(scope, runtime);
+    let installer = v8::Local::new(scope, installer);
+    let global = scope.get_current_context().global(scope);
+    let receiver = v8::undefined(scope).into();
+    installer
+        .call(scope, receiver, &[global.into()])
+        .ok_or_else(|| BindingError::runtime("Could not install performance"))?;
+    Ok(())
+}
+
+/// The function that defines `performance` on the object it is called with.
+fn performance_installer(
+    runtime: &mut JsRuntime,
+) -> Result<v8::Global<v8::Function>, BindingError> {
+    let source = runtime
+        .execute_script("belgie:performance", PERFORMANCE_SOURCE)
+        .map_err(|error| {
+            BindingError::runtime(format!("Could not install performance: {error}"))
+        })?;
+    deno_core::scope!(scope, runtime);
+    let source = v8::Local::<v8::Function>::try_from(v8::Local::new(scope, source))
+        .map_err(|_| BindingError::runtime("Could not install performance"))?;
+    let monotonic_now = v8::Function::new(scope, monotonic_now_callback)
+        .ok_or_else(|| BindingError::runtime("Could not create performance clock"))?;
+    let receiver = v8::undefined(scope).into();
+    let installer = source
+        .call(scope, receiver, &[monotonic_now.into()])
+        .and_then(|installer| v8::Local::<v8::Function>::try_from(installer).ok())
+        .ok_or_else(|| BindingError::runtime("Could not install performance"))?;
+    Ok(v8::Global::new(scope, installer))
+}
+
+/// Milliseconds since a process-wide epoch; callers subtract their own origin.
+fn monotonic_now_callback(
+    _scope: &mut v8::PinScope,
+    _args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    let elapsed = EPOCH.get_or_init(Instant::now).elapsed();
+    return_value.set_double(elapsed.as_secs_f64() * 1000.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::with_test_js_runtime;
+
+    /// Runs `body` against the polyfill's own `performance`, even where the runtime brings its
+    /// own.
+    fn eval_with_polyfill(body: &'static str) -> String {
+        with_test_js_runtime(move |runtime| {
+            let installer =
+                performance_installer(runtime).expect("performance installer should evaluate");
+            let test = runtime
+                .execute_script(
+                    "belgie:test",
+                    format!(
+                        "((install) => {{ const polyfill = {{}}; install(polyfill); \
+                         const {{ performance }} = polyfill; {body} }})"
+                    ),
+                )
+                .expect("test function should evaluate");
+            deno_core::scope!(scope, runtime);
+            let test = v8::Local::<v8::Function>::try_from(v8::Local::new(scope, test))
+                .expect("test source should evaluate to a function");
+            let installer = v8::Local::new(scope, installer);
+            let receiver = v8::undefined(scope).into();
+            test.call(scope, receiver, &[installer.into()])
+                .expect("performance calls should evaluate")
+                .to_rust_string_lossy(scope)
+        })
+    }
+
+    #[test]
+    fn reports_monotonic_time_since_install() {
+        let result = eval_with_polyfill(
+            r#"
+            const first = performance.now();
+            const second = performance.now();
+            return JSON.stringify([
+              first >= 0 && second >= first,
+              Math.abs(performance.timeOrigin - Date.now()) < 60000,
+              performance.toJSON().timeOrigin === performance.timeOrigin,
+              performance === globalThis.performance,
+            ]);
+            "#,
+        );
+
+        assert_eq!(result, "[true,true,true,false]");
+    }
+}
@@ -0,0 +1,178 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use crate::runtime::{DenoExecutionHandle, RuntimeSession};
+use crate::script::ScriptSource;
+use crate::types::error::BindingError;
+
+/// Runners bound to one script, each on its own worker thread and isolate.
+#[derive(Debug)]
+pub(crate) struct RunnerPool {
+    session: Arc<RuntimeSession>,
+    script: ScriptSource,
+    workers: Mutex<Vec<PoolWorker>>,
+    cursor: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct PoolWorker {
+    handle: DenoExecutionHandle,
+    in_flight: Arc<AtomicUsize>,
+}
+
+/// Counts a call against its worker until dropped.
+#[derive(Debug)]
+pub(crate) struct PoolLease {
+    handle: DenoExecutionHandle,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl RunnerPool {
+    pub(crate) fn new(
+        session: &Arc<RuntimeSession>,
+        script: ScriptSource,
+        size: usize,
+    ) -> Result<Self, BindingError> {
+        if size == 0 {
+            return Err(BindingError::runtime("Runner pool size must be at least 1"));
+        }
+        let workers = (0..size)
+            .map(|_| PoolWorker::bind(session, &script))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            session: session.clone(),
+            script,
+            workers: Mutex::new(workers),
+            cursor: AtomicUsize::new(0),
+        })
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        self.workers().len()
+    }
+
+    /// Picks the worker with the fewest calls in flight. Ties rotate so idle workers share the
+    /// load evenly, and workers that stopped serving are replaced before anything is picked.
+    pub(crate) fn acquire(&self) -> Result<PoolLease, BindingError> {
+        let mut stopped = Vec::new();
+        let lease = self.lease(&mut stopped);
+        // Closing waits for each worker thread, so it happens once other callers can pick again.
+        for worker in stopped {
+            let _ = self.session.release_script(&worker.handle);
+        }
+        lease
+    }
+
+    fn lease(&self, stopped: &mut Vec<PoolWorker>) -> Result<PoolLease, BindingError> {
+        let mut workers = self.workers();
+        for worker in workers.iter_mut() {
+            if !worker.handle.is_alive() {
+                stopped.push(std::mem::replace(
+                    worker,
+                    PoolWorker::bind(&self.session, &self.script)?,
+                ));
+            }
+        }
+        let start = self.cursor.fetch_add(1, Ordering::Relaxed) % workers.len();
+        let worker = workers[start..]
+            .iter()
+            .chain(&workers[..start])
+            .min_by_key(|worker| worker.in_flight.load(Ordering::Acquire))
+            .expect("runner pools always hold at least one worker");
+        worker.in_flight.fetch_add(1, Ordering::AcqRel);
+        Ok(PoolLease {
+            handle: worker.handle.clone(),
+            in_flight: worker.in_flight.clone(),
+        })
+    }
+
+    pub(crate) fn is_alive(&self) -> bool {
+        self.session.is_active()
+    }
+
+    pub(crate) fn description(&self) -> String {
+        format!("{} workers for {}", self.size(), self.script.description())
+    }
+
+    fn workers(&self) -> std::sync::MutexGuard<'_, Vec<PoolWorker>> {
+        self.workers
+            .lock()
+            .expect("runner pool worker lock should not be poisoned")
+    }
+}
+
+impl PoolWorker {
+    fn bind(session: &Arc<RuntimeSession>, script: &ScriptSource) -> Result<Self, BindingError> {
+        Ok(Self {
+            handle: RuntimeSession::bind_script(session, script.clone())?,
+            in_flight: Arc::default(),
+        })
+    }
+}
+
+impl PoolLease {
+    pub(crate) fn handle(&self) -> &DenoExecutionHandle {
+        &self.handle
+    }
+}
+
+impl Drop for PoolLease {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::options::{RuntimeOptions, ScriptOptions};
+    use crate::runtime::{DenoRuntime, RuntimeSession};
+    use crate::script::ScriptSource;
+
+    use super::RunnerPool;
+
+    fn test_pool(size: usize) -> RunnerPool {
+        let cwd = std::env::current_dir().expect("current dir should be available");
+        let session = RuntimeSession::activate(DenoRuntime::new(RuntimeOptions::new(cwd)))
+            .expect("runtime session should activate");
+        let script = ScriptSource::from_options(ScriptOptions::inline(
+            "export default () => 'ok';".to_string(),
+        ));
+        RunnerPool::new(&session, script, size).expect("runner pool should bind")
+    }
+
+    #[test]
+    fn spreads_concurrent_leases_across_idle_workers() {
+        let pool = test_pool(3);
+
+        let leases = (0..3)
+            .map(|_| pool.acquire().expect("lease should be granted"))
+            .collect::<Vec<_>>();
+
+        for (index, lease) in leases.iter().enumerate() {
+            for other in &leases[index + 1..] {
+                assert!(!Arc::ptr_eq(&lease.in_flight, &other.in_flight));
+            }
+        }
+    }
+
+    #[test]
+    fn replaces_workers_that_stopped_serving() {
+        let pool = test_pool(1);
+        let stopped = pool.acquire().expect("lease should be granted");
+        stopped.handle().cancel();
+        while stopped.handle().is_alive() {
+            std::thread::yield_now();
+        }
+        drop(stopped);
+
+        let lease = pool
+            .acquire()
+            .expect("a replacement worker should be bound");
+
+        assert!(lease.handle().is_alive());
+    }
+}
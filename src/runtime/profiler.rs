@@ -0,0 +1,92 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use deno_core::{
+    InspectorMsg, InspectorMsgKind, InspectorSessionKind, InspectorSessionOptions, JsRuntime,
+    JsRuntimeInspector, LocalInspectorSession,
+};
+
+use crate::types::error::BindingError;
+
+/// Records a CPU profile through a local inspector session on the isolate's thread.
+pub(crate) struct CpuProfiler {
+    session: LocalInspectorSession,
+    responses: Rc<RefCell<HashMap<i32, String>>>,
+    next_id: i32,
+}
+
+impl CpuProfiler {
+    pub(crate) fn start(js_runtime: &mut JsRuntime) -> Result<Self, BindingError> {
+        let responses = Rc::new(RefCell::new(HashMap::new()));
+        let session = JsRuntimeInspector::create_local_session(
+            js_runtime.inspector(),
+            Box::new({
+                let responses = responses.clone();
+                move |message: InspectorMsg| {
+                    if let InspectorMsgKind::Message(id) = message.kind {
+                        responses.borrow_mut().insert(id, message.content);
+                    }
+                }
+            }),
+            InspectorSessionOptions {
+                kind: InspectorSessionKind::NonBlocking {
+                    wait_for_disconnect: false,
+                },
+            },
+        );
+        let mut profiler = Self {
+            session,
+            responses,
+            next_id: 0,
+        };
+        profiler.request("Profiler.enable")?;
+        profiler.request("Profiler.start")?;
+        Ok(profiler)
+    }
+
+    /// Returns the profile as the `.cpuprofile` JSON Chrome DevTools loads.
+    pub(crate) fn stop(mut self) -> Result<Vec<u8>, BindingError> {
+        let result = self.request("Profiler.stop")?;
+        self.request("Profiler.disable")?;
+        let profile = result
+            .get("profile")
+            .ok_or_else(|| BindingError::runtime("The inspector returned no CPU profile"))?;
+        serde_json::to_vec(profile).map_err(|error| {
+            BindingError::runtime(format!("Could not encode CPU profile: {error}"))
+        })
+    }
+
+    /// V8 answers protocol requests while dispatching them, so the response is ready on return.
+    fn request(&mut self, method: &str) -> Result<serde_json::Value, BindingError> {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.session.post_message::<()>(id, method, None);
+        let response = self.responses.borrow_mut().remove(&id).ok_or_else(|| {
+            BindingError::runtime(format!("The inspector did not answer {method}"))
+        })?;
+        let mut response =
+            serde_json::from_str::<serde_json::Value>(&response).map_err(|error| {
+                BindingError::runtime(format!("Could not decode the {method} response: {error}"))
+            })?;
+        if let Some(error) = response.get("error") {
+            let message = error
+                .get("message")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("unknown inspector error");
+            return Err(BindingError::runtime(format!("{method} failed: {message}")));
+        }
+        Ok(response
+            .get_mut("result")
+            .map(serde_json::Value::take)
+            .unwrap_or_default())
+    }
+}
+
+/// Serializes the isolate's heap as the `.heapsnapshot` JSON Chrome DevTools loads.
+pub(crate) fn heap_snapshot(js_runtime: &mut JsRuntime) -> Vec<u8> {
+    let mut snapshot = Vec::new();
+    js_runtime.v8_isolate().take_heap_snapshot(|chunk| {
+        snapshot.extend_from_slice(chunk);
+        true
+    });
+    snapshot
+}
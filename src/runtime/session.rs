@@ -1,4 +1,4 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
 use deno_lib::worker::LibWorkerFactoryRoots;
@@ -7,10 +7,11 @@ use crate::command::CommandSource;
 use crate::embed::init::ensure_initialized;
 use crate::runtime::bound_runtime::BoundPackageEnvironment;
 use crate::runtime::{
-    BoundRuntime, CommandExecutionHandle, CommandExecutionOptions, DenoExecutionHandle, DenoRuntime,
+    BoundRuntime, CommandExecutionHandle, CommandExecutionOptions, DenoExecutionHandle,
+    DenoRuntime, HostFunction, HostFunctions, ScriptWatch, SharedState,
 };
 use crate::script::ScriptSource;
-use crate::types::error::BindingError;
+use crate::types::{error::BindingError, value::PyJsValue};
 
 pub(crate) struct RuntimeSession {
     runtime: DenoRuntime,
@@ -18,9 +19,56 @@ pub(crate) struct RuntimeSession {
     active: AtomicBool,
     scripts: Mutex<Vec<DenoExecutionHandle>>,
     commands: Mutex<Vec<CommandExecutionHandle>>,
+    watches: Mutex<Vec<ScriptWatch>>,
+    host_functions: Mutex<HostFunctions>,
+    /// Bumped by every registration, so runners bound earlier can tell they lack a function.
+    host_generation: AtomicU64,
+    /// The idle runner `eval`, `ready`, and `iterate` borrow, kept between their calls.
+    scratch: Mutex<Option<IdleRunner>>,
     worker_factory_roots: LibWorkerFactoryRoots,
 }
 
+/// A runner borrowed from the session. `RuntimeSession::return_runner` waits for a closed
+/// runner to stop; one dropped instead, by a cancelled coroutine or an abandoned stream, hands
+/// itself back and only signals its worker.
+#[derive(Debug)]
+pub(crate) struct BorrowedRunner {
+    session: Arc<RuntimeSession>,
+    /// Taken when the runner is returned.
+    handle: Option<DenoExecutionHandle>,
+    /// The registrations it was bound with, or `None` for a runner closed once returned.
+    host_generation: Option<u64>,
+}
+
+impl BorrowedRunner {
+    pub(crate) fn handle(&self) -> &DenoExecutionHandle {
+        self.handle
+            .as_ref()
+            .expect("borrowed runner should hold its handle until returned")
+    }
+
+    /// Returns the runner to the session it was borrowed from.
+    pub(crate) fn give_back(self) -> Result<(), BindingError> {
+        let session = self.session.clone();
+        session.return_runner(self)
+    }
+}
+
+impl Drop for BorrowedRunner {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = self.session.take_back(handle, self.host_generation, false);
+        }
+    }
+}
+
+/// The scratch runner while no call borrows it.
+#[derive(Debug)]
+struct IdleRunner {
+    handle: DenoExecutionHandle,
+    host_generation: u64,
+}
+
 impl std::fmt::Debug for RuntimeSession {
     fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         formatter
@@ -48,6 +96,10 @@ impl RuntimeSession {
             active: AtomicBool::new(true),
             scripts: Mutex::new(Vec::new()),
             commands: Mutex::new(Vec::new()),
+            watches: Mutex::new(Vec::new()),
+            host_functions: Mutex::new(HostFunctions::default()),
+            host_generation: AtomicU64::new(0),
+            scratch: Mutex::new(None),
             worker_factory_roots: LibWorkerFactoryRoots::default(),
         }))
     }
@@ -59,7 +111,14 @@ impl RuntimeSession {
         session.ensure_active()?;
         let bound = session.runtime.bind(script);
         let package_environment = session.package_environment_for_script(&bound)?;
-        let bound = bound.with_package_environment(package_environment);
+        let host_functions = session
+            .host_functions
+            .lock()
+            .expect("runtime host function lock should not be poisoned")
+            .clone();
+        let bound = bound
+            .with_package_environment(package_environment)
+            .with_host_functions(host_functions);
         let handle = DenoExecutionHandle::new(bound, session.worker_factory_roots.clone());
         session
             .scripts
@@ -69,6 +128,134 @@ impl RuntimeSession {
         Ok(handle)
     }
 
+    /// Takes the idle scratch runner, or binds `script` when it is busy, has stopped, or was
+    /// bound before a host function was registered. Every caller must bind the same script.
+    pub(crate) fn take_scratch(
+        session: &Arc<Self>,
+        script: fn() -> ScriptSource,
+    ) -> Result<BorrowedRunner, BindingError> {
+        session.ensure_active()?;
+        let idle = session
+            .scratch
+            .lock()
+            .expect("runtime scratch runner lock should not be poisoned")
+            .take();
+        if let Some(idle) = idle {
+            if session.keeps(&idle.handle, idle.host_generation) {
+                return Ok(BorrowedRunner {
+                    session: session.clone(),
+                    handle: Some(idle.handle),
+                    host_generation: Some(idle.host_generation),
+                });
+            }
+            session.release_script(&idle.handle)?;
+        }
+        let host_generation = session.host_generation.load(Ordering::Acquire);
+        Ok(BorrowedRunner {
+            session: session.clone(),
+            handle: Some(Self::bind_script(session, script())?),
+            host_generation: Some(host_generation),
+        })
+    }
+
+    /// Binds `script` to a runner of its own, closed once returned, for code the scratch runner
+    /// cannot run.
+    pub(crate) fn bind_throwaway(
+        session: &Arc<Self>,
+        script: ScriptSource,
+    ) -> Result<BorrowedRunner, BindingError> {
+        Ok(BorrowedRunner {
+            session: session.clone(),
+            handle: Some(Self::bind_script(session, script)?),
+            host_generation: None,
+        })
+    }
+
+    /// Keeps a scratch runner for the next borrower, unless another is already idle, and closes
+    /// every other runner.
+    pub(crate) fn return_runner(&self, mut runner: BorrowedRunner) -> Result<(), BindingError> {
+        match runner.handle.take() {
+            Some(handle) => self.take_back(handle, runner.host_generation, true),
+            None => Ok(()),
+        }
+    }
+
+    /// Without `wait`, a runner that is not kept is only signalled to stop, so dropping one never
+    /// blocks on its worker.
+    fn take_back(
+        &self,
+        handle: DenoExecutionHandle,
+        host_generation: Option<u64>,
+        wait: bool,
+    ) -> Result<(), BindingError> {
+        if let Some(host_generation) = host_generation
+            && self.keeps(&handle, host_generation)
+        {
+            let mut idle = self
+                .scratch
+                .lock()
+                .expect("runtime scratch runner lock should not be poisoned");
+            if idle.is_none() {
+                *idle = Some(IdleRunner {
+                    handle,
+                    host_generation,
+                });
+                return Ok(());
+            }
+        }
+        if wait {
+            return self.release_script(&handle);
+        }
+        self.forget_script(&handle);
+        handle.cancel();
+        Ok(())
+    }
+
+    fn keeps(&self, handle: &DenoExecutionHandle, host_generation: u64) -> bool {
+        self.is_active()
+            && handle.is_alive()
+            && host_generation == self.host_generation.load(Ordering::Acquire)
+    }
+
+    /// Closes a runner before the session does, for callers that bind one per use.
+    pub(crate) fn release_script(&self, handle: &DenoExecutionHandle) -> Result<(), BindingError> {
+        self.forget_script(handle);
+        handle.close_blocking()
+    }
+
+    fn forget_script(&self, handle: &DenoExecutionHandle) {
+        self.scripts
+            .lock()
+            .expect("runtime script handle lock should not be poisoned")
+            .retain(|script| !script.same_worker(handle));
+    }
+
+    pub(crate) fn register_watch(&self, watch: ScriptWatch) -> Result<(), BindingError> {
+        self.ensure_active()?;
+        let mut watches = self
+            .watches
+            .lock()
+            .expect("runtime watch lock should not be poisoned");
+        watches.retain(ScriptWatch::is_alive);
+        watches.push(watch);
+        Ok(())
+    }
+
+    /// Scripts bound after registration see the function on `globalThis.host`.
+    pub(crate) fn register_host_function(
+        &self,
+        name: String,
+        function: HostFunction,
+    ) -> Result<(), BindingError> {
+        self.ensure_active()?;
+        self.host_functions
+            .lock()
+            .expect("runtime host function lock should not be poisoned")
+            .insert(name, function);
+        self.host_generation.fetch_add(1, Ordering::AcqRel);
+        Ok(())
+    }
+
     pub(crate) fn start_command(
         session: Arc<Self>,
         command: CommandSource,
@@ -108,6 +295,16 @@ impl RuntimeSession {
             return Ok(());
         }
 
+        // Only signalled: a watch thread may be waiting for the GIL this thread holds.
+        for watch in std::mem::take(
+            &mut *self
+                .watches
+                .lock()
+                .expect("runtime watch lock should not be poisoned"),
+        ) {
+            watch.stop();
+        }
+
         let commands = std::mem::take(
             &mut *self
                 .commands
@@ -127,6 +324,10 @@ impl RuntimeSession {
         for script in &scripts {
             script.cancel();
         }
+        self.scratch
+            .lock()
+            .expect("runtime scratch runner lock should not be poisoned")
+            .take();
 
         for command in commands {
             command.close_blocking()?;
@@ -141,6 +342,29 @@ impl RuntimeSession {
         Ok(())
     }
 
+    /// Terminates the call running on each of this session's runners; returns how many were
+    /// running. Queued calls still run and every runner keeps serving.
+    pub(crate) fn interrupt(&self) -> usize {
+        self.scripts
+            .lock()
+            .expect("runtime script handle lock should not be poisoned")
+            .iter()
+            .filter(|script| script.interrupt())
+            .count()
+    }
+
+    pub(crate) fn state(&self) -> &Arc<SharedState> {
+        self.runtime.state()
+    }
+
+    pub(crate) fn op_metrics(&self) -> Result<PyJsValue, BindingError> {
+        self.runtime.op_metrics_snapshot()
+    }
+
+    pub(crate) fn clear_module_cache(&self) {
+        self.runtime.module_cache().clear();
+    }
+
     pub(crate) fn is_active(&self) -> bool {
         self.active.load(Ordering::Acquire)
     }
@@ -153,7 +377,7 @@ impl RuntimeSession {
         if self.is_active() {
             Ok(())
         } else {
-            Err(BindingError::runtime("Runtime session is closed"))
+            Err(BindingError::closed("Runtime session is closed"))
         }
     }
 
@@ -0,0 +1,134 @@
+use std::{collections::BTreeSet, sync::Mutex};
+
+use aws_lc_rs::digest;
+use deno_core::{JsRuntimeForSnapshot, RuntimeOptions, v8};
+use deno_runtime::tokio_util::create_and_run_current_thread;
+
+use crate::{
+    embed::init::spawn_v8_worker, runtime::error::map_js_error, types::error::BindingError,
+};
+
+/// Marks bytes from `create`; anything else is rejected before V8, which aborts the process on
+/// a blob it cannot read, ever sees it.
+const MAGIC: &[u8] = b"belgie-snapshot\n";
+
+/// A snapshot only loads into the V8 build that wrote it.
+fn build_tag() -> String {
+    format!(
+        "belgie {} v8 {}",
+        env!("CARGO_PKG_VERSION"),
+        v8::V8::get_version()
+    )
+}
+
+/// Runs `setup` as a classic script on a bare isolate and snapshots its heap, prefixed with the
+/// build that made it and a checksum of the V8 blob.
+pub(crate) fn create(setup: String) -> Result<Vec<u8>, BindingError> {
+    spawn_v8_worker("belgie-snapshot".to_string(), move || {
+        create_and_run_current_thread(async move {
+            let mut runtime = JsRuntimeForSnapshot::new(RuntimeOptions::default());
+            runtime
+                .execute_script("belgie:snapshot", setup)
+                .map_err(|error| map_js_error(&error))?;
+            Ok(encode(&runtime.snapshot()))
+        })
+    })
+    .join()
+    .map_err(|_| BindingError::runtime("Snapshot creation panicked"))?
+}
+
+fn encode(blob: &[u8]) -> Vec<u8> {
+    let tag = build_tag();
+    let tag_len = u32::try_from(tag.len()).expect("build tag should fit in a u32");
+    let mut bytes = Vec::with_capacity(MAGIC.len() + 4 + tag.len() + 32 + blob.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&tag_len.to_le_bytes());
+    bytes.extend_from_slice(tag.as_bytes());
+    bytes.extend_from_slice(digest::digest(&digest::SHA256, blob).as_ref());
+    bytes.extend_from_slice(blob);
+    bytes
+}
+
+/// Checks bytes from `create` and returns the V8 blob, which isolates borrow for as long as they
+/// live, so each distinct blob is kept for the life of the process.
+pub(crate) fn load(bytes: &[u8]) -> Result<&'static [u8], BindingError> {
+    static LOADED: Mutex<BTreeSet<&'static [u8]>> = Mutex::new(BTreeSet::new());
+
+    let blob = decode(bytes)?;
+    let mut loaded = LOADED
+        .lock()
+        .expect("loaded snapshot lock should not be poisoned");
+    if let Some(blob) = loaded.get(blob) {
+        return Ok(*blob);
+    }
+    let blob: &'static [u8] = Box::leak(blob.into());
+    loaded.insert(blob);
+    Ok(blob)
+}
+
+fn decode(bytes: &[u8]) -> Result<&[u8], BindingError> {
+    let invalid =
+        || BindingError::value_conversion("snapshot must be bytes from Runtime.create_snapshot()");
+    let rest = bytes.strip_prefix(MAGIC).ok_or_else(invalid)?;
+    let (tag_len, rest) = rest.split_first_chunk::<4>().ok_or_else(invalid)?;
+    let tag_len = u32::from_le_bytes(*tag_len) as usize;
+    if rest.len() < tag_len {
+        return Err(invalid());
+    }
+    let (tag, rest) = rest.split_at(tag_len);
+    let expected = build_tag();
+    if tag != expected.as_bytes() {
+        return Err(BindingError::value_conversion(format!(
+            "snapshot was created by {}; this build is {expected}",
+            String::from_utf8_lossy(tag)
+        )));
+    }
+    let (checksum, blob) = rest.split_first_chunk::<32>().ok_or_else(invalid)?;
+    if digest::digest(&digest::SHA256, blob).as_ref() != checksum {
+        return Err(BindingError::value_conversion(
+            "snapshot is corrupted: its checksum does not match",
+        ));
+    }
+    Ok(blob)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_blobs_and_rejects_other_bytes() {
+        let bytes = encode(b"v8 blob");
+
+        assert_eq!(decode(&bytes).expect("snapshot should decode"), b"v8 blob");
+        assert!(decode(b"not a snapshot").is_err());
+        assert!(decode(&bytes[..MAGIC.len() + 2]).is_err());
+
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().expect("snapshot should not be empty") ^= 1;
+        assert!(
+            decode(&corrupted)
+                .expect_err("corrupted blobs should be rejected")
+                .message()
+                .contains("checksum")
+        );
+
+        let mut other_build = bytes;
+        other_build[MAGIC.len() + 4] = b'B';
+        assert!(
+            decode(&other_build)
+                .expect_err("snapshots from other builds should be rejected")
+                .message()
+                .contains("this build is")
+        );
+    }
+
+    #[test]
+    fn load_shares_one_copy_of_each_blob() {
+        let bytes = encode(b"shared blob");
+
+        let first = load(&bytes).expect("snapshot should load");
+        let second = load(&bytes).expect("snapshot should load again");
+        assert!(std::ptr::eq(first, second));
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use deno_core::v8;
+
+use crate::types::{error::BindingError, value::PyJsValue};
+
+const STATE_GLOBAL: &str = "state";
+
+thread_local! {
+    static WORKER_STATE: RefCell<Arc<SharedState>> = RefCell::new(Arc::default());
+}
+
+/// Values shared by Python and every runner of one runtime. Reads and writes copy whole values
+/// across the JSON boundary, so nested objects are snapshots rather than live references.
+#[derive(Debug, Default)]
+pub(crate) struct SharedState {
+    values: Mutex<BTreeMap<String, PyJsValue>>,
+}
+
+impl SharedState {
+    pub(crate) fn get(&self, key: &str) -> Option<PyJsValue> {
+        self.values().get(key).cloned()
+    }
+
+    pub(crate) fn set(&self, key: String, value: PyJsValue) {
+        self.values().insert(key, value);
+    }
+
+    pub(crate) fn remove(&self, key: &str) -> Option<PyJsValue> {
+        self.values().remove(key)
+    }
+
+    pub(crate) fn contains(&self, key: &str) -> bool {
+        self.values().contains_key(key)
+    }
+
+    pub(crate) fn keys(&self) -> Vec<String> {
+        self.values().keys().cloned().collect()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.values().len()
+    }
+
+    pub(crate) fn clear(&self) {
+        self.values().clear();
+    }
+
+    fn values(&self) -> MutexGuard<'_, BTreeMap<String, PyJsValue>> {
+        self.values
+            .lock()
+            .expect("runtime state lock should not be poisoned")
+    }
+}
+
+/// Backs `globalThis.state` on the current worker thread with `state`.
+pub(crate) fn set_worker_state(state: Arc<SharedState>) {
+    WORKER_STATE.with(|current| *current.borrow_mut() = state);
+}
+
+/// Installs `globalThis.state` as a proxy whose properties read and write the worker's state.
+pub(crate) fn install_state_global(scope: &mut v8::PinScope<'_, '_>) -> Result<(), BindingError> {
+    let handler = v8::Object::new(scope);
+    install_trap(scope, handler, "get", get_trap)?;
+    install_trap(scope, handler, "set", set_trap)?;
+    install_trap(scope, handler, "has", has_trap)?;
+    install_trap(scope, handler, "deleteProperty", delete_trap)?;
+    install_trap(scope, handler, "ownKeys", own_keys_trap)?;
+    install_trap(scope, handler, "getOwnPropertyDescriptor", descriptor_trap)?;
+    let target = v8::Object::new(scope);
+    let state = v8::Proxy::new(scope, target, handler)
+        .ok_or_else(|| BindingError::runtime("Could not create runtime state"))?;
+
+    let key = v8::String::new(scope, STATE_GLOBAL)
+        .ok_or_else(|| BindingError::runtime("Could not create runtime state key"))?;
+    let global = scope.get_current_context().global(scope);
+    if !global
+        .define_own_property(
+            scope,
+            key.into(),
+            state.into(),
+            v8::PropertyAttribute::READ_ONLY | v8::PropertyAttribute::DONT_DELETE,
+        )
+        .unwrap_or(false)
+    {
+        return Err(BindingError::runtime("Could not install runtime state"));
+    }
+    Ok(())
+}
+
+fn install_trap<'s>(
+    scope: &mut v8::PinScope<'s, '_>,
+    handler: v8::Local<'s, v8::Object>,
+    name: &str,
+    callback: impl v8::MapFnTo<v8::FunctionCallback>,
+) -> Result<(), BindingError> {
+    let key = v8::String::new(scope, name)
+        .ok_or_else(|| BindingError::runtime("Could not create runtime state trap name"))?;
+    let trap = v8::Function::new(scope, callback).ok_or_else(|| {
+        BindingError::runtime(format!("Could not create runtime state trap {name}"))
+    })?;
+    if handler.set(scope, key.into(), trap.into()).unwrap_or(false) {
+        Ok(())
+    } else {
+        Err(BindingError::runtime(format!(
+            "Could not install runtime state trap {name}",
+        )))
+    }
+}
+
+fn worker_state() -> Arc<SharedState> {
+    WORKER_STATE.with(|state| state.borrow().clone())
+}
+
+/// Symbol keys (such as `Symbol.toPrimitive`) never name stored values.
+fn property_key(scope: &mut v8::PinScope, args: &v8::FunctionCallbackArguments) -> Option<String> {
+    let key = args.get(1);
+    (!key.is_symbol()).then(|| key.to_rust_string_lossy(scope))
+}
+
+fn get_trap(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let Some(value) = property_key(scope, &args).and_then(|key| worker_state().get(&key)) else {
+        return;
+    };
+    match value.to_v8(scope) {
+        Ok(value) => return_value.set(value),
+        Err(error) => throw_type_error(scope, &error.message()),
+    }
+}
+
+fn set_trap(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let Some(key) = property_key(scope, &args) else {
+        throw_type_error(scope, "state keys must be strings");
+        return;
+    };
+    match PyJsValue::from_v8(scope, args.get(2)) {
+        Ok(value) => {
+            worker_state().set(key, value);
+            return_value.set(v8::Boolean::new(scope, true).into());
+        }
+        Err(error) => throw_type_error(
+            scope,
+            &format!("state.{key} must be JSON-serializable: {}", error.message()),
+        ),
+    }
+}
+
+fn has_trap(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let found = property_key(scope, &args).is_some_and(|key| worker_state().contains(&key));
+    return_value.set(v8::Boolean::new(scope, found).into());
+}
+
+fn delete_trap(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    if let Some(key) = property_key(scope, &args) {
+        worker_state().remove(&key);
+    }
+    return_value.set(v8::Boolean::new(scope, true).into());
+}
+
+fn own_keys_trap(
+    scope: &mut v8::PinScope,
+    _args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let keys = worker_state()
+        .keys()
+        .iter()
+        .filter_map(|key| v8::String::new(scope, key).map(Into::into))
+        .collect::<Vec<v8::Local<v8::Value>>>();
+    return_value.set(v8::Array::new_with_elements(scope, &keys).into());
+}
+
+/// Reports stored values as plain writable data properties so `Object.keys`, spreading, and
+/// `JSON.stringify` see them.
+fn descriptor_trap(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let Some(value) = property_key(scope, &args).and_then(|key| worker_state().get(&key)) else {
+        return;
+    };
+    let value = match value.to_v8(scope) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_type_error(scope, &error.message());
+            return;
+        }
+    };
+    let descriptor = v8::Object::new(scope);
+    let enabled = v8::Boolean::new(scope, true).into();
+    for (name, field) in [
+        ("value", value),
+        ("writable", enabled),
+        ("enumerable", enabled),
+        ("configurable", enabled),
+    ] {
+        let Some(name) = v8::String::new(scope, name) else {
+            return;
+        };
+        let _ = descriptor.set(scope, name.into(), field);
+    }
+    return_value.set(descriptor.into());
+}
+
+fn throw_type_error(scope: &mut v8::PinScope, message: &str) {
+    let message = v8::String::new(scope, message).unwrap_or_else(|| v8::String::empty(scope));
+    let exception = v8::Exception::type_error(scope, message);
+    scope.throw_exception(exception);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::value::PyJsValue;
+    use deno_core::serde_json::json;
+
+    use super::SharedState;
+
+    #[test]
+    fn stores_values_in_key_order() {
+        let state = SharedState::default();
+        state.set("user".to_string(), PyJsValue::from_json(json!("alice")));
+        state.set("count".to_string(), PyJsValue::from_json(json!(1)));
+
+        assert_eq!(state.keys(), ["count", "user"]);
+        assert_eq!(
+            state.get("user"),
+            Some(PyJsValue::from_json(json!("alice")))
+        );
+        assert_eq!(state.remove("count"), Some(PyJsValue::from_json(json!(1))));
+        assert!(!state.contains("count"));
+        assert_eq!(state.len(), 1);
+    }
+}
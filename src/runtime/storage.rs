@@ -0,0 +1,258 @@
+use std::{cell::RefCell, sync::Arc};
+
+use deno_core::{JsRuntime, v8};
+use pyo3::{
+    Bound, Py, PyAny, PyResult, Python,
+    exceptions::PyTypeError,
+    types::{PyAnyMethods, PyString, PyStringMethods},
+};
+
+use crate::runtime::native_error::throw_native_error;
+use crate::types::error::BindingError;
+
+/// `localStorage` and `sessionStorage` in the shape scripts written for browsers expect. Keys and
+/// values are coerced to strings before they reach Python, matching the Web Storage API.
+/// `sessionStorage` lives in the isolate, so like a browser tab's it starts empty in every runner
+/// and is dropped by `reset()`.
+const STORAGE_SOURCE: &str = r#"((native) => {
+  class Storage {
+    #backend;
+    constructor(backend) {
+      this.#backend = backend;
+    }
+    get length() {
+      return this.#backend.keys().length;
+    }
+    key(index) {
+      return this.#backend.keys()[index] ?? null;
+    }
+    getItem(key) {
+      return this.#backend.get(String(key));
+    }
+    setItem(key, value) {
+      this.#backend.set(String(key), String(value));
+    }
+    removeItem(key) {
+      this.#backend.remove(String(key));
+    }
+    clear() {
+      this.#backend.clear();
+    }
+  }
+  const session = new Map();
+  const sessionBackend = {
+    get: (key) => session.get(key) ?? null,
+    set: (key, value) => void session.set(key, value),
+    remove: (key) => void session.delete(key),
+    clear: () => session.clear(),
+    keys: () => [...session.keys()],
+  };
+  for (const [name, backend] of [["localStorage", native], ["sessionStorage", sessionBackend]]) {
+    Object.defineProperty(globalThis, name, {
+      value: new Storage(backend),
+      enumerable: true,
+      configurable: true,
+    });
+  }
+})"#;
+
+thread_local! {
+    static WORKER_STORAGE: RefCell<Option<Arc<WebStorage>>> = const { RefCell::new(None) };
+}
+
+/// A Python mapping of strings that backs `localStorage` in every runner of one runtime.
+#[derive(Debug)]
+pub(crate) struct WebStorage {
+    items: Py<PyAny>,
+}
+
+impl WebStorage {
+    pub(crate) fn new(items: Py<PyAny>) -> Self {
+        Self { items }
+    }
+
+    fn get(&self, key: &str) -> PyResult<Option<String>> {
+        Python::attach(|py| {
+            let value = self.items.bind(py).call_method1("get", (key,))?;
+            if value.is_none() {
+                return Ok(None);
+            }
+            storage_string(&value, "values").map(Some)
+        })
+    }
+
+    fn set(&self, key: &str, value: &str) -> PyResult<()> {
+        Python::attach(|py| self.items.bind(py).set_item(key, value))
+    }
+
+    fn remove(&self, key: &str) -> PyResult<()> {
+        Python::attach(|py| {
+            self.items
+                .bind(py)
+                .call_method1("pop", (key, py.None()))
+                .map(drop)
+        })
+    }
+
+    fn clear(&self) -> PyResult<()> {
+        Python::attach(|py| self.items.bind(py).call_method0("clear").map(drop))
+    }
+
+    fn keys(&self) -> PyResult<Vec<String>> {
+        Python::attach(|py| {
+            self.items
+                .bind(py)
+                .try_iter()?
+                .map(|key| storage_string(&key?, "keys"))
+                .collect()
+        })
+    }
+}
+
+/// Reads a key or value from the mapping, which may hold anything Python put there.
+fn storage_string(item: &Bound<'_, PyAny>, kind: &str) -> PyResult<String> {
+    let string = item.cast::<PyString>().map_err(|_| {
+        PyTypeError::new_err(format!("localStorage {kind} must be strings, not {item:?}"))
+    })?;
+    Ok(string.to_str()?.to_string())
+}
+
+/// Backs `localStorage` on the current worker thread with `storage`.
+pub(crate) fn set_worker_storage(storage: Option<Arc<WebStorage>>) {
+    WORKER_STORAGE.with(|current| *current.borrow_mut() = storage);
+}
+
+pub(crate) fn install_storage_global(runtime: &mut JsRuntime) -> Result<(), BindingError> {
+    let installer = runtime
+        .execute_script("belgie:storage", STORAGE_SOURCE)
+        .map_err(|error| {
+            BindingError::runtime(format!("Could not install localStorage: {error}"))
+        })?;
+    deno_core::scope!(scope, runtime);
+    let installer = v8::Local::<v8::Function>::try_from(v8::Local::new(scope, installer))
+        .map_err(|_| BindingError::runtime("Could not install localStorage"))?;
+    let native = v8::Object::new(scope);
+    install_binding(scope, native, "get", get_callback)?;
+    install_binding(scope, native, "set", set_callback)?;
+    install_binding(scope, native, "remove", remove_callback)?;
+    install_binding(scope, native, "clear", clear_callback)?;
+    install_binding(scope, native, "keys", keys_callback)?;
+    let receiver = v8::undefined(scope).into();
+    installer
+        .call(scope, receiver, &[native.into()])
+        .ok_or_else(|| BindingError::runtime("Could not install localStorage"))?;
+    Ok(())
+}
+
+fn install_binding<'s>(
+    scope: &mut v8::PinScope<'s, '_>,
+    native: v8::Local<'s, v8::Object>,
+    name: &str,
+    callback: impl v8::MapFnTo<v8::FunctionCallback>,
+) -> Result<(), BindingError> {
+    let key = v8::String::new(scope, name)
+        .ok_or_else(|| BindingError::runtime("Could not create localStorage binding name"))?;
+    let function = v8::Function::new(scope, callback).ok_or_else(|| {
+        BindingError::runtime(format!("Could not create localStorage binding {name}"))
+    })?;
+    if native
+        .set(scope, key.into(), function.into())
+        .unwrap_or(false)
+    {
+        Ok(())
+    } else {
+        Err(BindingError::runtime(format!(
+            "Could not install localStorage binding {name}",
+        )))
+    }
+}
+
+/// Runs `operation` against the worker's storage, throwing its Python error into JavaScript.
+fn with_storage<T>(
+    scope: &mut v8::PinScope,
+    operation: &str,
+    run: impl FnOnce(&WebStorage) -> PyResult<T>,
+) -> Option<T> {
+    let origin = format!("localStorage.{operation}");
+    let Some(storage) = WORKER_STORAGE.with(|storage| storage.borrow().clone()) else {
+        throw_native_error(
+            scope,
+            &origin,
+            "localStorage is not available on this worker",
+        );
+        return None;
+    };
+    match run(&storage) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            throw_native_error(scope, &origin, &format!("{origin} failed: {error}"));
+            None
+        }
+    }
+}
+
+fn string_argument(
+    scope: &mut v8::PinScope,
+    args: &v8::FunctionCallbackArguments,
+    index: i32,
+) -> String {
+    args.get(index).to_rust_string_lossy(scope)
+}
+
+fn get_callback(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let key = string_argument(scope, &args, 0);
+    match with_storage(scope, "getItem", |storage| storage.get(&key)) {
+        Some(Some(value)) => {
+            let value = v8::String::new(scope, &value).unwrap_or_else(|| v8::String::empty(scope));
+            return_value.set(value.into());
+        }
+        Some(None) => return_value.set_null(),
+        None => {}
+    }
+}
+
+fn set_callback(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    _return_value: v8::ReturnValue,
+) {
+    let key = string_argument(scope, &args, 0);
+    let value = string_argument(scope, &args, 1);
+    with_storage(scope, "setItem", |storage| storage.set(&key, &value));
+}
+
+fn remove_callback(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    _return_value: v8::ReturnValue,
+) {
+    let key = string_argument(scope, &args, 0);
+    with_storage(scope, "removeItem", |storage| storage.remove(&key));
+}
+
+fn clear_callback(
+    scope: &mut v8::PinScope,
+    _args: v8::FunctionCallbackArguments,
+    _return_value: v8::ReturnValue,
+) {
+    with_storage(scope, "clear", WebStorage::clear);
+}
+
+fn keys_callback(
+    scope: &mut v8::PinScope,
+    _args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let Some(keys) = with_storage(scope, "key", WebStorage::keys) else {
+        return;
+    };
+    let keys = keys
+        .iter()
+        .filter_map(|key| v8::String::new(scope, key).map(Into::into))
+        .collect::<Vec<v8::Local<v8::Value>>>();
+    return_value.set(v8::Array::new_with_elements(scope, &keys).into());
+}
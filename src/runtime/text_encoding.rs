@@ -0,0 +1,172 @@
+use deno_core::JsRuntime;
+
+use crate::types::error::BindingError;
+
+/// UTF-8 `TextEncoder`/`TextDecoder` for the lightweight backend, which has no `deno_web`.
+///
+/// Evaluates to a function that defines both classes on its target.
+const TEXT_ENCODING_SOURCE: &str = r#"((target) => {
+  const REPLACEMENT = 0xfffd;
+  const LABELS = new Set(["utf-8", "utf8", "unicode-1-1-utf-8"]);
+
+  class TextEncoder {
+    get encoding() {
+      return "utf-8";
+    }
+
+    encode(input = "") {
+      const bytes = [];
+      for (const character of String(input)) {
+        let code = character.codePointAt(0);
+        if (code >= 0xd800 && code <= 0xdfff) {
+          code = REPLACEMENT;
+        }
+        if (code < 0x80) {
+          bytes.push(code);
+        } else if (code < 0x800) {
+          bytes.push(0xc0 | (code >> 6), 0x80 | (code & 0x3f));
+        } else if (code < 0x10000) {
+          bytes.push(0xe0 | (code >> 12), 0x80 | ((code >> 6) & 0x3f), 0x80 | (code & 0x3f));
+        } else {
+          bytes.push(
+            0xf0 | (code >> 18),
+            0x80 | ((code >> 12) & 0x3f),
+            0x80 | ((code >> 6) & 0x3f),
+            0x80 | (code & 0x3f),
+          );
+        }
+      }
+      return new Uint8Array(bytes);
+    }
+  }
+
+  class TextDecoder {
+    #fatal;
+    #ignoreBOM;
+
+    constructor(label = "utf-8", options = {}) {
+      if (!LABELS.has(String(label).trim().toLowerCase())) {
+        throw new RangeError(`The encoding label provided ('${label}') is invalid.`);
+      }
+      this.#fatal = Boolean(options.fatal);
+      this.#ignoreBOM = Boolean(options.ignoreBOM);
+    }
+
+    get encoding() {
+      return "utf-8";
+    }
+
+    get fatal() {
+      return this.#fatal;
+    }
+
+    get ignoreBOM() {
+      return this.#ignoreBOM;
+    }
+
+    decode(input = new Uint8Array()) {
+      const bytes = ArrayBuffer.isView(input)
+        ? new Uint8Array(input.buffer, input.byteOffset, input.byteLength)
+        : new Uint8Array(input);
+      const codes = [];
+      let index = 0;
+      if (!this.#ignoreBOM && bytes[0] === 0xef && bytes[1] === 0xbb && bytes[2] === 0xbf) {
+        index = 3;
+      }
+      while (index < bytes.length) {
+        const lead = bytes[index];
+        const [length, minimum] =
+          lead < 0x80 ? [1, 0]
+          : lead < 0xc0 ? [0, 0]
+          : lead < 0xe0 ? [2, 0x80]
+          : lead < 0xf0 ? [3, 0x800]
+          : lead < 0xf8 ? [4, 0x10000]
+          : [0, 0];
+        let code = length === 1 ? lead : lead & (0x7f >> length);
+        let valid = length > 0 && index + length <= bytes.length;
+        for (let offset = 1; valid && offset < length; offset += 1) {
+          const next = bytes[index + offset];
+          valid = (next & 0xc0) === 0x80;
+          code = (code << 6) | (next & 0x3f);
+        }
+        valid &&= code >= minimum && code <= 0x10ffff && !(code >= 0xd800 && code <= 0xdfff);
+        if (!valid) {
+          if (this.#fatal) {
+            throw new TypeError("The encoded data was not valid.");
+          }
+          codes.push(REPLACEMENT);
+          index += 1;
+          continue;
+        }
+        codes.push(code);
+        index += length;
+      }
+      let result = "";
+      for (let start = 0; start < codes.length; start += 8192) {
+        result += String.fromCodePoint(...codes.slice(start, start + 8192));
+      }
+      return result;
+    }
+  }
+
+  for (const [name, value] of [["TextEncoder", TextEncoder], ["TextDecoder", TextDecoder]]) {
+    Object.defineProperty(target, name, { value, writable: true, configurable: true });
+  }
+})"#;
+
+/// Leaves a runtime's own encoders, such as the Deno worker's, in place.
+pub(crate) fn install_text_encoding(runtime: &mut JsRuntime) -> Result<(), BindingError> {
+    let source = format!(
+        r#"if (typeof globalThis.TextEncoder !== "function" || typeof globalThis.TextDecoder !== "function") {{
+  ({TEXT_ENCODING_SOURCE})(globalThis);
+}}"#
+    );
+    runtime
+        .execute_script("belgie:text_encoding", source)
+        .map(drop)
+        .map_err(|error| {
+            BindingError::runtime(format!("Could not install text encoding globals: {error}"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use deno_core::v8;
+
+    use super::*;
+    use crate::runtime::with_test_js_runtime;
+
+    /// Runs `body` against the polyfill's own classes, even where the runtime brings its own.
+    fn eval_with_polyfill(body: &'static str) -> String {
+        with_test_js_runtime(move |runtime| {
+            let source = format!(
+                "(() => {{ const polyfill = {{}}; ({TEXT_ENCODING_SOURCE})(polyfill); \
+                 const {{ TextEncoder, TextDecoder }} = polyfill; {body} }})()"
+            );
+            let value = runtime
+                .execute_script("belgie:test", source)
+                .expect("round trip should evaluate");
+            deno_core::scope!(scope, runtime);
+            v8::Local::new(scope, value).to_rust_string_lossy(scope)
+        })
+    }
+
+    #[test]
+    fn round_trips_multi_byte_text() {
+        let result = eval_with_polyfill(
+            r#"
+            const bytes = new TextEncoder().encode("héllo \u{1f600}");
+            return JSON.stringify([
+              Array.from(bytes),
+              new TextDecoder().decode(bytes),
+              TextEncoder === globalThis.TextEncoder,
+            ]);
+            "#,
+        );
+
+        assert_eq!(
+            result,
+            r#"[[104,195,169,108,108,111,32,240,159,152,128],"héllo 😀",false]"#
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex, Weak,
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::runtime::RuntimeSession;
+use crate::script::ScriptSource;
+use crate::types::{error::BindingError, runner::RunnerArguments, value::PyJsValue};
+
+/// Editors often save in several writes; changes this close together reload once.
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+pub(crate) type WatchCallback = Box<dyn Fn(Result<PyJsValue, BindingError>) + Send>;
+
+/// Runs a file script once, then again in a fresh runner each time the file changes.
+#[derive(Clone)]
+pub(crate) struct ScriptWatch {
+    inner: Arc<ScriptWatchInner>,
+}
+
+struct ScriptWatchInner {
+    path: PathBuf,
+    events: mpsc::Sender<WatchEvent>,
+    stopped: Arc<AtomicBool>,
+    /// Dropped on stop, which ends the OS-level watch.
+    watcher: Mutex<Option<RecommendedWatcher>>,
+    join_handle: Mutex<Option<thread::JoinHandle<()>>>,
+}
+
+enum WatchEvent {
+    Changed,
+    Stop,
+}
+
+impl std::fmt::Debug for ScriptWatch {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("ScriptWatch")
+            .field("path", &self.inner.path)
+            .field("alive", &self.is_alive())
+            .finish()
+    }
+}
+
+impl ScriptWatch {
+    /// Watches the script's directory rather than the file, so saves that replace the file
+    /// through a rename keep being seen.
+    pub(crate) fn start(
+        session: &Arc<RuntimeSession>,
+        script: ScriptSource,
+        arguments: RunnerArguments,
+        on_result: WatchCallback,
+    ) -> Result<Self, BindingError> {
+        let path = script
+            .filename()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| BindingError::runtime("Only Script.from_file scripts can be watched"))?;
+        let directory = path
+            .parent()
+            .ok_or_else(|| BindingError::runtime("Watched script has no parent directory"))?;
+        let watch_error = |error: notify::Error| {
+            BindingError::runtime(format!("Could not watch {}: {error}", path.display()))
+        };
+        let (events, receiver) = mpsc::channel();
+        let changes = events.clone();
+        let file_name: Option<OsString> = path.file_name().map(OsString::from);
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let Ok(event) = event else {
+                    return;
+                };
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                    && event
+                        .paths
+                        .iter()
+                        .any(|changed| changed.file_name() == file_name.as_deref())
+                {
+                    let _ = changes.send(WatchEvent::Changed);
+                }
+            })
+            .map_err(watch_error)?;
+        watcher
+            .watch(directory, RecursiveMode::NonRecursive)
+            .map_err(watch_error)?;
+
+        let weak_session = Arc::downgrade(session);
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_stopped = stopped.clone();
+        let join_handle = thread::Builder::new()
+            .name("belgie-watch".to_string())
+            .spawn(move || {
+                watch_loop(
+                    weak_session,
+                    script,
+                    arguments,
+                    receiver,
+                    thread_stopped,
+                    on_result,
+                )
+            })
+            .map_err(|error| {
+                BindingError::runtime(format!("Could not start the watch thread: {error}"))
+            })?;
+        let watch = Self {
+            inner: Arc::new(ScriptWatchInner {
+                path,
+                events,
+                stopped,
+                watcher: Mutex::new(Some(watcher)),
+                join_handle: Mutex::new(Some(join_handle)),
+            }),
+        };
+        if let Err(error) = session.register_watch(watch.clone()) {
+            watch.close_blocking();
+            return Err(error);
+        }
+        Ok(watch)
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    pub(crate) fn is_alive(&self) -> bool {
+        self.inner
+            .join_handle
+            .lock()
+            .expect("watch join handle lock should not be poisoned")
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// Stops watching without waiting; a run already in progress finishes on its own.
+    pub(crate) fn stop(&self) {
+        self.inner.stopped.store(true, Ordering::Release);
+        self.inner
+            .watcher
+            .lock()
+            .expect("watch lock should not be poisoned")
+            .take();
+        let _ = self.inner.events.send(WatchEvent::Stop);
+    }
+
+    /// Stops watching and waits for the watch thread, unless called from that thread.
+    pub(crate) fn close_blocking(&self) {
+        self.stop();
+        let join_handle = self
+            .inner
+            .join_handle
+            .lock()
+            .expect("watch join handle lock should not be poisoned")
+            .take();
+        if let Some(join_handle) = join_handle
+            && join_handle.thread().id() != thread::current().id()
+        {
+            let _ = join_handle.join();
+        }
+    }
+}
+
+fn watch_loop(
+    session: Weak<RuntimeSession>,
+    script: ScriptSource,
+    arguments: RunnerArguments,
+    receiver: mpsc::Receiver<WatchEvent>,
+    stopped: Arc<AtomicBool>,
+    on_result: WatchCallback,
+) {
+    // The first run uses the script as bound; later runs read the file again.
+    let mut source = Ok(script.clone());
+    loop {
+        let Some(active) = session.upgrade().filter(|session| session.is_active()) else {
+            return;
+        };
+        let result = source.and_then(|source| run_once(&active, source, arguments.clone()));
+        drop(active);
+        if stopped.load(Ordering::Acquire) {
+            return;
+        }
+        on_result(result);
+        if !matches!(receiver.recv(), Ok(WatchEvent::Changed)) || !settle(&receiver) {
+            return;
+        }
+        source = script.reloaded();
+    }
+}
+
+/// Waits until no change has arrived for `DEBOUNCE`; `false` once the watch is stopped.
+fn settle(receiver: &mpsc::Receiver<WatchEvent>) -> bool {
+    loop {
+        match receiver.recv_timeout(DEBOUNCE) {
+            Ok(WatchEvent::Changed) => {}
+            Err(mpsc::RecvTimeoutError::Timeout) => return true,
+            Ok(WatchEvent::Stop) | Err(mpsc::RecvTimeoutError::Disconnected) => return false,
+        }
+    }
+}
+
+fn run_once(
+    session: &Arc<RuntimeSession>,
+    source: ScriptSource,
+    arguments: RunnerArguments,
+) -> Result<PyJsValue, BindingError> {
+    // Imported files are not watched, but edits to them still show up on the next reload.
+    session.clear_module_cache();
+    let handle = RuntimeSession::bind_script(session, source)?;
+    let result = handle.invoke_blocking(arguments);
+    session.release_script(&handle)?;
+    result
+}
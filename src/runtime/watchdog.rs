@@ -0,0 +1,159 @@
+use std::{
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread,
+    time::{Duration, Instant},
+};
+
+use deno_core::v8;
+
+use crate::runtime::cpu_budget::CpuBudget;
+
+/// Terminates a worker's call once its deadline passes or it spends its CPU budget. The thread
+/// starts on the first armed call and then serves every later call of that worker, which arms
+/// and disarms it.
+pub(crate) struct CallWatchdog {
+    isolate_handle: v8::IsolateHandle,
+    shared: Arc<Shared>,
+    join_handle: Option<thread::JoinHandle<()>>,
+}
+
+/// The limit that stopped a call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Expiry {
+    Timeout,
+    CpuBudget,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct State {
+    armed: Option<Armed>,
+    /// Set when the armed call was terminated; cleared when the next call is armed.
+    fired: Option<Expiry>,
+    shutdown: bool,
+}
+
+struct Armed {
+    isolate_handle: v8::IsolateHandle,
+    deadline: Option<Instant>,
+    cpu_budget: Option<CpuBudget>,
+}
+
+impl Armed {
+    fn expired(&self, now: Instant) -> Option<Expiry> {
+        if self.deadline.is_some_and(|deadline| now >= deadline) {
+            Some(Expiry::Timeout)
+        } else if self.cpu_budget.as_ref().is_some_and(CpuBudget::exhausted) {
+            Some(Expiry::CpuBudget)
+        } else {
+            None
+        }
+    }
+
+    /// How long the watchdog may sleep before either limit could pass.
+    fn wait(&self, now: Instant) -> Duration {
+        let until_deadline = self.deadline.map(|deadline| deadline - now);
+        let until_sample = self.cpu_budget.as_ref().map(CpuBudget::next_sample);
+        until_deadline
+            .into_iter()
+            .chain(until_sample)
+            .min()
+            .unwrap_or(Duration::MAX)
+    }
+}
+
+impl CallWatchdog {
+    pub(crate) fn new(isolate_handle: v8::IsolateHandle) -> Self {
+        Self {
+            isolate_handle,
+            shared: Arc::default(),
+            join_handle: None,
+        }
+    }
+
+    /// Arms the watchdog for one call. Must run on the worker thread, whose CPU clock the budget
+    /// samples; with neither limit, or a budget this platform cannot measure, it stays idle.
+    pub(crate) fn arm(&mut self, timeout: Option<Duration>, cpu_budget: Option<Duration>) {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let cpu_budget = cpu_budget.and_then(CpuBudget::start);
+        if deadline.is_none() && cpu_budget.is_none() {
+            return;
+        }
+        if self.join_handle.is_none() {
+            let shared = self.shared.clone();
+            self.join_handle = thread::Builder::new()
+                .name("belgie-watchdog".to_string())
+                .spawn(move || watch(&shared))
+                .ok();
+        }
+        let mut state = self.lock();
+        state.armed = Some(Armed {
+            isolate_handle: self.isolate_handle.clone(),
+            deadline,
+            cpu_budget,
+        });
+        state.fired = None;
+        drop(state);
+        self.shared.changed.notify_one();
+    }
+
+    /// Disarms the watchdog and reports which limit terminated the call, if any.
+    pub(crate) fn disarm(&mut self) -> Option<Expiry> {
+        let mut state = self.lock();
+        state.armed = None;
+        state.fired.take()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.shared
+            .state
+            .lock()
+            .expect("watchdog state lock should not be poisoned")
+    }
+}
+
+impl Drop for CallWatchdog {
+    fn drop(&mut self) {
+        self.lock().shutdown = true;
+        self.shared.changed.notify_one();
+        if let Some(join_handle) = self.join_handle.take() {
+            let _ = join_handle.join();
+        }
+    }
+}
+
+/// Terminating under the lock means `disarm` either sees `fired` or runs before the limits
+/// are checked, so a call that already finished is never reported as stopped.
+fn watch(shared: &Shared) {
+    let mut state = shared
+        .state
+        .lock()
+        .expect("watchdog state lock should not be poisoned");
+    while !state.shutdown {
+        let Some(armed) = &state.armed else {
+            state = shared
+                .changed
+                .wait(state)
+                .expect("watchdog state lock should not be poisoned");
+            continue;
+        };
+        let now = Instant::now();
+        if let Some(expiry) = armed.expired(now) {
+            armed.isolate_handle.terminate_execution();
+            state.armed = None;
+            state.fired = Some(expiry);
+            continue;
+        }
+        let wait = armed.wait(now);
+        state = shared
+            .changed
+            .wait_timeout(state, wait)
+            .expect("watchdog state lock should not be poisoned")
+            .0;
+    }
+}
@@ -0,0 +1,247 @@
+use deno_core::{JsRuntime, v8};
+
+use crate::types::error::BindingError;
+
+/// An `AbortController` and `AbortSignal` polyfill for the lightweight backend, which has no
+/// `EventTarget`, so the signal keeps its own listener list. It cancels nothing by itself: the
+/// lightweight backend has no `fetch` or other cancellable op, so code awaiting work checks
+/// `signal.aborted` or listens for `abort` and rejects with `signal.reason`.
+///
+/// Evaluates to a function that defines both classes on its target.
+const ABORT_SOURCE: &str = r#"((target) => {
+  const domException = (message, name) => {
+    if (typeof globalThis.DOMException === "function") {
+      return new DOMException(message, name);
+    }
+    const error = new Error(message);
+    error.name = name;
+    return error;
+  };
+  const constructing = Symbol("AbortSignal");
+  const state = new WeakMap();
+  const abortSignal = (signal, reason) => {
+    const internal = state.get(signal);
+    if (internal.aborted) {
+      return;
+    }
+    internal.aborted = true;
+    internal.reason = reason === undefined ? domException("This operation was aborted", "AbortError") : reason;
+    const event = { type: "abort", target: signal, currentTarget: signal };
+    if (typeof signal.onabort === "function") {
+      signal.onabort.call(signal, event);
+    }
+    for (const { listener, once } of [...internal.listeners]) {
+      if (once) {
+        signal.removeEventListener("abort", listener);
+      }
+      if (typeof listener === "function") {
+        listener.call(signal, event);
+      } else {
+        listener.handleEvent(event);
+      }
+    }
+  };
+
+  class AbortSignal {
+    constructor(key) {
+      if (key !== constructing) {
+        throw new TypeError("Illegal constructor");
+      }
+      state.set(this, { aborted: false, reason: undefined, listeners: [] });
+      this.onabort = null;
+    }
+
+    get aborted() {
+      return state.get(this).aborted;
+    }
+
+    get reason() {
+      return state.get(this).reason;
+    }
+
+    throwIfAborted() {
+      if (this.aborted) {
+        throw this.reason;
+      }
+    }
+
+    addEventListener(type, listener, options) {
+      if (type !== "abort" || listener == null) {
+        return;
+      }
+      const { listeners } = state.get(this);
+      if (!listeners.some((entry) => entry.listener === listener)) {
+        listeners.push({ listener, once: typeof options === "object" && options !== null && Boolean(options.once) });
+      }
+    }
+
+    removeEventListener(type, listener) {
+      const internal = state.get(this);
+      if (type === "abort") {
+        internal.listeners = internal.listeners.filter((entry) => entry.listener !== listener);
+      }
+    }
+
+    static abort(reason) {
+      const signal = new AbortSignal(constructing);
+      abortSignal(signal, reason);
+      return signal;
+    }
+
+    static timeout(milliseconds) {
+      const signal = new AbortSignal(constructing);
+      setTimeout(() => abortSignal(signal, domException("The operation timed out.", "TimeoutError")), milliseconds);
+      return signal;
+    }
+
+    static any(signals) {
+      const signal = new AbortSignal(constructing);
+      for (const source of signals) {
+        if (source.aborted) {
+          abortSignal(signal, source.reason);
+          return signal;
+        }
+      }
+      for (const source of signals) {
+        source.addEventListener("abort", () => abortSignal(signal, source.reason), { once: true });
+      }
+      return signal;
+    }
+  }
+
+  class AbortController {
+    #signal = new AbortSignal(constructing);
+
+    get signal() {
+      return this.#signal;
+    }
+
+    abort(reason) {
+      abortSignal(this.#signal, reason);
+    }
+  }
+
+  for (const [name, value] of [["AbortSignal", AbortSignal], ["AbortController", AbortController]]) {
+    Object.defineProperty(target, name, { value, writable: true, configurable: true });
+  }
+})"#;
+
+/// Leaves a runtime's own `AbortController`, such as the Deno worker's, in place.
+pub(crate) fn install_web_abort(runtime: &mut JsRuntime) -> Result<(), BindingError> {
+    let source = format!(
+        r#"if (typeof globalThis.AbortController !== "function" || typeof globalThis.AbortSignal !== "function") {{
+  ({ABORT_SOURCE})(globalThis);
+}}"#
+    );
+    runtime
+        .execute_script("belgie:abort", source)
+        .map(drop)
+        .map_err(|error| {
+            BindingError::runtime(format!("Could not install AbortController: {error}"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::with_test_js_runtime;
+
+    /// Runs `body` against the polyfill's own classes, even where the runtime brings its own.
+    fn eval_with_polyfill(body: &'static str) -> String {
+        with_test_js_runtime(move |runtime| {
+            let source = format!(
+                "(() => {{ const polyfill = {{}}; ({ABORT_SOURCE})(polyfill); \
+                 const {{ AbortController, AbortSignal }} = polyfill; {body} }})()"
+            );
+            let value = runtime
+                .execute_script("belgie:test", source)
+                .expect("abort calls should evaluate");
+            deno_core::scope!(scope, runtime);
+            v8::Local::new(scope, value).to_rust_string_lossy(scope)
+        })
+    }
+
+    #[test]
+    fn aborting_a_controller_notifies_listeners_once_with_the_reason() {
+        let result = eval_with_polyfill(
+            r#"
+            const controller = new AbortController();
+            const seen = [];
+            const once = () => seen.push("once");
+            controller.signal.onabort = () => seen.push("onabort");
+            controller.signal.addEventListener("abort", (event) => seen.push(event.type));
+            controller.signal.addEventListener("abort", once, { once: true });
+            controller.signal.addEventListener("abort", once);
+            controller.abort();
+            controller.abort("again");
+            let thrown;
+            try {
+              controller.signal.throwIfAborted();
+            } catch (error) {
+              thrown = error.name;
+            }
+            const combined = AbortSignal.any([new AbortController().signal, AbortSignal.abort("done")]);
+            let constructed;
+            try {
+              new AbortSignal();
+            } catch (error) {
+              constructed = error.name;
+            }
+            return JSON.stringify([
+              seen,
+              controller.signal.aborted,
+              controller.signal.reason.name,
+              thrown,
+              combined.reason,
+              constructed,
+            ]);
+            "#,
+        );
+
+        assert_eq!(
+            result,
+            r#"[["onabort","abort","once"],true,"AbortError","AbortError","done","TypeError"]"#
+        );
+    }
+
+    #[test]
+    fn any_follows_the_first_source_to_abort() {
+        let result = eval_with_polyfill(
+            r#"
+            const first = new AbortController();
+            const second = new AbortController();
+            const combined = AbortSignal.any([first.signal, second.signal]);
+            const before = combined.aborted;
+            second.abort("second");
+            first.abort("first");
+            return JSON.stringify([before, combined.aborted, combined.reason]);
+            "#,
+        );
+
+        assert_eq!(result, r#"[false,true,"second"]"#);
+    }
+
+    #[test]
+    fn install_keeps_an_existing_abort_controller() {
+        let result = with_test_js_runtime(|runtime| {
+            runtime
+                .execute_script(
+                    "belgie:test",
+                    "globalThis.AbortController = class { static standIn = true; }; \
+                     globalThis.AbortSignal = class { static standIn = true; };",
+                )
+                .expect("stand-ins should install");
+            install_web_abort(runtime).expect("AbortController should install");
+            let value = runtime
+                .execute_script(
+                    "belgie:test",
+                    "JSON.stringify([AbortController.standIn, AbortSignal.standIn])",
+                )
+                .expect("globals should evaluate");
+            deno_core::scope!(scope, runtime);
+            v8::Local::new(scope, value).to_rust_string_lossy(scope)
+        });
+
+        assert_eq!(result, "[true,true]");
+    }
+}
@@ -0,0 +1,134 @@
+use deno_core::{JsRuntime, v8};
+
+use crate::types::error::BindingError;
+
+/// Forgiving-base64 `btoa`/`atob` for the lightweight backend. Both work on "binary strings"
+/// whose code units are bytes, so `btoa` rejects anything outside Latin-1 like browsers do.
+///
+/// Evaluates to a function that defines both on its target.
+const BASE64_SOURCE: &str = r#"((target) => {
+  const ALPHABET = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+  const invalidCharacter = (message) => {
+    if (typeof globalThis.DOMException === "function") {
+      return new DOMException(message, "InvalidCharacterError");
+    }
+    const error = new Error(message);
+    error.name = "InvalidCharacterError";
+    return error;
+  };
+
+  function btoa(data) {
+    if (arguments.length === 0) {
+      throw new TypeError("btoa requires 1 argument");
+    }
+    const input = String(data);
+    let output = "";
+    for (let index = 0; index < input.length; index += 3) {
+      const bytes = [0, 1, 2].map((offset) => input.charCodeAt(index + offset));
+      for (const byte of bytes) {
+        if (byte > 0xff) {
+          throw invalidCharacter("The string to be encoded contains characters outside of the Latin1 range.");
+        }
+      }
+      const [first, second, third] = bytes;
+      const chunk = (first << 16) | ((second || 0) << 8) | (third || 0);
+      output += ALPHABET[(chunk >> 18) & 63] + ALPHABET[(chunk >> 12) & 63];
+      output += Number.isNaN(second) ? "=" : ALPHABET[(chunk >> 6) & 63];
+      output += Number.isNaN(third) ? "=" : ALPHABET[chunk & 63];
+    }
+    return output;
+  }
+
+  function atob(data) {
+    if (arguments.length === 0) {
+      throw new TypeError("atob requires 1 argument");
+    }
+    let input = String(data).replace(/[\t\n\f\r ]/g, "");
+    if (input.length % 4 === 0) {
+      input = input.replace(/={1,2}$/, "");
+    }
+    if (input.length % 4 === 1 || /[^A-Za-z0-9+/]/.test(input)) {
+      throw invalidCharacter("The string to be decoded is not correctly encoded.");
+    }
+    let output = "";
+    let buffer = 0;
+    let bits = 0;
+    for (const character of input) {
+      buffer = (buffer << 6) | ALPHABET.indexOf(character);
+      bits += 6;
+      if (bits >= 8) {
+        bits -= 8;
+        output += String.fromCharCode((buffer >> bits) & 0xff);
+      }
+    }
+    return output;
+  }
+
+  for (const [name, value] of [["btoa", btoa], ["atob", atob]]) {
+    Object.defineProperty(target, name, { value, writable: true, configurable: true });
+  }
+})"#;
+
+/// Leaves a runtime's own `btoa` and `atob`, such as the Deno worker's, in place.
+pub(crate) fn install_web_base64(runtime: &mut JsRuntime) -> Result<(), BindingError> {
+    let source = format!(
+        r#"if (typeof globalThis.btoa !== "function" || typeof globalThis.atob !== "function") {{
+  ({BASE64_SOURCE})(globalThis);
+}}"#
+    );
+    runtime
+        .execute_script("belgie:base64", source)
+        .map(drop)
+        .map_err(|error| BindingError::runtime(format!("Could not install base64: {error}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::with_test_js_runtime;
+
+    /// Runs `body` against the polyfill's own functions, even where the runtime brings its own.
+    fn eval_with_polyfill(body: &'static str) -> String {
+        with_test_js_runtime(move |runtime| {
+            let source = format!(
+                "(() => {{ const polyfill = {{}}; ({BASE64_SOURCE})(polyfill); \
+                 const {{ btoa, atob }} = polyfill; {body} }})()"
+            );
+            let value = runtime
+                .execute_script("belgie:test", source)
+                .expect("base64 calls should evaluate");
+            deno_core::scope!(scope, runtime);
+            v8::Local::new(scope, value).to_rust_string_lossy(scope)
+        })
+    }
+
+    #[test]
+    fn encodes_and_decodes_latin1_binary_strings() {
+        let result = eval_with_polyfill(
+            r#"
+            const rejects = (run) => {
+              try {
+                run();
+                return "ok";
+              } catch (error) {
+                return error.name;
+              }
+            };
+            return JSON.stringify([
+              btoa("hello"),
+              atob(btoa("hello")),
+              btoa("\xff\x00"),
+              atob(" aGk "),
+              rejects(() => btoa("☃")),
+              rejects(() => atob("a")),
+              btoa === globalThis.btoa,
+            ]);
+            "#,
+        );
+
+        assert_eq!(
+            result,
+            r#"["aGVsbG8=","hello","/wA=","hi","InvalidCharacterError","InvalidCharacterError",false]"#
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use deno_core::{JsRuntime, v8};
+
+use crate::runtime::native_error::throw_native_error;
+use crate::types::error::BindingError;
+
+/// `crypto.getRandomValues`/`crypto.randomUUID` for the lightweight backend, backed by the
+/// system CSPRNG. Deno workers ship full Web Crypto, so an existing `crypto` is left alone.
+///
+/// Evaluates to a function that takes the random source and returns one that defines `crypto`
+/// on its target.
+const WEB_CRYPTO_SOURCE: &str = r#"((randomBytes) => (target) => {
+  if (typeof target.crypto?.getRandomValues === "function") {
+    return;
+  }
+  const INTEGER_ARRAYS = [
+    Int8Array,
+    Uint8Array,
+    Uint8ClampedArray,
+    Int16Array,
+    Uint16Array,
+    Int32Array,
+    Uint32Array,
+    BigInt64Array,
+    BigUint64Array,
+  ];
+  const getRandomValues = (array) => {
+    if (!INTEGER_ARRAYS.some((type) => array instanceof type)) {
+      throw new TypeError("crypto.getRandomValues requires an integer typed array");
+    }
+    if (array.byteLength > 65536) {
+      throw new RangeError("crypto.getRandomValues cannot generate more than 65536 bytes");
+    }
+    new Uint8Array(array.buffer, array.byteOffset, array.byteLength).set(randomBytes(array.byteLength));
+    return array;
+  };
+  const randomUUID = () => {
+    const bytes = randomBytes(16);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    const hex = Array.from(bytes, (byte) => byte.toString(16).padStart(2, "0")).join("");
+    return `${hex.slice(0, 8)}-${hex.slice(8, 12)}-${hex.slice(12, 16)}-${hex.slice(16, 20)}-${hex.slice(20)}`;
+  };
+  Object.defineProperty(target, "crypto", {
+    value: Object.freeze({ getRandomValues, randomUUID }),
+    writable: true,
+    configurable: true,
+  });
+})"#;
+
+pub(crate) fn install_web_crypto(runtime: &mut JsRuntime) -> Result<(), BindingError> {
+    let installer = web_crypto_installer(runtime)?;
+    deno_core::scope!(scope, runtime);
+    let installer = v8::Local::new(scope, installer);
+    let global = scope.get_current_context().global(scope);
+    let receiver = v8::undefined(scope).into();
+    installer
+        .call(scope, receiver, &[global.into()])
+        .ok_or_else(|| BindingError::runtime("Could not install web crypto"))?;
+    Ok(())
+}
+
+/// The function that defines `crypto` on the object it is called with.
+fn web_crypto_installer(
+    runtime: &mut JsRuntime,
+) -> Result<v8::Global<v8::Function>, BindingError> {
+    let source = runtime
+        .execute_script("belgie:web_crypto", WEB_CRYPTO_SOURCE)
+        .map_err(|error| BindingError::runtime(format!("Could not install web crypto: {error}")))?;
+    deno_core::scope!(scope, runtime);
+    let source = v8::Local::<v8::Function>::try_from(v8::Local::new(scope, source))
+        .map_err(|_| BindingError::runtime("Could not install web crypto"))?;
+    let random_bytes = v8::Function::new(scope, random_bytes_callback)
+        .ok_or_else(|| BindingError::runtime("Could not create crypto random source"))?;
+    let receiver = v8::undefined(scope).into();
+    let installer = source
+        .call(scope, receiver, &[random_bytes.into()])
+        .and_then(|installer| v8::Local::<v8::Function>::try_from(installer).ok())
+        .ok_or_else(|| BindingError::runtime("Could not install web crypto"))?;
+    Ok(v8::Global::new(scope, installer))
+}
+
+fn random_bytes_callback(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let length = args.get(0).uint32_value(scope).unwrap_or(0) as usize;
+    let mut bytes = vec![0; length];
+    if aws_lc_rs::rand::fill(&mut bytes).is_err() {
+        throw_native_error(
+            scope,
+            "crypto.getRandomValues",
+            "Could not read from the system random source",
+        );
+        return;
+    }
+    let store = v8::ArrayBuffer::new_backing_store_from_vec(bytes).make_shared();
+    let buffer = v8::ArrayBuffer::with_backing_store(scope, &store);
+    if let Some(array) = v8::Uint8Array::new(scope, buffer, 0, length) {
+        return_value.set(array.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::with_test_js_runtime;
+
+    /// Runs `body` against the polyfill's own `crypto`, even where the runtime brings its own.
+    fn eval_with_polyfill(body: &'static str) -> String {
+        with_test_js_runtime(move |runtime| {
+            let installer =
+                web_crypto_installer(runtime).expect("web crypto installer should evaluate");
+            let test = runtime
+                .execute_script(
+                    "belgie:test",
+                    format!(
+                        "((install) => {{ const polyfill = {{}}; install(polyfill); \
+                         const {{ crypto }} = polyfill; {body} }})"
+                    ),
+                )
+                .expect("test function should evaluate");
+            deno_core::scope!(scope, runtime);
+            let test = v8::Local::<v8::Function>::try_from(v8::Local::new(scope, test))
+                .expect("test source should evaluate to a function");
+            let installer = v8::Local::new(scope, installer);
+            let receiver = v8::undefined(scope).into();
+            test.call(scope, receiver, &[installer.into()])
+                .expect("crypto calls should evaluate")
+                .to_rust_string_lossy(scope)
+        })
+    }
+
+    #[test]
+    fn generates_version_four_uuids_and_fills_arrays_in_place() {
+        let result = eval_with_polyfill(
+            r#"
+            const array = new Uint32Array(8);
+            const filled = crypto.getRandomValues(array);
+            let oversized;
+            try {
+              crypto.getRandomValues(new Uint8Array(65537));
+            } catch (error) {
+              oversized = error.name;
+            }
+            return JSON.stringify([
+              /^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$/
+                .test(crypto.randomUUID()),
+              filled === array && array.some((value) => value !== 0),
+              oversized,
+              crypto === globalThis.crypto,
+            ]);
+            "#,
+        );
+
+        assert_eq!(result, r#"[true,true,"RangeError",false]"#);
+    }
+}
@@ -0,0 +1,195 @@
+use deno_core::{JsRuntime, v8};
+
+use crate::types::error::BindingError;
+
+/// `structuredClone` for the lightweight backend. Follows the HTML structured clone algorithm
+/// for the types scripts exchange in practice: shared and cyclic references survive, class
+/// instances become plain objects, and functions, symbols, and host objects throw
+/// `DataCloneError`. Transfer lists are not supported.
+///
+/// Evaluates to a function that defines `structuredClone` on its target.
+const STRUCTURED_CLONE_SOURCE: &str = r#"((target) => {
+  const dataCloneError = (message) => {
+    if (typeof globalThis.DOMException === "function") {
+      return new DOMException(message, "DataCloneError");
+    }
+    const error = new Error(message);
+    error.name = "DataCloneError";
+    return error;
+  };
+  const tag = (value) => Object.prototype.toString.call(value).slice(8, -1);
+  const ERRORS = { Error, EvalError, RangeError, ReferenceError, SyntaxError, TypeError, URIError };
+
+  function structuredClone(value, options) {
+    if (arguments.length === 0) {
+      throw new TypeError("structuredClone requires 1 argument");
+    }
+    if (options?.transfer !== undefined && [...options.transfer].length > 0) {
+      throw dataCloneError("structuredClone does not support transferring values in this runtime.");
+    }
+    const memory = new Map();
+
+    const clone = (input) => {
+      if (typeof input === "function") {
+        throw dataCloneError(`${input.name || "anonymous"} could not be cloned.`);
+      }
+      if (typeof input === "symbol") {
+        throw dataCloneError(`${String(input)} could not be cloned.`);
+      }
+      if (input === null || typeof input !== "object") {
+        return input;
+      }
+      if (memory.has(input)) {
+        return memory.get(input);
+      }
+      const remember = (output) => {
+        memory.set(input, output);
+        return output;
+      };
+      switch (tag(input)) {
+        case "Boolean":
+        case "Number":
+        case "String":
+        case "BigInt":
+          return remember(Object(input.valueOf()));
+        case "Date":
+          return remember(new Date(input.getTime()));
+        case "RegExp":
+          return remember(new RegExp(input.source, input.flags));
+        case "ArrayBuffer":
+          return remember(input.slice(0));
+        case "DataView": {
+          const buffer = clone(input.buffer);
+          return remember(new DataView(buffer, input.byteOffset, input.byteLength));
+        }
+        case "Map": {
+          const output = remember(new Map());
+          for (const [key, item] of input) {
+            output.set(clone(key), clone(item));
+          }
+          return output;
+        }
+        case "Set": {
+          const output = remember(new Set());
+          for (const item of input) {
+            output.add(clone(item));
+          }
+          return output;
+        }
+        case "Error": {
+          const Constructor = ERRORS[input.name] ?? Error;
+          const output = remember(new Constructor(clone(input.message)));
+          if (input.stack !== undefined) {
+            output.stack = String(input.stack);
+          }
+          if ("cause" in input) {
+            output.cause = clone(input.cause);
+          }
+          return output;
+        }
+        case "Array": {
+          const output = remember(new Array(input.length));
+          for (const key of Object.keys(input)) {
+            output[key] = clone(input[key]);
+          }
+          return output;
+        }
+        case "Object": {
+          const output = remember({});
+          for (const key of Object.keys(input)) {
+            output[key] = clone(input[key]);
+          }
+          return output;
+        }
+        default:
+          if (ArrayBuffer.isView(input)) {
+            const buffer = clone(input.buffer);
+            return remember(new input.constructor(buffer, input.byteOffset, input.length));
+          }
+          throw dataCloneError(`${tag(input)} object could not be cloned.`);
+      }
+    };
+
+    return clone(value);
+  }
+
+  Object.defineProperty(target, "structuredClone", {
+    value: structuredClone,
+    writable: true,
+    configurable: true,
+  });
+})"#;
+
+/// Leaves a runtime's own `structuredClone`, such as the Deno worker's, in place.
+pub(crate) fn install_web_structured_clone(runtime: &mut JsRuntime) -> Result<(), BindingError> {
+    let source = format!(
+        r#"if (typeof globalThis.structuredClone !== "function") {{
+  ({STRUCTURED_CLONE_SOURCE})(globalThis);
+}}"#
+    );
+    runtime
+        .execute_script("belgie:structured-clone", source)
+        .map(drop)
+        .map_err(|error| {
+            BindingError::runtime(format!("Could not install structuredClone: {error}"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::with_test_js_runtime;
+
+    /// Runs `body` against the polyfill's own `structuredClone`, even where the runtime brings
+    /// its own.
+    fn eval_with_polyfill(body: &'static str) -> String {
+        with_test_js_runtime(move |runtime| {
+            let source = format!(
+                "(() => {{ const polyfill = {{}}; ({STRUCTURED_CLONE_SOURCE})(polyfill); \
+                 const {{ structuredClone }} = polyfill; {body} }})()"
+            );
+            let value = runtime
+                .execute_script("belgie:test", source)
+                .expect("structuredClone calls should evaluate");
+            deno_core::scope!(scope, runtime);
+            v8::Local::new(scope, value).to_rust_string_lossy(scope)
+        })
+    }
+
+    #[test]
+    fn deep_clones_collections_dates_and_typed_arrays() {
+        let result = eval_with_polyfill(
+            r#"
+            const shared = { n: 1 };
+            const source = {
+              map: new Map([["a", shared]]),
+              set: new Set([1]),
+              when: new Date(5),
+              bytes: new Uint8Array([1, 2]),
+              both: [shared, shared],
+            };
+            source.self = source;
+            const copy = structuredClone(source);
+            copy.map.get("a").n = 2;
+            copy.bytes[0] = 9;
+            let thrown = "none";
+            try {
+              structuredClone({ run() {} });
+            } catch (error) {
+              thrown = error.name;
+            }
+            return JSON.stringify([
+              shared.n,
+              copy.map instanceof Map && copy.set.has(1),
+              copy.when instanceof Date && copy.when.getTime(),
+              source.bytes[0],
+              copy.both[0] === copy.both[1] && copy.self === copy,
+              thrown,
+              structuredClone === globalThis.structuredClone,
+            ]);
+            "#,
+        );
+
+        assert_eq!(result, r#"[1,true,5,1,true,"DataCloneError",false]"#);
+    }
+}
@@ -0,0 +1,469 @@
+use deno_core::{JsRuntime, v8};
+use url::{Url, form_urlencoded, quirks};
+
+use crate::types::error::BindingError;
+
+/// `URL`/`URLSearchParams` for the lightweight backend. Parsing and the component setters go
+/// through the `url` crate's WHATWG quirks API; Deno workers already ship `deno_url`.
+///
+/// Evaluates to a function that takes the native helpers and returns one that defines both
+/// classes on its target, unless the target already has them.
+const WEB_URL_SOURCE: &str = r#"((native) => (target) => {
+  if (typeof target.URL === "function" && typeof target.URLSearchParams === "function") {
+    return;
+  }
+  const COMPONENTS = [
+    "href",
+    "origin",
+    "protocol",
+    "username",
+    "password",
+    "host",
+    "hostname",
+    "port",
+    "pathname",
+    "search",
+    "hash",
+  ];
+  const SETTABLE = new Set(COMPONENTS.filter((name) => name !== "href" && name !== "origin"));
+  let linkSearchParams;
+  let reparseSearchParams;
+
+  class URLSearchParams {
+    #pairs = [];
+    #onUpdate = null;
+
+    constructor(init = "") {
+      if (typeof init === "object" && init !== null) {
+        const entries = typeof init[Symbol.iterator] === "function" ? init : Object.entries(init);
+        for (const entry of entries) {
+          const pair = Array.from(entry);
+          if (pair.length !== 2) {
+            throw new TypeError("Each URLSearchParams pair must have exactly two items");
+          }
+          this.#pairs.push([String(pair[0]), String(pair[1])]);
+        }
+      } else {
+        this.#parse(String(init));
+      }
+    }
+
+    #parse(query) {
+      const flat = native.parseSearch(query.startsWith("?") ? query.slice(1) : query);
+      this.#pairs = [];
+      for (let index = 0; index < flat.length; index += 2) {
+        this.#pairs.push([flat[index], flat[index + 1]]);
+      }
+    }
+
+    #update() {
+      this.#onUpdate?.(this.toString());
+    }
+
+    static {
+      linkSearchParams = (params, query, onUpdate) => {
+        params.#parse(query);
+        params.#onUpdate = onUpdate;
+      };
+      reparseSearchParams = (params, query) => params.#parse(query);
+    }
+
+    get size() {
+      return this.#pairs.length;
+    }
+
+    append(name, value) {
+      this.#pairs.push([String(name), String(value)]);
+      this.#update();
+    }
+
+    delete(name, value) {
+      name = String(name);
+      this.#pairs = this.#pairs.filter(
+        ([key, current]) => key !== name || (value !== undefined && current !== String(value)),
+      );
+      this.#update();
+    }
+
+    get(name) {
+      name = String(name);
+      return this.#pairs.find(([key]) => key === name)?.[1] ?? null;
+    }
+
+    getAll(name) {
+      name = String(name);
+      return this.#pairs.filter(([key]) => key === name).map(([, value]) => value);
+    }
+
+    has(name, value) {
+      name = String(name);
+      return this.#pairs.some(
+        ([key, current]) => key === name && (value === undefined || current === String(value)),
+      );
+    }
+
+    set(name, value) {
+      name = String(name);
+      value = String(value);
+      const index = this.#pairs.findIndex(([key]) => key === name);
+      if (index === -1) {
+        this.#pairs.push([name, value]);
+      } else {
+        this.#pairs[index][1] = value;
+        this.#pairs = this.#pairs.filter(([key], current) => current <= index || key !== name);
+      }
+      this.#update();
+    }
+
+    sort() {
+      this.#pairs.sort(([left], [right]) => (left < right ? -1 : left > right ? 1 : 0));
+      this.#update();
+    }
+
+    forEach(callback, thisArg) {
+      for (const [name, value] of this.#pairs) {
+        callback.call(thisArg, value, name, this);
+      }
+    }
+
+    *entries() {
+      for (const [name, value] of this.#pairs) {
+        yield [name, value];
+      }
+    }
+
+    *keys() {
+      for (const [name] of this.#pairs) {
+        yield name;
+      }
+    }
+
+    *values() {
+      for (const [, value] of this.#pairs) {
+        yield value;
+      }
+    }
+
+    [Symbol.iterator]() {
+      return this.entries();
+    }
+
+    toString() {
+      return native.serializeSearch(this.#pairs.flat());
+    }
+  }
+
+  class URL {
+    #components;
+    #searchParams = new URLSearchParams();
+
+    constructor(input, base = undefined) {
+      const components = native.parse(String(input), base === undefined ? undefined : String(base));
+      if (components === null) {
+        throw new TypeError(`Invalid URL: '${input}'`);
+      }
+      this.#components = components;
+      linkSearchParams(this.#searchParams, this.search, (query) => {
+        this.#components = native.set(this.href, "search", query);
+      });
+    }
+
+    static canParse(input, base = undefined) {
+      return native.parse(String(input), base === undefined ? undefined : String(base)) !== null;
+    }
+
+    static parse(input, base = undefined) {
+      return URL.canParse(input, base) ? new URL(input, base) : null;
+    }
+
+    static {
+      for (const name of SETTABLE) {
+        Object.defineProperty(URL.prototype, name, {
+          get() {
+            return this.#components[COMPONENTS.indexOf(name)];
+          },
+          set(value) {
+            this.#components = native.set(this.href, name, String(value));
+            if (name === "search") {
+              reparseSearchParams(this.#searchParams, this.search);
+            }
+          },
+          enumerable: true,
+          configurable: true,
+        });
+      }
+    }
+
+    get href() {
+      return this.#components[0];
+    }
+
+    set href(value) {
+      const components = native.parse(String(value), undefined);
+      if (components === null) {
+        throw new TypeError(`Invalid URL: '${value}'`);
+      }
+      this.#components = components;
+      reparseSearchParams(this.#searchParams, this.search);
+    }
+
+    get origin() {
+      return this.#components[1];
+    }
+
+    get searchParams() {
+      return this.#searchParams;
+    }
+
+    toString() {
+      return this.href;
+    }
+
+    toJSON() {
+      return this.href;
+    }
+  }
+
+  for (const [name, value] of [["URL", URL], ["URLSearchParams", URLSearchParams]]) {
+    Object.defineProperty(target, name, { value, writable: true, configurable: true });
+  }
+})"#;
+
+pub(crate) fn install_web_url(runtime: &mut JsRuntime) -> Result<(), BindingError> {
+    let installer = web_url_installer(runtime)?;
+    deno_core::scope!(scope, runtime);
+    let installer = v8::Local::new(scope, installer);
+    let global = scope.get_current_context().global(scope);
+    let receiver = v8::undefined(scope).into();
+    installer
+        .call(scope, receiver, &[global.into()])
+        .ok_or_else(|| BindingError::runtime("Could not install URL"))?;
+    Ok(())
+}
+
+/// The function that defines `URL` and `URLSearchParams` on the object it is called with.
+fn web_url_installer(runtime: &mut JsRuntime) -> Result<v8::Global<v8::Function>, BindingError> {
+    let source = runtime
+        .execute_script("belgie:web_url", WEB_URL_SOURCE)
+        .map_err(|error| BindingError::runtime(format!("Could not install URL: {error}")))?;
+    deno_core::scope!(scope, runtime);
+    let source = v8::Local::<v8::Function>::try_from(v8::Local::new(scope, source))
+        .map_err(|_| BindingError::runtime("Could not install URL"))?;
+    let native = v8::Object::new(scope);
+    set_helper(scope, native, "parse", parse_callback)?;
+    set_helper(scope, native, "set", set_callback)?;
+    set_helper(scope, native, "parseSearch", parse_search_callback)?;
+    set_helper(scope, native, "serializeSearch", serialize_search_callback)?;
+    let receiver = v8::undefined(scope).into();
+    let installer = source
+        .call(scope, receiver, &[native.into()])
+        .and_then(|installer| v8::Local::<v8::Function>::try_from(installer).ok())
+        .ok_or_else(|| BindingError::runtime("Could not install URL"))?;
+    Ok(v8::Global::new(scope, installer))
+}
+
+fn set_helper(
+    scope: &mut v8::PinScope<'_, '_>,
+    native: v8::Local<v8::Object>,
+    name: &str,
+    callback: impl v8::MapFnTo<v8::FunctionCallback>,
+) -> Result<(), BindingError> {
+    let key = v8::String::new(scope, name)
+        .ok_or_else(|| BindingError::runtime("Could not create URL helper name"))?;
+    let function = v8::Function::new(scope, callback)
+        .ok_or_else(|| BindingError::runtime(format!("Could not create URL helper {name}")))?;
+    if !native
+        .set(scope, key.into(), function.into())
+        .unwrap_or(false)
+    {
+        return Err(BindingError::runtime(format!(
+            "Could not install URL helper {name}"
+        )));
+    }
+    Ok(())
+}
+
+/// `parse(input, base)` returns the URL components in `COMPONENTS` order, or `null`.
+fn parse_callback(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let input = args.get(0).to_rust_string_lossy(scope);
+    let base = args.get(1);
+    let parsed = if base.is_undefined() {
+        Url::parse(&input)
+    } else {
+        Url::parse(&base.to_rust_string_lossy(scope))
+            .and_then(|base| Url::options().base_url(Some(&base)).parse(&input))
+    };
+    match parsed {
+        Ok(url) => return_value.set(components(scope, &url).into()),
+        Err(_) => return_value.set_null(),
+    }
+}
+
+/// `set(href, name, value)` applies one WHATWG component setter. Invalid values leave the URL
+/// unchanged, as the URL standard requires.
+fn set_callback(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let href = args.get(0).to_rust_string_lossy(scope);
+    let name = args.get(1).to_rust_string_lossy(scope);
+    let value = args.get(2).to_rust_string_lossy(scope);
+    let Ok(mut url) = Url::parse(&href) else {
+        return_value.set_null();
+        return;
+    };
+    let _ = match name.as_str() {
+        "protocol" => quirks::set_protocol(&mut url, &value),
+        "username" => quirks::set_username(&mut url, &value),
+        "password" => quirks::set_password(&mut url, &value),
+        "host" => quirks::set_host(&mut url, &value),
+        "hostname" => quirks::set_hostname(&mut url, &value),
+        "port" => quirks::set_port(&mut url, &value),
+        "pathname" => {
+            quirks::set_pathname(&mut url, &value);
+            Ok(())
+        }
+        "search" => {
+            quirks::set_search(&mut url, &value);
+            Ok(())
+        }
+        "hash" => {
+            quirks::set_hash(&mut url, &value);
+            Ok(())
+        }
+        _ => Ok(()),
+    };
+    return_value.set(components(scope, &url).into());
+}
+
+/// `parseSearch(query)` decodes `application/x-www-form-urlencoded` into flat name/value pairs.
+fn parse_search_callback(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let query = args.get(0).to_rust_string_lossy(scope);
+    let pairs = form_urlencoded::parse(query.as_bytes())
+        .flat_map(|(name, value)| [name.into_owned(), value.into_owned()])
+        .collect::<Vec<_>>();
+    return_value.set(string_array(scope, &pairs).into());
+}
+
+/// `serializeSearch(pairs)` encodes flat name/value pairs as a query string without `?`.
+fn serialize_search_callback(
+    scope: &mut v8::PinScope,
+    args: v8::FunctionCallbackArguments,
+    mut return_value: v8::ReturnValue,
+) {
+    let Ok(pairs) = v8::Local::<v8::Array>::try_from(args.get(0)) else {
+        return_value.set(v8::String::empty(scope).into());
+        return;
+    };
+    let values = (0..pairs.length())
+        .map(|index| {
+            pairs
+                .get_index(scope, index)
+                .map(|value| value.to_rust_string_lossy(scope))
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    let mut serializer = form_urlencoded::Serializer::new(String::new());
+    for pair in values.chunks(2) {
+        serializer.append_pair(&pair[0], pair.get(1).map_or("", String::as_str));
+    }
+    let query =
+        v8::String::new(scope, &serializer.finish()).unwrap_or_else(|| v8::String::empty(scope));
+    return_value.set(query.into());
+}
+
+fn components<'s>(scope: &mut v8::PinScope<'s, '_>, url: &Url) -> v8::Local<'s, v8::Array> {
+    let origin = quirks::origin(url);
+    string_array(
+        scope,
+        &[
+            quirks::href(url),
+            origin.as_str(),
+            quirks::protocol(url),
+            quirks::username(url),
+            quirks::password(url),
+            quirks::host(url),
+            quirks::hostname(url),
+            quirks::port(url),
+            quirks::pathname(url),
+            quirks::search(url),
+            quirks::hash(url),
+        ],
+    )
+}
+
+fn string_array<'s>(
+    scope: &mut v8::PinScope<'s, '_>,
+    values: &[impl AsRef<str>],
+) -> v8::Local<'s, v8::Array> {
+    let elements = values
+        .iter()
+        .map(|value| {
+            v8::String::new(scope, value.as_ref())
+                .unwrap_or_else(|| v8::String::empty(scope))
+                .into()
+        })
+        .collect::<Vec<v8::Local<v8::Value>>>();
+    v8::Array::new_with_elements(scope, &elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::with_test_js_runtime;
+
+    /// Runs `body` against the polyfill's own classes, even where the runtime brings its own.
+    fn eval_with_polyfill(body: &'static str) -> String {
+        with_test_js_runtime(move |runtime| {
+            let installer = web_url_installer(runtime).expect("URL installer should evaluate");
+            let test = runtime
+                .execute_script(
+                    "belgie:test",
+                    format!(
+                        "((install) => {{ const polyfill = {{}}; install(polyfill); \
+                         const {{ URL, URLSearchParams }} = polyfill; {body} }})"
+                    ),
+                )
+                .expect("test function should evaluate");
+            deno_core::scope!(scope, runtime);
+            let test = v8::Local::<v8::Function>::try_from(v8::Local::new(scope, test))
+                .expect("test source should evaluate to a function");
+            let installer = v8::Local::new(scope, installer);
+            let receiver = v8::undefined(scope).into();
+            test.call(scope, receiver, &[installer.into()])
+                .expect("URL calls should evaluate")
+                .to_rust_string_lossy(scope)
+        })
+    }
+
+    #[test]
+    fn parses_queries_and_fragments() {
+        let result = eval_with_polyfill(
+            r#"
+            const url = new URL("../b?z=1&q=a+b#top", "https://example.com/a/c");
+            url.searchParams.append("n", "é");
+            return JSON.stringify([
+              url.href,
+              url.searchParams.get("q"),
+              url.hash,
+              new URLSearchParams({ a: "1 2" }).toString(),
+              URL === globalThis.URL,
+            ]);
+            "#,
+        );
+
+        assert_eq!(
+            result,
+            r#"["https://example.com/b?z=1&q=a+b&n=%C3%A9#top","a b","#top","a=1+2",false]"#
+        );
+    }
+}
@@ -1,6 +1,8 @@
 mod dependencies;
+mod module_source;
 mod signature;
 mod source;
 
+pub(crate) use module_source::{ModuleEncoding, ModuleSource};
 pub(crate) use signature::{ParamPattern, RunSignature};
-pub(crate) use source::ScriptSource;
+pub(crate) use source::{ScriptSource, check_script, transpile_eval_source};
@@ -0,0 +1,126 @@
+use std::io::Read;
+use std::sync::Arc;
+
+use crate::types::error::BindingError;
+
+/// Source of one in-memory script module. Compressed modules stay compressed until a runner's
+/// worker thread decodes them, so bundles bound to many runners are held once, small.
+#[derive(Clone, Debug)]
+pub(crate) enum ModuleSource {
+    Text(Arc<str>),
+    Compressed {
+        encoding: ModuleEncoding,
+        data: Arc<[u8]>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ModuleEncoding {
+    Gzip,
+    Brotli,
+}
+
+impl ModuleEncoding {
+    pub(crate) const ALL: [Self; 2] = [Self::Gzip, Self::Brotli];
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|encoding| encoding.name() == name)
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Brotli => "br",
+        }
+    }
+}
+
+impl ModuleSource {
+    /// The module's UTF-8 source, decompressing it first when needed.
+    pub(crate) fn decode(&self, specifier: &str) -> Result<String, BindingError> {
+        let data = match self {
+            Self::Text(source) => return Ok(source.to_string()),
+            Self::Compressed { encoding, data } => {
+                decompress(*encoding, data).map_err(|error| {
+                    BindingError::module_load(format!(
+                        "Could not decompress {} script module {specifier}: {error}",
+                        encoding.name()
+                    ))
+                })?
+            }
+        };
+        String::from_utf8(data).map_err(|_| {
+            BindingError::module_load(format!(
+                "Script module {specifier} is not valid UTF-8 once decompressed"
+            ))
+        })
+    }
+}
+
+impl From<String> for ModuleSource {
+    fn from(source: String) -> Self {
+        Self::Text(source.into())
+    }
+}
+
+fn decompress(encoding: ModuleEncoding, data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    match encoding {
+        ModuleEncoding::Gzip => flate2::read::GzDecoder::new(data).read_to_end(&mut output)?,
+        ModuleEncoding::Brotli => brotli::Decompressor::new(data, 4096).read_to_end(&mut output)?,
+    };
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    const SOURCE: &str = "export const greeting = 'hello';\n";
+
+    #[test]
+    fn decodes_gzip_and_brotli_modules() {
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gzip.write_all(SOURCE.as_bytes())
+            .expect("gzip should compress");
+        let gzip = gzip.finish().expect("gzip should finish");
+        let mut brotli = Vec::new();
+        brotli::CompressorWriter::new(&mut brotli, 4096, 5, 22)
+            .write_all(SOURCE.as_bytes())
+            .expect("brotli should compress");
+
+        for (encoding, data) in [
+            (ModuleEncoding::Gzip, gzip),
+            (ModuleEncoding::Brotli, brotli),
+        ] {
+            let module = ModuleSource::Compressed {
+                encoding,
+                data: data.into(),
+            };
+            assert_eq!(
+                module
+                    .decode("./greeting.js")
+                    .expect("module should decode"),
+                SOURCE
+            );
+        }
+    }
+
+    #[test]
+    fn reports_corrupt_compressed_modules() {
+        let module = ModuleSource::Compressed {
+            encoding: ModuleEncoding::Gzip,
+            data: Arc::from(&b"not gzip"[..]),
+        };
+
+        let error = module
+            .decode("./broken.js")
+            .expect_err("corrupt data should fail");
+
+        assert!(error.message().contains("./broken.js"));
+    }
+}
@@ -86,7 +86,15 @@ pub(crate) fn parse_script_module(
     content: &str,
     media_type: MediaType,
 ) -> Option<deno_ast::ParsedSource> {
-    let specifier = ModuleSpecifier::parse("file:///belgie_inline_script.ts").ok()?;
+    try_parse_script_module(content, media_type).ok()
+}
+
+pub(crate) fn try_parse_script_module(
+    content: &str,
+    media_type: MediaType,
+) -> Result<deno_ast::ParsedSource, deno_ast::ParseDiagnostic> {
+    let specifier = ModuleSpecifier::parse("file:///belgie_inline_script.ts")
+        .expect("the inline script specifier should be a valid URL");
     parse_module(ParseParams {
         specifier,
         text: content.into(),
@@ -95,7 +103,6 @@ pub(crate) fn parse_script_module(
         scope_analysis: false,
         maybe_syntax: None,
     })
-    .ok()
 }
 
 pub(crate) fn run_signature_from_parsed(parsed: &deno_ast::ParsedSource) -> Option<RunSignature> {
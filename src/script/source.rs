@@ -1,32 +1,45 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use deno_ast::MediaType;
+use deno_ast::{MediaType, SourceMapOption};
 
+use super::ModuleSource;
 use super::dependencies::{analyze_parsed_script_dependencies, content_may_have_resolver_imports};
 use super::signature::{self, RunSignature, run_signature_from_parsed};
 use crate::options::ScriptOptions;
+use crate::types::error::{BindingError, SyntaxErrorLocation};
+use crate::types::value::PyJsValue;
 
 const INLINE_REACT_IMPORT_SOURCE: &str = "npm:react@19.2.6";
 
 #[derive(Clone, Debug)]
 pub(crate) struct ScriptSource {
-    content: String,
+    /// Shared so binding a script to many runners does not copy megabyte bundles per runner.
+    content: Arc<str>,
     kind: ScriptSourceKind,
     media_type: deno_ast::MediaType,
     needs_package_loader: bool,
     run_signature: Option<RunSignature>,
+    globals: Option<PyJsValue>,
+    modules: BTreeMap<String, ModuleSource>,
+    imports: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Debug)]
 enum ScriptSourceKind {
-    Inline,
+    Inline { name: Option<String> },
     File { path: PathBuf },
 }
 
 impl ScriptSource {
     pub(crate) fn from_options(options: ScriptOptions) -> Self {
         let path = options.path().map(Path::to_path_buf);
-        let content = options.into_content();
+        let name = options.name().map(str::to_string);
+        let globals = options.globals().cloned();
+        let modules = options.modules().clone();
+        let imports = options.imports().clone();
+        let content = Arc::<str>::from(options.into_content());
         let (media_type, parsed) = parsed_source(&content, path.as_deref());
         let needs_package_loader = media_type == MediaType::Tsx
             || parsed
@@ -36,7 +49,7 @@ impl ScriptSource {
         let run_signature = parsed.as_ref().and_then(run_signature_from_parsed);
         let kind = match path {
             Some(path) => ScriptSourceKind::File { path },
-            None => ScriptSourceKind::Inline,
+            None => ScriptSourceKind::Inline { name },
         };
         Self {
             content,
@@ -44,30 +57,68 @@ impl ScriptSource {
             media_type,
             needs_package_loader,
             run_signature,
+            globals,
+            modules,
+            imports,
         }
     }
 
+    /// Reads a file script again, keeping its globals, modules, and imports.
+    pub(crate) fn reloaded(&self) -> Result<Self, BindingError> {
+        let ScriptSourceKind::File { path } = &self.kind else {
+            return Err(BindingError::runtime("Only file scripts can be reloaded"));
+        };
+        let content = std::fs::read_to_string(path).map_err(|error| {
+            BindingError::runtime(format!(
+                "Could not read script file {}: {error}",
+                path.display()
+            ))
+        })?;
+        Ok(Self::from_options(
+            ScriptOptions::from_file(content, path.clone())
+                .with_globals(self.globals.clone())
+                .with_modules(self.modules.clone())
+                .with_imports(self.imports.clone()),
+        ))
+    }
+
     pub(crate) fn content(&self) -> &str {
         &self.content
     }
 
+    pub(crate) fn shared_content(&self) -> Arc<str> {
+        self.content.clone()
+    }
+
     pub(crate) fn filename(&self) -> Option<&Path> {
         match &self.kind {
-            ScriptSourceKind::Inline => None,
+            ScriptSourceKind::Inline { .. } => None,
             ScriptSourceKind::File { path } => Some(path),
         }
     }
 
+    /// Caller-supplied module file name for inline scripts.
+    pub(crate) fn inline_name(&self) -> Option<&str> {
+        match &self.kind {
+            ScriptSourceKind::Inline { name } => name.as_deref(),
+            ScriptSourceKind::File { .. } => None,
+        }
+    }
+
     pub(crate) fn media_type(&self) -> deno_ast::MediaType {
         self.media_type
     }
 
-    pub(crate) fn execution_content(&self) -> String {
-        if matches!(&self.kind, ScriptSourceKind::Inline) && self.media_type == MediaType::Tsx {
+    /// Shares the bound source unless inline TSX needs its JSX pragma prepended.
+    pub(crate) fn execution_content(&self) -> Arc<str> {
+        if matches!(&self.kind, ScriptSourceKind::Inline { .. })
+            && self.media_type == MediaType::Tsx
+        {
             format!(
                 "/** @jsxRuntime automatic */\n/** @jsxImportSource {INLINE_REACT_IMPORT_SOURCE} */\n{}",
                 self.content,
             )
+            .into()
         } else {
             self.content.clone()
         }
@@ -81,16 +132,86 @@ impl ScriptSource {
         self.run_signature.as_ref()
     }
 
+    pub(crate) fn globals(&self) -> Option<&PyJsValue> {
+        self.globals.as_ref()
+    }
+
+    pub(crate) fn modules(&self) -> &BTreeMap<String, ModuleSource> {
+        &self.modules
+    }
+
+    /// Import map entries from bare specifiers (or `prefix/` entries) to their targets.
+    pub(crate) fn imports(&self) -> &BTreeMap<String, String> {
+        &self.imports
+    }
+
     pub(crate) fn description(&self) -> String {
         match &self.kind {
             ScriptSourceKind::File { path } => format!("file script at {}", path.display()),
-            ScriptSourceKind::Inline => {
+            ScriptSourceKind::Inline { name: None } => {
                 format!("inline script ({} bytes)", self.content().len())
             }
+            ScriptSourceKind::Inline { name: Some(name) } => {
+                format!("inline script {name:?} ({} bytes)", self.content().len())
+            }
         }
     }
 }
 
+/// Parses inline `content` the way scripts are bound, without running it. TypeScript is tried
+/// first and TSX second, so a failure reports the TypeScript diagnostic.
+pub(crate) fn check_script(content: &str) -> Result<(), BindingError> {
+    let diagnostic = match signature::try_parse_script_module(content, MediaType::TypeScript) {
+        Ok(_) => return Ok(()),
+        Err(diagnostic) => diagnostic,
+    };
+    if signature::parse_script_module(content, MediaType::Tsx).is_some() {
+        return Ok(());
+    }
+    let position = diagnostic.display_position();
+    let source_line = content
+        .lines()
+        .nth(position.line_number.saturating_sub(1))
+        .unwrap_or_default()
+        .to_string();
+    Err(BindingError::syntax_at(
+        format!(
+            "{} at line {}, column {}\n\n  {source_line}",
+            diagnostic.message(),
+            position.line_number,
+            position.column_number,
+        ),
+        SyntaxErrorLocation {
+            line: position.line_number,
+            column: position.column_number,
+            source_line,
+        },
+    ))
+}
+
+/// Checks `content` and strips its TypeScript so the runner can `eval` it as plain JavaScript.
+/// `None` when the code needs a module of its own: it parses only as TSX or reaches for `import`.
+pub(crate) fn transpile_eval_source(content: &str) -> Result<Option<String>, BindingError> {
+    check_script(content)?;
+    if content.contains("import") {
+        return Ok(None);
+    }
+    let Ok(parsed) = signature::try_parse_script_module(content, MediaType::TypeScript) else {
+        return Ok(None);
+    };
+    let transpiled = parsed
+        .transpile(
+            &deno_ast::TranspileOptions::default(),
+            &deno_ast::TranspileModuleOptions { module_kind: None },
+            &deno_ast::EmitOptions {
+                source_map: SourceMapOption::None,
+                ..Default::default()
+            },
+        )
+        .map_err(|error| BindingError::syntax(error.to_string()))?;
+    Ok(Some(transpiled.into_source().text))
+}
+
 fn parsed_source(
     content: &str,
     path: Option<&Path>,
@@ -133,6 +254,47 @@ mod tests {
         assert_eq!(source.description(), "inline script (30 bytes)");
     }
 
+    #[test]
+    fn clones_share_script_content() {
+        let source = ScriptSource::from_options(ScriptOptions::inline(
+            "export default () => 'shared';".to_string(),
+        ));
+
+        let bound = source.clone();
+
+        assert!(std::ptr::eq(source.content(), bound.content()));
+    }
+
+    #[test]
+    fn keeps_caller_supplied_names_for_inline_sources() {
+        let source = ScriptSource::from_options(
+            ScriptOptions::inline("export default () => 1;".to_string())
+                .with_name(Some("widget".to_string())),
+        );
+
+        assert_eq!(source.inline_name(), Some("widget"));
+        assert_eq!(source.filename(), None);
+        assert_eq!(source.description(), "inline script \"widget\" (23 bytes)");
+    }
+
+    #[test]
+    fn transpiles_eval_sources_that_a_runner_can_eval() {
+        let transpiled =
+            super::transpile_eval_source("(function (): number { return 1 as number; });")
+                .expect("typed code should transpile")
+                .expect("typed code should not need a module");
+
+        assert!(transpiled.starts_with("(function"));
+        assert!(!transpiled.contains("number"));
+        assert_eq!(
+            super::transpile_eval_source("(() => import(\"npm:is-number\"))").ok(),
+            Some(None)
+        );
+        assert!(
+            super::transpile_eval_source("(function () { return ({ const a = 1; a }); })").is_err()
+        );
+    }
+
     #[test]
     fn creates_file_sources_from_file_options() {
         let path = PathBuf::from("/tmp/belgie/scripts/main.ts");
@@ -1,12 +1,80 @@
+use std::time::Duration;
+
 #[derive(Clone, Debug)]
 pub(crate) enum BindingError {
-    Runtime { message: String },
-    ModuleLoad { message: String },
-    MissingRunExport { context: String },
-    NonFunctionRunExport { context: String },
-    JavaScript { message: String },
-    ValueConversion { message: String },
-    Argument { message: String },
+    Runtime {
+        message: String,
+    },
+    ModuleLoad {
+        message: String,
+    },
+    /// Script or module source that does not parse.
+    Syntax {
+        message: String,
+        location: Option<Box<SyntaxErrorLocation>>,
+    },
+    /// A runner or runtime session used after it was closed.
+    Closed {
+        message: String,
+    },
+    MissingRunExport {
+        context: String,
+    },
+    NonFunctionRunExport {
+        context: String,
+    },
+    JavaScript {
+        message: String,
+        details: Option<Box<JavaScriptErrorDetails>>,
+    },
+    ValueConversion {
+        message: String,
+    },
+    Argument {
+        message: String,
+    },
+    Timeout {
+        timeout: Duration,
+    },
+    /// A call that spent more CPU time than `RuntimeOptions(cpu_budget=...)` allows.
+    CpuBudgetExceeded {
+        budget: Duration,
+    },
+    /// A return value whose serialized size exceeds `RuntimeOptions(max_result_bytes=...)`.
+    ResultTooLarge {
+        size: usize,
+        limit: usize,
+    },
+}
+
+/// Where a parse diagnostic points, with 1-based line and column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SyntaxErrorLocation {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) source_line: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct JavaScriptErrorDetails {
+    pub(crate) name: Option<String>,
+    pub(crate) message: Option<String>,
+    pub(crate) stack: Option<String>,
+    pub(crate) filename: Option<String>,
+    pub(crate) line: Option<i64>,
+    pub(crate) column: Option<i64>,
+    pub(crate) source_line: Option<String>,
+    pub(crate) request_id: Option<String>,
+    /// The thrown error's call stack, innermost frame first.
+    pub(crate) frames: Vec<JavaScriptStackFrame>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct JavaScriptStackFrame {
+    pub(crate) file: Option<String>,
+    pub(crate) line: Option<i64>,
+    pub(crate) column: Option<i64>,
+    pub(crate) function: Option<String>,
 }
 
 impl BindingError {
@@ -22,6 +90,26 @@ impl BindingError {
         }
     }
 
+    pub(crate) fn syntax(message: impl Into<String>) -> Self {
+        Self::Syntax {
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    pub(crate) fn syntax_at(message: impl Into<String>, location: SyntaxErrorLocation) -> Self {
+        Self::Syntax {
+            message: message.into(),
+            location: Some(Box::new(location)),
+        }
+    }
+
+    pub(crate) fn closed(message: impl Into<String>) -> Self {
+        Self::Closed {
+            message: message.into(),
+        }
+    }
+
     pub(crate) fn missing_run_export(context: impl Into<String>) -> Self {
         Self::MissingRunExport {
             context: context.into(),
@@ -37,6 +125,17 @@ impl BindingError {
     pub(crate) fn javascript(message: impl Into<String>) -> Self {
         Self::JavaScript {
             message: message.into(),
+            details: None,
+        }
+    }
+
+    pub(crate) fn javascript_with_details(
+        message: impl Into<String>,
+        details: JavaScriptErrorDetails,
+    ) -> Self {
+        Self::JavaScript {
+            message: message.into(),
+            details: Some(Box::new(details)),
         }
     }
 
@@ -52,19 +151,59 @@ impl BindingError {
         }
     }
 
+    pub(crate) fn timeout(timeout: Duration) -> Self {
+        Self::Timeout { timeout }
+    }
+
+    pub(crate) fn cpu_budget_exceeded(budget: Duration) -> Self {
+        Self::CpuBudgetExceeded { budget }
+    }
+
+    pub(crate) fn result_too_large(size: usize, limit: usize) -> Self {
+        Self::ResultTooLarge { size, limit }
+    }
+
+    /// Attaches `request_id` to a JavaScript error; other errors are returned unchanged.
+    pub(crate) fn with_request_id(self, request_id: Option<&str>) -> Self {
+        match (self, request_id) {
+            (Self::JavaScript { message, details }, Some(request_id)) => {
+                let mut details = details.unwrap_or_default();
+                details.request_id = Some(request_id.to_string());
+                Self::JavaScript {
+                    message,
+                    details: Some(details),
+                }
+            }
+            (error, _) => error,
+        }
+    }
+
     pub(crate) fn message(&self) -> String {
         match self {
             Self::Runtime { message } => message.clone(),
             Self::ModuleLoad { message } => message.clone(),
+            Self::Syntax { message, .. } => message.clone(),
+            Self::Closed { message } => message.clone(),
             Self::MissingRunExport { context } => {
                 format!("Script does not export a callable run function: {context}")
             }
             Self::NonFunctionRunExport { context } => {
                 format!("Script run export is not callable: {context}")
             }
-            Self::JavaScript { message } => message.clone(),
+            Self::JavaScript { message, .. } => message.clone(),
             Self::ValueConversion { message } => message.clone(),
             Self::Argument { message } => message.clone(),
+            Self::Timeout { timeout } => format!(
+                "Deno execution timed out after {} seconds",
+                timeout.as_secs_f64()
+            ),
+            Self::CpuBudgetExceeded { budget } => format!(
+                "Deno execution exceeded its CPU budget of {} seconds",
+                budget.as_secs_f64()
+            ),
+            Self::ResultTooLarge { size, limit } => format!(
+                "Script result is {size} bytes, over the {limit} byte max_result_bytes limit"
+            ),
         }
     }
 }
@@ -1,22 +1,23 @@
-use deno_core::{
-    serde_json::{Map, Value},
-    v8,
-};
+use deno_core::v8;
 use pyo3::{
     Bound, Py, PyAny, PyResult,
     types::{PyAnyMethods, PyDict, PyDictMethods, PyTuple, PyTupleMethods},
 };
 
 use crate::script::{ParamPattern, RunSignature};
-use crate::types::{error::BindingError, value::PyJsValue};
+use crate::types::{
+    error::BindingError,
+    value::{PyJsValue, is_tagged_value},
+};
 
 pub(crate) type SyncRunnerResult = PyResult<Py<PyAny>>;
 pub(crate) type AsyncRunnerResult = PyResult<Py<PyAny>>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub(crate) struct RunnerArguments {
     positional: Vec<PyJsValue>,
-    keyword: Map<String, Value>,
+    keyword: Vec<(String, PyJsValue)>,
+    request_id: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -29,7 +30,7 @@ pub(crate) enum CallArgument {
 enum SlotState {
     Empty,
     Value(PyJsValue),
-    Object(Map<String, Value>),
+    Object(Vec<(String, PyJsValue)>),
 }
 
 impl RunnerArguments {
@@ -41,21 +42,29 @@ impl RunnerArguments {
             .iter()
             .map(|value| PyJsValue::from_py(&value))
             .collect::<PyResult<Vec<_>>>()?;
-        let mut keyword = Map::new();
+        let mut keyword = Vec::new();
         if let Some(kwargs) = kwargs {
             for (key, value) in kwargs.iter() {
-                keyword.insert(
-                    key.extract::<String>()?,
-                    PyJsValue::from_py(&value)?.as_json().clone(),
-                );
+                keyword.push((key.extract::<String>()?, PyJsValue::from_py(&value)?));
             }
         }
         Ok(Self {
             positional,
             keyword,
+            request_id: None,
         })
     }
 
+    pub(crate) fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+
+    /// The caller's correlation id, attached to the call's console messages and errors.
+    pub(crate) fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
     #[cfg(test)]
     pub(crate) fn is_empty(&self) -> bool {
         self.positional.is_empty() && self.keyword.is_empty()
@@ -81,7 +90,7 @@ impl RunnerArguments {
     fn legacy_values_for_call(&self) -> Vec<PyJsValue> {
         let mut values = self.positional.clone();
         if !self.keyword.is_empty() {
-            values.push(PyJsValue::from_json(Value::Object(self.keyword.clone())));
+            values.push(PyJsValue::Object(self.keyword.clone()));
         }
         values
     }
@@ -105,7 +114,7 @@ impl RunnerArguments {
             if let Some(index) = find_ident_param_index(&slots, signature, name, |slot| {
                 matches!(slot, SlotState::Empty)
             }) {
-                assign_slot_value(&mut slots[index], PyJsValue::from_json(value.clone()))?;
+                assign_slot_value(&mut slots[index], value.clone())?;
                 continue;
             }
 
@@ -194,20 +203,17 @@ fn assign_positional_args(
             )));
         }
         if matches!(signature.params[index], ParamPattern::Rest(_)) {
-            let rest_values: Vec<Value> = positional[index..]
-                .iter()
-                .map(|value| value.as_json().clone())
-                .collect();
             assign_slot_value(
                 &mut slots[index],
-                PyJsValue::from_json(Value::Array(rest_values)),
+                PyJsValue::Array(positional[index..].to_vec()),
             )?;
             return Ok(());
         }
         if matches!(signature.params[index], ParamPattern::Object { .. })
-            && let Value::Object(object) = positional[index].as_json().clone()
+            && !is_tagged_value(&positional[index])
+            && let PyJsValue::Object(object) = &positional[index]
         {
-            slots[index] = SlotState::Object(object);
+            slots[index] = SlotState::Object(object.clone());
             index += 1;
             continue;
         }
@@ -222,7 +228,7 @@ fn apply_single_param_keyword(
     slot: &mut SlotState,
     param: &ParamPattern,
     name: &str,
-    value: Value,
+    value: PyJsValue,
 ) -> Result<(), BindingError> {
     match param {
         ParamPattern::Ident {
@@ -230,7 +236,7 @@ fn apply_single_param_keyword(
             accepts_object_fields,
         } => {
             if ident == name {
-                assign_slot_value(slot, PyJsValue::from_json(value))
+                assign_slot_value(slot, value)
             } else if *accepts_object_fields {
                 merge_into_slot_object(slot, name, value)
             } else {
@@ -245,27 +251,30 @@ fn apply_single_param_keyword(
 }
 
 fn insert_unique_field(
-    object: &mut Map<String, Value>,
+    object: &mut Vec<(String, PyJsValue)>,
     name: &str,
-    value: Value,
+    value: PyJsValue,
 ) -> Result<(), BindingError> {
-    if object.contains_key(name) {
+    if object.iter().any(|(key, _)| key == name) {
         return Err(BindingError::argument(format!(
             "run() got multiple values for argument '{name}'"
         )));
     }
-    object.insert(name.to_string(), value);
+    object.push((name.to_string(), value));
     Ok(())
 }
 
 fn merge_into_slot_object(
     slot: &mut SlotState,
     name: &str,
-    value: Value,
+    value: PyJsValue,
 ) -> Result<(), BindingError> {
     match slot {
         SlotState::Value(existing) => {
-            if let Value::Object(mut object) = existing.as_json().clone() {
+            if !is_tagged_value(existing)
+                && let PyJsValue::Object(object) = existing
+            {
+                let mut object = object.clone();
                 insert_unique_field(&mut object, name, value)?;
                 *slot = SlotState::Object(object);
                 Ok(())
@@ -322,10 +331,14 @@ fn find_object_key_slot(
     None
 }
 
-fn merge_object_field(slot: &mut SlotState, name: &str, value: Value) -> Result<(), BindingError> {
+fn merge_object_field(
+    slot: &mut SlotState,
+    name: &str,
+    value: PyJsValue,
+) -> Result<(), BindingError> {
     match slot {
         SlotState::Empty => {
-            let mut object = Map::new();
+            let mut object = Vec::new();
             insert_unique_field(&mut object, name, value)?;
             *slot = SlotState::Object(object);
             Ok(())
@@ -340,13 +353,13 @@ fn merge_object_field(slot: &mut SlotState, name: &str, value: Value) -> Result<
 fn merge_overflow_keyword(
     slot: &mut SlotState,
     name: &str,
-    value: Value,
+    value: PyJsValue,
 ) -> Result<(), BindingError> {
     match slot {
         SlotState::Empty | SlotState::Object(_) => merge_object_field(slot, name, value),
         SlotState::Value(existing) => {
-            let mut object = match existing.as_json() {
-                Value::Object(object) => object.clone(),
+            let mut object = match &*existing {
+                PyJsValue::Object(object) if !is_tagged_value(existing) => object.clone(),
                 _ => {
                     return Err(BindingError::argument(
                         "run() overflow argument must be an object",
@@ -382,13 +395,11 @@ fn finalize_slots(
     for (index, slot) in slots[..=last_index].iter().enumerate() {
         arguments.push(match slot {
             SlotState::Empty if matches!(signature.params[index], ParamPattern::Rest(_)) => {
-                CallArgument::Value(PyJsValue::from_json(Value::Array(vec![])))
+                CallArgument::Value(PyJsValue::Array(Vec::new()))
             }
             SlotState::Empty => CallArgument::Undefined,
             SlotState::Value(value) => CallArgument::Value(value.clone()),
-            SlotState::Object(object) => {
-                CallArgument::Value(PyJsValue::from_json(Value::Object(object.clone())))
-            }
+            SlotState::Object(object) => CallArgument::Value(PyJsValue::Object(object.clone())),
         });
     }
     Ok(arguments)
@@ -409,8 +420,8 @@ fn expand_call_arguments_for_v8(
     let extra_capacity = call_arguments
         .get(rest_index)
         .and_then(|argument| match argument {
-            CallArgument::Value(value) => match value.as_json() {
-                Value::Array(elements) => Some(elements.len().saturating_sub(1)),
+            CallArgument::Value(value) => match value {
+                PyJsValue::Array(elements) => Some(elements.len().saturating_sub(1)),
                 _ => None,
             },
             CallArgument::Undefined => None,
@@ -422,15 +433,11 @@ fn expand_call_arguments_for_v8(
         if index == rest_index
             && let CallArgument::Value(value) = argument
         {
-            match value.into_json() {
-                Value::Array(elements) => {
-                    expanded.extend(
-                        elements
-                            .into_iter()
-                            .map(|element| CallArgument::Value(PyJsValue::from_json(element))),
-                    );
+            match value {
+                PyJsValue::Array(elements) => {
+                    expanded.extend(elements.into_iter().map(CallArgument::Value));
                 }
-                other => expanded.push(CallArgument::Value(PyJsValue::from_json(other))),
+                other => expanded.push(CallArgument::Value(other)),
             }
             continue;
         }
@@ -469,7 +476,11 @@ mod tests {
     ) -> RunnerArguments {
         RunnerArguments {
             positional: positional.into_iter().map(PyJsValue::from_json).collect(),
-            keyword: keywords,
+            keyword: keywords
+                .into_iter()
+                .map(|(key, value)| (key, PyJsValue::from_json(value)))
+                .collect(),
+            request_id: None,
         }
     }
 
@@ -479,7 +490,7 @@ mod tests {
         call_arguments
             .into_iter()
             .map(|argument| match argument {
-                CallArgument::Value(value) => value.as_json().clone(),
+                CallArgument::Value(value) => value.to_json(),
                 CallArgument::Undefined => Value::Null,
             })
             .collect()
@@ -874,10 +885,10 @@ mod tests {
             ident_param("first", false),
             ident_param("options", false),
         ]);
-        let arguments = RunnerArguments {
-            positional: vec![PyJsValue::from_json(Value::Number(1.into()))],
-            keyword: Map::from_iter([("first".to_string(), Value::Number(2.into()))]),
-        };
+        let arguments = runner_arguments_from(
+            vec![Value::Number(1.into())],
+            Map::from_iter([("first".to_string(), Value::Number(2.into()))]),
+        );
         let error = arguments
             .values_for_call(Some(&sig))
             .expect_err("duplicate keyword should fail");
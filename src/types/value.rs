@@ -1,15 +1,16 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt::Write};
 
 use deno_core::{
     serde_json::{Map, Number, Value},
-    serde_v8, v8,
+    v8,
 };
 use pyo3::{
     Bound, Py, PyAny, PyResult, Python,
     conversion::IntoPyObjectExt,
     exceptions::{PyTypeError, PyValueError},
     types::{
-        PyAnyMethods, PyDict, PyDictMethods, PyFloat, PyInt, PyList, PyListMethods, PyString,
+        PyAnyMethods, PyByteArray, PyByteArrayMethods, PyBytes, PyBytesMethods, PyDict,
+        PyDictMethods, PyFloat, PyInt, PyList, PyListMethods, PyModule, PySet, PyString,
         PyStringMethods, PyTuple, PyTupleMethods, PyTypeMethods,
     },
 };
@@ -17,51 +18,183 @@ use pyo3::{
 use crate::types::error::BindingError;
 
 const MAX_SAFE_INTEGER: i64 = 9_007_199_254_740_991;
+/// Reserved object key that carries binary data as hex in the JSON form from `to_json`.
+const BYTES_KEY: &str = "\u{0}belgie:bytes";
+/// Carries a `Date` as milliseconds since the Unix epoch.
+const DATE_KEY: &str = "\u{0}belgie:date";
+/// Carries a `Set` as an array of its values in insertion order.
+const SET_KEY: &str = "\u{0}belgie:set";
+/// Carries a `Map` as an array of `[key, value]` pairs in insertion order.
+const MAP_KEY: &str = "\u{0}belgie:map";
+const RESERVED_KEYS: [(&str, &str); 4] = [
+    (BYTES_KEY, "binary data"),
+    (DATE_KEY, "dates"),
+    (SET_KEY, "sets"),
+    (MAP_KEY, "maps"),
+];
 
+/// What reading a JavaScript value does with a function, symbol, BigInt, RegExp, non-finite
+/// number, or cycle, none of which has a JSON form.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum Unserializable {
+    #[default]
+    Error,
+    Null,
+    Stringify,
+}
+
+impl Unserializable {
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        match name {
+            "error" => Some(Self::Error),
+            "null" => Some(Self::Null),
+            "stringify" => Some(Self::Stringify),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Null => "null",
+            Self::Stringify => "stringify",
+        }
+    }
+}
+
+/// A value crossing between Python and JavaScript: JSON plus binary data, which stays raw
+/// bytes all the way to a `Uint8Array` or `bytes`. Dates, sets, and maps are objects holding
+/// only their reserved key.
 #[derive(Clone, Debug, PartialEq)]
-pub struct PyJsValue {
-    inner: Value,
+pub enum PyJsValue {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<PyJsValue>),
+    /// Entries in insertion order, each key at most once.
+    Object(Vec<(String, PyJsValue)>),
+    Bytes(Vec<u8>),
 }
 
 impl PyJsValue {
+    /// Reads the JSON form from `to_json`, so hex under the binary data key becomes bytes again.
     pub(crate) fn from_json(value: Value) -> Self {
-        Self { inner: value }
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(value) => Self::Bool(value),
+            Value::Number(number) => Self::Number(number),
+            Value::String(value) => Self::String(value),
+            Value::Array(values) => Self::Array(values.into_iter().map(Self::from_json).collect()),
+            Value::Object(values) => {
+                if let Some(bytes) = bytes_payload(&values).and_then(decode_bytes) {
+                    return Self::Bytes(bytes);
+                }
+                Self::Object(
+                    values
+                        .into_iter()
+                        .map(|(key, value)| (key, Self::from_json(value)))
+                        .collect(),
+                )
+            }
+        }
     }
 
-    pub(crate) fn as_json(&self) -> &Value {
-        &self.inner
+    /// The value as JSON, with binary data as hex under a reserved key.
+    pub(crate) fn to_json(&self) -> Value {
+        match self {
+            Self::Null => Value::Null,
+            Self::Bool(value) => Value::Bool(*value),
+            Self::Number(number) => Value::Number(number.clone()),
+            Self::String(value) => Value::String(value.clone()),
+            Self::Array(values) => Value::Array(values.iter().map(Self::to_json).collect()),
+            Self::Object(entries) => Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.to_json()))
+                    .collect(),
+            ),
+            Self::Bytes(bytes) => {
+                let mut hex = String::with_capacity(bytes.len() * 2);
+                for byte in bytes {
+                    let _ = write!(hex, "{byte:02x}");
+                }
+                Value::Object(Map::from_iter([(BYTES_KEY.to_string(), Value::String(hex))]))
+            }
+        }
     }
 
-    pub(crate) fn into_json(self) -> Value {
-        self.inner
+    pub(crate) fn as_object(&self) -> Option<&[(String, PyJsValue)]> {
+        match self {
+            Self::Object(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(number) => number.as_f64(),
+            _ => None,
+        }
+    }
+
+    /// Bytes the value takes as compact JSON, counted without building the string. Binary data
+    /// counts as its own length, not its hex form.
+    pub(crate) fn serialized_len(&self) -> usize {
+        let separators = |len: usize| 2 + len.saturating_sub(1);
+        match self {
+            Self::Null | Self::Bool(true) => 4,
+            Self::Bool(false) => 5,
+            Self::Number(number) => json_len(number),
+            Self::String(value) => json_len(value),
+            Self::Array(values) => {
+                separators(values.len()) + values.iter().map(Self::serialized_len).sum::<usize>()
+            }
+            Self::Object(entries) => {
+                separators(entries.len())
+                    + entries
+                        .iter()
+                        .map(|(key, value)| json_len(key) + 1 + value.serialized_len())
+                        .sum::<usize>()
+            }
+            Self::Bytes(bytes) => bytes.len(),
+        }
+    }
+
+    /// Encodes the value as MessagePack. Binary data becomes `bin`, dates the timestamp extension,
+    /// sets arrays, and maps MessagePack maps keyed by their original keys.
+    pub(crate) fn to_msgpack(&self) -> Result<Vec<u8>, BindingError> {
+        let mut output = Vec::new();
+        value_to_msgpack(&mut output, self)?;
+        Ok(output)
     }
 
     pub(crate) fn from_py(value: &Bound<'_, PyAny>) -> PyResult<Self> {
         let mut seen = HashSet::new();
-        Ok(Self::from_json(Self::value_from_py(value, "$", &mut seen)?))
+        Self::value_from_py(value, "$", &mut seen)
     }
 
     fn value_from_py(
         value: &Bound<'_, PyAny>,
         path: &str,
         seen: &mut HashSet<usize>,
-    ) -> PyResult<Value> {
+    ) -> PyResult<Self> {
         if value.is_none() {
-            return Ok(Value::Null);
+            return Ok(Self::Null);
         }
         if let Ok(value) = value.extract::<bool>() {
-            return Ok(Value::Bool(value));
+            return Ok(Self::Bool(value));
         }
         if value.cast::<PyInt>().is_ok() {
             if let Ok(value) = value.extract::<i64>()
                 && (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&value)
             {
-                return Ok(Value::Number(Number::from(value)));
+                return Ok(Self::Number(Number::from(value)));
             }
             if let Ok(value) = value.extract::<u64>()
                 && value <= MAX_SAFE_INTEGER as u64
             {
-                return Ok(Value::Number(Number::from(value)));
+                return Ok(Self::Number(Number::from(value)));
             }
             return Err(PyValueError::new_err(format!(
                 "Python int at {path} must be within the JavaScript safe integer range",
@@ -74,14 +207,20 @@ impl PyJsValue {
                     "Python float at {path} must be finite to pass as JSON",
                 )));
             }
-            return Number::from_f64(number).map(Value::Number).ok_or_else(|| {
+            return Number::from_f64(number).map(Self::Number).ok_or_else(|| {
                 PyValueError::new_err(format!(
                     "Could not convert Python float at {path} to a JSON number",
                 ))
             });
         }
         if value.cast::<PyString>().is_ok() {
-            return Ok(Value::String(value.extract::<String>()?));
+            return Ok(Self::String(value.extract::<String>()?));
+        }
+        if let Ok(bytes) = value.cast::<PyBytes>() {
+            return Ok(Self::Bytes(bytes.as_bytes().to_vec()));
+        }
+        if let Ok(bytes) = value.cast::<PyByteArray>() {
+            return Ok(Self::Bytes(bytes.to_vec()));
         }
         if let Ok(dict) = value.cast::<PyDict>() {
             let id = value.as_ptr() as usize;
@@ -90,16 +229,22 @@ impl PyJsValue {
                     "Cannot pass Python data structure cycle as JSON at {path}",
                 )));
             }
-            let mut object = Map::new();
+            let mut object = Vec::with_capacity(dict.len());
             for (key, value) in dict.iter() {
                 let key = key.extract::<String>().map_err(|_| {
                     PyTypeError::new_err(format!("JSON object keys must be strings at {path}",))
                 })?;
+                if let Some(purpose) = reserved_key_purpose(&key) {
+                    return Err(PyValueError::new_err(format!(
+                        "JSON object key at {path} is reserved for {purpose}",
+                    )));
+                }
                 let item_path = object_path(path, &key);
-                object.insert(key, Self::value_from_py(&value, &item_path, seen)?);
+                let value = Self::value_from_py(&value, &item_path, seen)?;
+                object.push((key, value));
             }
             seen.remove(&id);
-            return Ok(Value::Object(object));
+            return Ok(Self::Object(object));
         }
         if let Ok(list) = value.cast::<PyList>() {
             let id = value.as_ptr() as usize;
@@ -113,7 +258,7 @@ impl PyJsValue {
                 array.push(Self::value_from_py(&value, &array_path(path, index), seen)?);
             }
             seen.remove(&id);
-            return Ok(Value::Array(array));
+            return Ok(Self::Array(array));
         }
         if let Ok(tuple) = value.cast::<PyTuple>() {
             let id = value.as_ptr() as usize;
@@ -127,7 +272,11 @@ impl PyJsValue {
                 array.push(Self::value_from_py(&value, &array_path(path, index), seen)?);
             }
             seen.remove(&id);
-            return Ok(Value::Array(array));
+            return Ok(Self::Array(array));
+        }
+        let datetime = value.py().import("datetime")?;
+        if value.is_instance(&datetime.getattr("datetime")?)? {
+            return date_from_py(value, &datetime);
         }
 
         let type_name = value.get_type().name()?.to_string_lossy().into_owned();
@@ -137,51 +286,376 @@ impl PyJsValue {
     }
 
     pub(crate) fn to_py(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
-        json_to_py(py, &self.inner)
+        value_to_py(py, self)
     }
 
     pub(crate) fn to_v8<'s, 'i>(
         &self,
         scope: &mut v8::PinScope<'s, 'i>,
     ) -> Result<v8::Local<'s, v8::Value>, BindingError> {
-        serde_v8::to_v8(scope, &self.inner).map_err(|error| {
-            BindingError::value_conversion(format!("Could not convert JSON value to V8: {error}",))
-        })
+        value_to_v8(scope, self)
     }
 
     pub(crate) fn from_v8<'s, 'i>(
         scope: &mut v8::PinScope<'s, 'i>,
         value: v8::Local<'s, v8::Value>,
     ) -> Result<Self, BindingError> {
-        let mut seen = Vec::new();
-        Ok(Self::from_json(value_from_v8(
-            scope, value, "$", &mut seen,
-        )?))
+        Self::from_v8_with(scope, value, Unserializable::Error)
+    }
+
+    /// Like `from_v8`, but replaces values without a JSON form as `unserializable` says.
+    pub(crate) fn from_v8_with<'s, 'i>(
+        scope: &mut v8::PinScope<'s, 'i>,
+        value: v8::Local<'s, v8::Value>,
+        unserializable: Unserializable,
+    ) -> Result<Self, BindingError> {
+        let mut conversion = V8Conversion {
+            seen: Vec::new(),
+            unserializable,
+        };
+        value_from_v8(scope, value, "$", &mut conversion)
+    }
+}
+
+/// Length of `value` as compact JSON.
+fn json_len(value: &(impl serde::Serialize + ?Sized)) -> usize {
+    struct Counter(usize);
+
+    impl std::io::Write for Counter {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0 += bytes.len();
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    // Writing a number or string to an infallible writer cannot fail.
+    let _ = deno_core::serde_json::to_writer(&mut counter, value);
+    counter.0
+}
+
+struct V8Conversion {
+    /// Objects being read, with the path each was first reached at.
+    seen: Vec<(v8::Global<v8::Object>, String)>,
+    unserializable: Unserializable,
+}
+
+impl V8Conversion {
+    fn unserializable(
+        &self,
+        message: String,
+        stringified: impl FnOnce() -> String,
+    ) -> Result<PyJsValue, BindingError> {
+        match self.unserializable {
+            Unserializable::Error => Err(BindingError::value_conversion(message)),
+            Unserializable::Null => Ok(PyJsValue::Null),
+            Unserializable::Stringify => Ok(PyJsValue::String(stringified())),
+        }
+    }
+}
+
+/// Reports whether `value` is binary data, a date, a set, or a map rather than a plain object.
+pub(crate) fn is_tagged_value(value: &PyJsValue) -> bool {
+    matches!(value, PyJsValue::Bytes(_))
+        || RESERVED_KEYS
+            .iter()
+            .any(|(key, _)| tag_payload(value, key).is_some())
+}
+
+fn reserved_key_purpose(key: &str) -> Option<&'static str> {
+    RESERVED_KEYS
+        .iter()
+        .find(|(reserved, _)| *reserved == key)
+        .map(|(_, purpose)| *purpose)
+}
+
+fn tag_payload<'v>(value: &'v PyJsValue, key: &str) -> Option<&'v PyJsValue> {
+    match value.as_object()? {
+        [(tag, payload)] if tag == key => Some(payload),
+        _ => None,
+    }
+}
+
+fn tagged_value(key: &str, payload: PyJsValue) -> PyJsValue {
+    PyJsValue::Object(vec![(key.to_string(), payload)])
+}
+
+fn bytes_payload(object: &Map<String, Value>) -> Option<&str> {
+    if object.len() != 1 {
+        return None;
     }
+    object.get(BYTES_KEY)?.as_str()
 }
 
-type SeenV8Objects = Vec<v8::Global<v8::Object>>;
+/// The limited Python API has no datetime types, so dates go through the `datetime` module.
+fn unix_epoch<'py>(datetime: &Bound<'py, PyModule>) -> PyResult<Bound<'py, PyAny>> {
+    let kwargs = PyDict::new(datetime.py());
+    kwargs.set_item("tzinfo", datetime.getattr("timezone")?.getattr("utc")?)?;
+    datetime
+        .getattr("datetime")?
+        .call((1970, 1, 1), Some(&kwargs))
+}
+
+/// Naive datetimes are read as UTC; anything finer than a millisecond is dropped.
+fn date_from_py(
+    value: &Bound<'_, PyAny>,
+    datetime: &Bound<'_, PyModule>,
+) -> PyResult<PyJsValue> {
+    let py = value.py();
+    let epoch = unix_epoch(datetime)?;
+    let aware = if value.getattr("tzinfo")?.is_none() {
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("tzinfo", datetime.getattr("timezone")?.getattr("utc")?)?;
+        value.call_method("replace", (), Some(&kwargs))?
+    } else {
+        value.clone()
+    };
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("milliseconds", 1)?;
+    let millisecond = datetime.getattr("timedelta")?.call((), Some(&kwargs))?;
+    let milliseconds = aware
+        .sub(&epoch)?
+        .floor_div(&millisecond)?
+        .extract::<i64>()?;
+    Ok(tagged_value(
+        DATE_KEY,
+        PyJsValue::Number(Number::from(milliseconds)),
+    ))
+}
+
+fn date_to_py(py: Python<'_>, milliseconds: f64) -> PyResult<Py<PyAny>> {
+    let datetime = py.import("datetime")?;
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("milliseconds", milliseconds)?;
+    let offset = datetime.getattr("timedelta")?.call((), Some(&kwargs))?;
+    Ok(unix_epoch(&datetime)?.add(offset)?.unbind())
+}
 
-fn json_to_py(py: Python<'_>, value: &Value) -> PyResult<Py<PyAny>> {
+fn tagged_items<'v>(value: &'v PyJsValue, key: &str) -> Option<&'v [PyJsValue]> {
+    match tag_payload(value, key)? {
+        PyJsValue::Array(items) => Some(items),
+        _ => None,
+    }
+}
+
+fn map_pairs(value: &PyJsValue) -> Option<Vec<(&PyJsValue, &PyJsValue)>> {
+    tagged_items(value, MAP_KEY)?
+        .iter()
+        .map(|pair| match pair {
+            PyJsValue::Array(pair) => match pair.as_slice() {
+                [key, value] => Some((key, value)),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn decode_bytes(hex: &str) -> Option<Vec<u8>> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+fn value_to_v8<'s, 'i>(
+    scope: &mut v8::PinScope<'s, 'i>,
+    value: &PyJsValue,
+) -> Result<v8::Local<'s, v8::Value>, BindingError> {
+    if let Some(milliseconds) = tag_payload(value, DATE_KEY).and_then(PyJsValue::as_f64) {
+        return v8::Date::new(scope, milliseconds)
+            .map(Into::into)
+            .ok_or_else(|| BindingError::value_conversion("Could not create Date"));
+    }
+    if let Some(pairs) = map_pairs(value) {
+        let map = v8::Map::new(scope);
+        for (key, value) in pairs {
+            let key = value_to_v8(scope, key)?;
+            let value = value_to_v8(scope, value)?;
+            map.set(scope, key, value)
+                .ok_or_else(|| BindingError::value_conversion("Could not fill Map"))?;
+        }
+        return Ok(map.into());
+    }
+    if let Some(items) = tagged_items(value, SET_KEY) {
+        let items = items
+            .iter()
+            .map(|item| value_to_v8(scope, item))
+            .collect::<Result<Vec<_>, _>>()?;
+        let items = v8::Array::new_with_elements(scope, &items);
+        return new_set(scope, items.into());
+    }
     match value {
-        Value::Null => Ok(py.None()),
-        Value::Bool(value) => (*value).into_py_any(py),
-        Value::Number(value) => number_to_py(py, value),
-        Value::String(value) => value.clone().into_py_any(py),
-        Value::Array(values) => {
+        PyJsValue::Null => Ok(v8::null(scope).into()),
+        PyJsValue::Bool(value) => Ok(v8::Boolean::new(scope, *value).into()),
+        PyJsValue::Number(number) => {
+            Ok(v8::Number::new(scope, number.as_f64().unwrap_or(f64::NAN)).into())
+        }
+        PyJsValue::String(value) => v8::String::new(scope, value)
+            .map(Into::into)
+            .ok_or_else(|| BindingError::value_conversion("Could not create JavaScript string")),
+        PyJsValue::Array(values) => {
+            let elements = values
+                .iter()
+                .map(|value| value_to_v8(scope, value))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(v8::Array::new_with_elements(scope, &elements).into())
+        }
+        PyJsValue::Object(entries) => {
+            let object = v8::Object::new(scope);
+            for (key, value) in entries {
+                let key = v8::String::new(scope, key).ok_or_else(|| {
+                    BindingError::value_conversion("Could not create JavaScript object key")
+                })?;
+                let value = value_to_v8(scope, value)?;
+                // A data property, so a `__proto__` key stays a key instead of a prototype.
+                object
+                    .create_data_property(scope, key.into(), value)
+                    .filter(|created| *created)
+                    .ok_or_else(|| BindingError::value_conversion("Could not fill object"))?;
+            }
+            Ok(object.into())
+        }
+        PyJsValue::Bytes(bytes) => {
+            let length = bytes.len();
+            let store = v8::ArrayBuffer::new_backing_store_from_vec(bytes.clone()).make_shared();
+            let buffer = v8::ArrayBuffer::with_backing_store(scope, &store);
+            v8::Uint8Array::new(scope, buffer, 0, length)
+                .map(Into::into)
+                .ok_or_else(|| BindingError::value_conversion("Could not create Uint8Array"))
+        }
+    }
+}
+
+/// Built through the realm's `Set` constructor, which V8 does not expose directly.
+fn new_set<'s, 'i>(
+    scope: &mut v8::PinScope<'s, 'i>,
+    items: v8::Local<'s, v8::Value>,
+) -> Result<v8::Local<'s, v8::Value>, BindingError> {
+    let name = v8::String::new(scope, "Set")
+        .ok_or_else(|| BindingError::value_conversion("Could not create Set constructor name"))?;
+    let global = scope.get_current_context().global(scope);
+    global
+        .get(scope, name.into())
+        .and_then(|constructor| v8::Local::<v8::Function>::try_from(constructor).ok())
+        .and_then(|constructor| constructor.new_instance(scope, &[items]))
+        .map(Into::into)
+        .ok_or_else(|| BindingError::value_conversion("Could not create Set"))
+}
+
+fn value_to_msgpack(output: &mut Vec<u8>, value: &PyJsValue) -> Result<(), BindingError> {
+    if let Some(milliseconds) = tag_payload(value, DATE_KEY).and_then(PyJsValue::as_f64) {
+        return msgpack_timestamp(output, milliseconds);
+    }
+    if let Some(pairs) = map_pairs(value) {
+        rmp::encode::write_map_len(output, msgpack_len(pairs.len())?).map_err(msgpack_error)?;
+        for (key, value) in pairs {
+            value_to_msgpack(output, key)?;
+            value_to_msgpack(output, value)?;
+        }
+        return Ok(());
+    }
+    if let Some(items) = tagged_items(value, SET_KEY) {
+        return msgpack_array(output, items);
+    }
+    match value {
+        PyJsValue::Null => rmp::encode::write_nil(output).map_err(msgpack_error),
+        PyJsValue::Bool(value) => rmp::encode::write_bool(output, *value).map_err(msgpack_error),
+        PyJsValue::Number(number) => match (number.as_i64(), number.as_u64()) {
+            (Some(value), _) => rmp::encode::write_sint(output, value)
+                .map(drop)
+                .map_err(msgpack_error),
+            (None, Some(value)) => rmp::encode::write_uint(output, value)
+                .map(drop)
+                .map_err(msgpack_error),
+            (None, None) => rmp::encode::write_f64(output, number.as_f64().unwrap_or(f64::NAN))
+                .map_err(msgpack_error),
+        },
+        PyJsValue::String(value) => rmp::encode::write_str(output, value).map_err(msgpack_error),
+        PyJsValue::Array(values) => msgpack_array(output, values),
+        PyJsValue::Object(entries) => {
+            rmp::encode::write_map_len(output, msgpack_len(entries.len())?)
+                .map_err(msgpack_error)?;
+            for (key, value) in entries {
+                rmp::encode::write_str(output, key).map_err(msgpack_error)?;
+                value_to_msgpack(output, value)?;
+            }
+            Ok(())
+        }
+        PyJsValue::Bytes(bytes) => rmp::encode::write_bin(output, bytes).map_err(msgpack_error),
+    }
+}
+
+fn msgpack_array(output: &mut Vec<u8>, items: &[PyJsValue]) -> Result<(), BindingError> {
+    rmp::encode::write_array_len(output, msgpack_len(items.len())?).map_err(msgpack_error)?;
+    items
+        .iter()
+        .try_for_each(|item| value_to_msgpack(output, item))
+}
+
+/// The 96-bit form of the timestamp extension (type -1), which covers every `Date`.
+fn msgpack_timestamp(output: &mut Vec<u8>, milliseconds: f64) -> Result<(), BindingError> {
+    let seconds = (milliseconds / 1000.0).floor();
+    let nanoseconds =
+        (((milliseconds - seconds * 1000.0) * 1_000_000.0).round() as u32).min(999_999_999);
+    rmp::encode::write_ext_meta(output, 12, -1).map_err(msgpack_error)?;
+    output.extend_from_slice(&nanoseconds.to_be_bytes());
+    output.extend_from_slice(&(seconds as i64).to_be_bytes());
+    Ok(())
+}
+
+fn msgpack_len(len: usize) -> Result<u32, BindingError> {
+    u32::try_from(len).map_err(|_| {
+        BindingError::value_conversion("Value has too many items to encode as MessagePack")
+    })
+}
+
+fn msgpack_error(error: impl std::fmt::Display) -> BindingError {
+    BindingError::value_conversion(format!("Could not encode MessagePack: {error}"))
+}
+
+fn value_to_py(py: Python<'_>, value: &PyJsValue) -> PyResult<Py<PyAny>> {
+    if let Some(milliseconds) = tag_payload(value, DATE_KEY).and_then(PyJsValue::as_f64) {
+        return date_to_py(py, milliseconds);
+    }
+    if let Some(pairs) = map_pairs(value) {
+        let dict = PyDict::new(py);
+        for (key, value) in pairs {
+            dict.set_item(value_to_py(py, key)?, value_to_py(py, value)?)?;
+        }
+        return Ok(dict.into_any().unbind());
+    }
+    if let Some(items) = tagged_items(value, SET_KEY) {
+        let items = items
+            .iter()
+            .map(|item| value_to_py(py, item))
+            .collect::<PyResult<Vec<_>>>()?;
+        return Ok(PySet::new(py, items)?.into_any().unbind());
+    }
+    match value {
+        PyJsValue::Null => Ok(py.None()),
+        PyJsValue::Bool(value) => (*value).into_py_any(py),
+        PyJsValue::Number(value) => number_to_py(py, value),
+        PyJsValue::String(value) => value.clone().into_py_any(py),
+        PyJsValue::Array(values) => {
             let values = values
                 .iter()
-                .map(|value| json_to_py(py, value))
+                .map(|value| value_to_py(py, value))
                 .collect::<PyResult<Vec<_>>>()?;
             Ok(PyList::new(py, values)?.into_any().unbind())
         }
-        Value::Object(values) => {
+        PyJsValue::Object(entries) => {
             let dict = PyDict::new(py);
-            for (key, value) in values {
-                dict.set_item(key, json_to_py(py, value)?)?;
+            for (key, value) in entries {
+                dict.set_item(key, value_to_py(py, value)?)?;
             }
             Ok(dict.into_any().unbind())
         }
+        PyJsValue::Bytes(bytes) => Ok(PyBytes::new(py, bytes).into_any().unbind()),
     }
 }
 
@@ -206,13 +680,13 @@ fn value_from_v8<'s, 'i>(
     scope: &mut v8::PinScope<'s, 'i>,
     value: v8::Local<'s, v8::Value>,
     path: &str,
-    seen: &mut SeenV8Objects,
-) -> Result<Value, BindingError> {
+    conversion: &mut V8Conversion,
+) -> Result<PyJsValue, BindingError> {
     if value.is_null_or_undefined() {
-        return Ok(Value::Null);
+        return Ok(PyJsValue::Null);
     }
     if value.is_boolean() {
-        return Ok(Value::Bool(value.boolean_value(scope)));
+        return Ok(PyJsValue::Bool(value.boolean_value(scope)));
     }
     if value.is_number() {
         let number = value.number_value(scope).ok_or_else(|| {
@@ -221,60 +695,119 @@ fn value_from_v8<'s, 'i>(
             )
         })?;
         if !number.is_finite() {
-            return Err(BindingError::value_conversion(format!(
-                "JavaScript number at {path} must be finite to return as JSON",
-            )));
+            return conversion.unserializable(
+                format!("JavaScript number at {path} must be finite to return as JSON"),
+                || {
+                    if number.is_nan() {
+                        "NaN".to_string()
+                    } else if number > 0.0 {
+                        "Infinity".to_string()
+                    } else {
+                        "-Infinity".to_string()
+                    }
+                },
+            );
         }
         if number.fract() == 0.0
             && (-MAX_SAFE_INTEGER as f64..=MAX_SAFE_INTEGER as f64).contains(&number)
         {
-            return Ok(Value::Number(Number::from(number as i64)));
+            return Ok(PyJsValue::Number(Number::from(number as i64)));
         }
-        return Number::from_f64(number).map(Value::Number).ok_or_else(|| {
+        return Number::from_f64(number).map(PyJsValue::Number).ok_or_else(|| {
             BindingError::value_conversion(format!(
                 "Could not convert JavaScript number at {path} to JSON",
             ))
         });
     }
     if value.is_string() {
-        return Ok(Value::String(value.to_rust_string_lossy(scope)));
+        return Ok(PyJsValue::String(value.to_rust_string_lossy(scope)));
     }
     if value.is_big_int() {
-        return Err(BindingError::value_conversion(format!(
-            "Cannot convert JavaScript BigInt at {path} to Python JSON",
-        )));
+        return conversion.unserializable(
+            format!("Cannot convert JavaScript BigInt at {path} to Python JSON"),
+            || value.to_rust_string_lossy(scope),
+        );
     }
     if value.is_symbol() {
-        return Err(BindingError::value_conversion(format!(
-            "Cannot convert JavaScript Symbol at {path} to Python JSON",
-        )));
+        return conversion.unserializable(
+            format!("Cannot convert JavaScript Symbol at {path} to Python JSON"),
+            || {
+                let description = v8::Local::<v8::Symbol>::try_from(value)
+                    .map(|symbol| symbol.description(scope))
+                    .ok()
+                    .filter(|description| !description.is_undefined())
+                    .map(|description| description.to_rust_string_lossy(scope))
+                    .unwrap_or_default();
+                format!("Symbol({description})")
+            },
+        );
     }
     if value.is_function() {
-        return Err(BindingError::value_conversion(format!(
-            "Cannot convert JavaScript function at {path} to Python JSON",
-        )));
+        return conversion.unserializable(
+            format!("Cannot convert JavaScript function at {path} to Python JSON"),
+            || {
+                let name = v8::Local::<v8::Function>::try_from(value)
+                    .map(|function| function.get_name(scope).to_rust_string_lossy(scope))
+                    .unwrap_or_default();
+                if name.is_empty() {
+                    "[Function (anonymous)]".to_string()
+                } else {
+                    format!("[Function {name}]")
+                }
+            },
+        );
     }
     if value.is_date() {
-        return Err(BindingError::value_conversion(format!(
-            "Cannot convert JavaScript Date at {path} to Python JSON",
-        )));
-    }
-    if value.is_map() {
-        return Err(BindingError::value_conversion(format!(
-            "Cannot convert JavaScript Map at {path} to Python JSON",
-        )));
+        let date = v8::Local::<v8::Date>::try_from(value).map_err(|_| {
+            BindingError::value_conversion(format!("Could not convert JavaScript Date at {path}"))
+        })?;
+        let milliseconds = date.value_of();
+        if !milliseconds.is_finite() {
+            return Err(BindingError::value_conversion(format!(
+                "JavaScript Date at {path} is an Invalid Date",
+            )));
+        }
+        return Ok(tagged_value(
+            DATE_KEY,
+            PyJsValue::Number(Number::from(milliseconds as i64)),
+        ));
     }
-    if value.is_set() {
-        return Err(BindingError::value_conversion(format!(
-            "Cannot convert JavaScript Set at {path} to Python JSON",
-        )));
+    if value.is_map() || value.is_set() {
+        return collection_from_v8(scope, value, path, conversion);
     }
     if value.is_reg_exp() {
-        return Err(BindingError::value_conversion(format!(
-            "Cannot convert JavaScript RegExp at {path} to Python JSON",
-        )));
+        return conversion.unserializable(
+            format!("Cannot convert JavaScript RegExp at {path} to Python JSON"),
+            || value.to_rust_string_lossy(scope),
+        );
+    }
+    if value.is_uint8_array() {
+        let view = v8::Local::<v8::ArrayBufferView>::try_from(value).map_err(|_| {
+            BindingError::value_conversion(format!(
+                "Could not convert JavaScript Uint8Array at {path}",
+            ))
+        })?;
+        let mut bytes = vec![0; view.byte_length()];
+        view.copy_contents(&mut bytes);
+        return Ok(PyJsValue::Bytes(bytes));
     }
-    if value.is_array_buffer() || value.is_array_buffer_view() {
+    if value.is_array_buffer() {
+        let buffer = v8::Local::<v8::ArrayBuffer>::try_from(value).map_err(|_| {
+            BindingError::value_conversion(format!(
+                "Could not convert JavaScript ArrayBuffer at {path}",
+            ))
+        })?;
+        let length = buffer.byte_length();
+        let view = v8::Uint8Array::new(scope, buffer, 0, length).ok_or_else(|| {
+            BindingError::value_conversion(format!(
+                "Could not read JavaScript ArrayBuffer at {path}",
+            ))
+        })?;
+        let mut bytes = vec![0; length];
+        view.copy_contents(&mut bytes);
+        return Ok(PyJsValue::Bytes(bytes));
+    }
+    if value.is_array_buffer_view() {
         return Err(BindingError::value_conversion(format!(
             "Cannot convert JavaScript binary data at {path} to Python JSON",
         )));
@@ -286,7 +819,9 @@ fn value_from_v8<'s, 'i>(
         let object = v8::Local::<v8::Object>::try_from(value).map_err(|_| {
             BindingError::value_conversion(format!("Could not convert JavaScript array at {path}",))
         })?;
-        enter_v8_object(scope, object, path, seen)?;
+        if let Some(cycle) = enter_v8_object(scope, object, path, conversion)? {
+            return Ok(cycle);
+        }
         let mut values = Vec::with_capacity(array.length() as usize);
         for index in 0..array.length() {
             let value = array.get_index(scope, index).ok_or_else(|| {
@@ -296,18 +831,18 @@ fn value_from_v8<'s, 'i>(
                 ))
             })?;
             if value.is_undefined() {
-                values.push(Value::Null);
+                values.push(PyJsValue::Null);
             } else {
                 values.push(value_from_v8(
                     scope,
                     value,
                     &array_path(path, index as usize),
-                    seen,
+                    conversion,
                 )?);
             }
         }
-        let _ = seen.pop();
-        return Ok(Value::Array(values));
+        let _ = conversion.seen.pop();
+        return Ok(PyJsValue::Array(values));
     }
     if value.is_object() {
         let object = v8::Local::<v8::Object>::try_from(value).map_err(|_| {
@@ -321,7 +856,9 @@ fn value_from_v8<'s, 'i>(
                 "Only plain JavaScript objects can be returned as Python JSON at {path}; got {constructor_name}",
             )));
         }
-        enter_v8_object(scope, object, path, seen)?;
+        if let Some(cycle) = enter_v8_object(scope, object, path, conversion)? {
+            return Ok(cycle);
+        }
         let keys = object
             .get_own_property_names(
                 scope,
@@ -334,7 +871,7 @@ fn value_from_v8<'s, 'i>(
                     "Could not enumerate JavaScript object at {path}",
                 ))
             })?;
-        let mut values = Map::new();
+        let mut values = Vec::with_capacity(keys.length() as usize);
         for index in 0..keys.length() {
             let key = keys.get_index(scope, index).ok_or_else(|| {
                 BindingError::value_conversion(format!(
@@ -350,13 +887,16 @@ fn value_from_v8<'s, 'i>(
                 continue;
             }
             let key = key.to_rust_string_lossy(scope);
-            values.insert(
-                key.clone(),
-                value_from_v8(scope, value, &object_path(path, &key), seen)?,
-            );
+            if let Some(purpose) = reserved_key_purpose(&key) {
+                return Err(BindingError::value_conversion(format!(
+                    "JavaScript object key at {path} is reserved for {purpose}",
+                )));
+            }
+            let value = value_from_v8(scope, value, &object_path(path, &key), conversion)?;
+            values.push((key, value));
         }
-        let _ = seen.pop();
-        return Ok(Value::Object(values));
+        let _ = conversion.seen.pop();
+        return Ok(PyJsValue::Object(values));
     }
 
     Err(BindingError::value_conversion(format!(
@@ -365,19 +905,71 @@ fn value_from_v8<'s, 'i>(
     )))
 }
 
+/// Reads a `Map` as `[key, value]` pairs or a `Set` as its values, both in insertion order.
+fn collection_from_v8<'s, 'i>(
+    scope: &mut v8::PinScope<'s, 'i>,
+    value: v8::Local<'s, v8::Value>,
+    path: &str,
+    conversion: &mut V8Conversion,
+) -> Result<PyJsValue, BindingError> {
+    let kind = if value.is_map() { "Map" } else { "Set" };
+    let object = v8::Local::<v8::Object>::try_from(value).map_err(|_| {
+        BindingError::value_conversion(format!("Could not convert JavaScript {kind} at {path}"))
+    })?;
+    if let Some(cycle) = enter_v8_object(scope, object, path, conversion)? {
+        return Ok(cycle);
+    }
+    let (entries, is_key_value) = object.preview_entries(scope);
+    let entries = entries.ok_or_else(|| {
+        BindingError::value_conversion(format!("Could not read JavaScript {kind} at {path}"))
+    })?;
+    let mut items = Vec::with_capacity(entries.length() as usize);
+    for index in 0..entries.length() {
+        let entry = entries.get_index(scope, index).ok_or_else(|| {
+            BindingError::value_conversion(format!("Could not read JavaScript {kind} at {path}"))
+        })?;
+        items.push(value_from_v8(
+            scope,
+            entry,
+            &array_path(path, items.len()),
+            conversion,
+        )?);
+    }
+    let _ = conversion.seen.pop();
+    if !is_key_value {
+        return Ok(tagged_value(SET_KEY, PyJsValue::Array(items)));
+    }
+    let pairs = items
+        .chunks_exact(2)
+        .map(|pair| PyJsValue::Array(pair.to_vec()))
+        .collect();
+    Ok(tagged_value(MAP_KEY, PyJsValue::Array(pairs)))
+}
+
 fn enter_v8_object<'s, 'i>(
     scope: &mut v8::PinScope<'s, 'i>,
     object: v8::Local<'s, v8::Object>,
     path: &str,
-    seen: &mut SeenV8Objects,
-) -> Result<(), BindingError> {
-    if seen.iter().any(|seen_object| seen_object == &object) {
-        return Err(BindingError::value_conversion(format!(
-            "Cannot convert JavaScript data structure cycle at {path} to Python JSON",
-        )));
+    conversion: &mut V8Conversion,
+) -> Result<Option<PyJsValue>, BindingError> {
+    if let Some((_, first_path)) = conversion
+        .seen
+        .iter()
+        .find(|(seen_object, _)| seen_object == &object)
+    {
+        return conversion
+            .unserializable(
+                format!(
+                    "Cannot convert JavaScript data structure cycle at {path} to Python JSON; it refers back to {first_path}",
+                ),
+                || format!("[Circular {first_path}]"),
+            )
+            .map(Some);
     }
-    seen.push(v8::Global::new(scope, object));
-    Ok(())
+    conversion
+        .seen
+        .push((v8::Global::new(scope, object), path.to_string()));
+    Ok(None)
 }
 
 fn array_path(path: &str, index: usize) -> String {
@@ -401,28 +993,86 @@ fn object_path(path: &str, key: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{MAX_SAFE_INTEGER, PyJsValue};
+    use super::{
+        BYTES_KEY, DATE_KEY, MAP_KEY, MAX_SAFE_INTEGER, PyJsValue, SET_KEY, tagged_value,
+    };
     use crate::runtime::with_test_js_runtime;
     use deno_core::{
         serde_json::{Map, Number, Value},
         v8,
     };
-    use pyo3::types::{PyAnyMethods, PyDict, PyDictMethods, PyList, PyListMethods, PyTuple};
+    use pyo3::types::{
+        PyAnyMethods, PyBytes, PyDict, PyDictMethods, PyList, PyListMethods, PyTuple,
+    };
+
+    #[test]
+    fn counts_compact_json_bytes() {
+        let value =
+            PyJsValue::from_json(deno_core::serde_json::json!({"html": "<p>é</p>", "n": [1, 2]}));
+
+        assert_eq!(
+            value.serialized_len(),
+            deno_core::serde_json::to_string(&value.to_json())
+                .expect("value should serialize")
+                .len()
+        );
+        assert_eq!(PyJsValue::from_json(Value::Null).serialized_len(), 4);
+        assert_eq!(
+            PyJsValue::Array(vec![PyJsValue::Bytes(vec![0; 10])]).serialized_len(),
+            12
+        );
+    }
+
+    #[test]
+    fn keeps_binary_data_as_bytes_and_hex_only_in_json() {
+        let value = PyJsValue::from_json(deno_core::serde_json::json!({
+            "body": { BYTES_KEY: "00ff" },
+            "bad": { BYTES_KEY: "0" },
+        }));
+
+        let Some([(_, body), (_, bad)]) = value.as_object() else {
+            panic!("value should stay an object");
+        };
+        assert_eq!(body, &PyJsValue::Bytes(vec![0x00, 0xff]));
+        assert!(matches!(bad, PyJsValue::Object(_)));
+        assert_eq!(PyJsValue::from_json(value.to_json()), value);
+    }
+
+    #[test]
+    fn encodes_nested_and_tagged_values_as_msgpack() {
+        let value = PyJsValue::Object(vec![
+            (
+                "n".to_string(),
+                PyJsValue::from_json(deno_core::serde_json::json!([1, -1, 1.5, null])),
+            ),
+            (
+                "when".to_string(),
+                tagged_value(DATE_KEY, PyJsValue::Number(Number::from(1_500))),
+            ),
+        ]);
+
+        let mut expected = vec![0x82, 0xa1, b'n', 0x94, 0x01, 0xff, 0xcb];
+        expected.extend_from_slice(&1.5_f64.to_be_bytes());
+        expected.extend_from_slice(&[0xc0, 0xa4, b'w', b'h', b'e', b'n', 0xc7, 12, 0xff]);
+        expected.extend_from_slice(&500_000_000_u32.to_be_bytes());
+        expected.extend_from_slice(&1_i64.to_be_bytes());
+        assert_eq!(value.to_msgpack().expect("value should encode"), expected);
+    }
 
     #[test]
     fn models_json_primitive_values() {
-        assert_eq!(PyJsValue::from_json(Value::Null).as_json(), &Value::Null);
+        assert_eq!(PyJsValue::from_json(Value::Null), PyJsValue::Null);
         assert_eq!(
-            PyJsValue::from_json(Value::Bool(true)).as_json(),
-            &Value::Bool(true)
+            PyJsValue::from_json(Value::Bool(true)),
+            PyJsValue::Bool(true)
         );
         assert_eq!(
-            PyJsValue::from_json(Value::Number(Number::from(42))).as_json(),
-            &Value::Number(Number::from(42))
+            PyJsValue::from_json(Value::Number(Number::from(42))),
+            PyJsValue::Number(Number::from(42))
         );
         assert_eq!(
-            PyJsValue::from_json(Value::String("deno".to_string())).as_json(),
-            &Value::String("deno".to_string())
+            PyJsValue::from_json(Value::String("deno".to_string())),
+            PyJsValue::String("deno".to_string())
         );
     }
 
@@ -434,7 +1084,7 @@ mod tests {
             Value::Null,
         ]));
 
-        assert!(matches!(array.as_json(), Value::Array(values) if values.len() == 3));
+        assert!(matches!(array, PyJsValue::Array(values) if values.len() == 3));
     }
 
     #[test]
@@ -444,7 +1094,7 @@ mod tests {
             Value::Number(Number::from(42)),
         )])));
 
-        assert!(matches!(object.as_json(), Value::Object(values) if values.contains_key("answer")));
+        assert!(matches!(object.as_object(), Some([(key, _)]) if key == "answer"));
     }
 
     #[test]
@@ -458,7 +1108,7 @@ mod tests {
 
             let value = PyJsValue::from_py(dict.as_any()).expect("dict should convert");
 
-            assert!(matches!(value.as_json(), Value::Object(_)));
+            assert!(matches!(value, PyJsValue::Object(_)));
         });
     }
 
@@ -471,11 +1121,14 @@ mod tests {
             dict.set_item("a", 2).expect("a should insert");
 
             let value = PyJsValue::from_py(dict.as_any()).expect("dict should convert");
-            let Value::Object(object) = value.as_json() else {
+            let Some(object) = value.as_object() else {
                 panic!("dict should convert to JSON object");
             };
 
-            assert_eq!(object.keys().collect::<Vec<_>>(), ["z", "a"]);
+            assert_eq!(
+                object.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+                ["z", "a"]
+            );
         });
     }
 
@@ -593,7 +1246,69 @@ mod tests {
             let round_trip =
                 PyJsValue::from_v8(scope, v8_value).expect("V8 should convert to JSON");
 
-            assert_eq!(round_trip.as_json(), value.as_json());
+            assert_eq!(round_trip, value);
+        });
+    }
+
+    #[test]
+    fn bridges_python_bytes_through_v8_as_uint8_arrays() {
+        pyo3::Python::initialize();
+        let value = pyo3::Python::attach(|py| {
+            let bytes =
+                PyList::new(py, [PyBytes::new(py, &[0, 1, 255])]).expect("list should build");
+            PyJsValue::from_py(bytes.as_any()).expect("bytes should convert")
+        });
+
+        with_test_js_runtime(move |runtime| {
+            deno_core::scope!(scope, runtime);
+            let v8_value = value.to_v8(scope).expect("bytes should convert to V8");
+            let array =
+                v8::Local::<v8::Array>::try_from(v8_value).expect("list should be an array");
+            let item = array.get_index(scope, 0).expect("array item should exist");
+            let round_trip = PyJsValue::from_v8(scope, v8_value).expect("V8 should convert back");
+
+            assert!(item.is_uint8_array());
+            assert_eq!(round_trip, value);
+            assert_eq!(
+                round_trip,
+                PyJsValue::Array(vec![PyJsValue::Bytes(vec![0, 1, 255])])
+            );
+        });
+    }
+
+    #[test]
+    fn bridges_dates_sets_and_maps_through_v8() {
+        let value = PyJsValue::Array(vec![
+            tagged_value(DATE_KEY, PyJsValue::Number(Number::from(86_400_000))),
+            tagged_value(
+                SET_KEY,
+                PyJsValue::Array(vec![PyJsValue::Number(Number::from(1)), PyJsValue::Null]),
+            ),
+            tagged_value(
+                MAP_KEY,
+                PyJsValue::Array(vec![PyJsValue::Array(vec![
+                    PyJsValue::String("key".to_string()),
+                    PyJsValue::Bool(true),
+                ])]),
+            ),
+        ]);
+
+        with_test_js_runtime(move |runtime| {
+            deno_core::scope!(scope, runtime);
+            let v8_value = value
+                .to_v8(scope)
+                .expect("tagged values should convert to V8");
+            let array =
+                v8::Local::<v8::Array>::try_from(v8_value).expect("list should be an array");
+            let items = (0..3)
+                .map(|index| array.get_index(scope, index).expect("item should exist"))
+                .collect::<Vec<_>>();
+            let round_trip = PyJsValue::from_v8(scope, v8_value).expect("V8 should convert back");
+
+            assert!(items[0].is_date());
+            assert!(items[1].is_set());
+            assert!(items[2].is_map());
+            assert_eq!(round_trip, value);
         });
     }
 
@@ -122,7 +122,13 @@ pub fn normalize_output_directory(
 
 pub fn read_script_file(py: Python<'_>, path: PathBuf) -> PyResult<(PathBuf, String)> {
     let path = absolutize(py, path)?;
-    let content = fs::read_to_string(&path).map_err(io_error_to_py)?;
+    let content = fs::read_to_string(&path).map_err(|err| {
+        let message = format!("Could not read script file {}: {err}", path.display());
+        match err.kind() {
+            io::ErrorKind::NotFound => PyFileNotFoundError::new_err(message),
+            _ => PyOSError::new_err(message),
+        }
+    })?;
     Ok((path, content))
 }
 
@@ -280,6 +286,22 @@ mod tests {
         remove_dir(&root);
     }
 
+    #[test]
+    fn missing_script_file_errors_include_the_path() {
+        let root = temp_dir("missing-script").expect("temp dir should be created");
+        let missing = root.join("missing.ts");
+
+        with_python(|py| {
+            let error =
+                read_script_file(py, missing.clone()).expect_err("missing script should fail");
+
+            assert!(error.is_instance_of::<PyFileNotFoundError>(py));
+            assert!(error.to_string().contains(&missing.display().to_string()));
+        });
+
+        remove_dir(&root);
+    }
+
     #[test]
     fn absolutizes_relative_script_paths_against_the_current_directory() {
         let _cwd_guard = CWD_LOCK.lock().expect("cwd lock should not be poisoned");
@@ -1,10 +1,15 @@
 use pyo3::{
-    PyErr,
+    PyErr, PyResult, Python,
     exceptions::{PyTypeError, PyValueError},
+    types::{PyAnyMethods, PyCFunction},
 };
 
-use crate::exceptions::{BelgieJavaScriptError, BelgieModuleError, BelgieRuntimeError};
-use crate::types::error::BindingError;
+use crate::exceptions::{
+    BelgieClosedError, BelgieJavaScriptError, BelgieModuleError, BelgieResultTooLargeError,
+    BelgieRuntimeError, BelgieSyntaxError, BelgieTimeoutError,
+};
+use crate::types::error::{BindingError, JavaScriptErrorDetails, SyntaxErrorLocation};
+use crate::types::value::PyJsValue;
 
 pub(crate) fn from_binding_error(error: BindingError) -> PyErr {
     match error {
@@ -17,10 +22,88 @@ pub(crate) fn from_binding_error(error: BindingError) -> PyErr {
         }
         BindingError::Runtime { message } => BelgieRuntimeError::new_err(message),
         BindingError::ModuleLoad { message } => BelgieModuleError::new_err(message),
+        BindingError::Syntax { message, location } => syntax_error(message, location.as_deref()),
+        BindingError::Closed { message } => BelgieClosedError::new_err(message),
         BindingError::MissingRunExport { .. } | BindingError::NonFunctionRunExport { .. } => {
             BelgieModuleError::new_err(error.message())
         }
-        BindingError::JavaScript { .. } => BelgieJavaScriptError::new_err(error.message()),
+        BindingError::JavaScript { message, details } => {
+            javascript_error(message, details.map(|details| *details).unwrap_or_default())
+        }
         BindingError::Argument { .. } => PyTypeError::new_err(error.message()),
+        BindingError::Timeout { .. } | BindingError::CpuBudgetExceeded { .. } => {
+            BelgieTimeoutError::new_err(error.message())
+        }
+        BindingError::ResultTooLarge { .. } => BelgieResultTooLargeError::new_err(error.message()),
+    }
+}
+
+fn syntax_error(message: String, location: Option<&SyntaxErrorLocation>) -> PyErr {
+    let error = BelgieSyntaxError::new_err(message);
+    let attached = Python::attach(|py| -> PyResult<()> {
+        let value = error.value(py);
+        value.setattr("line", location.map(|location| location.line))?;
+        value.setattr("column", location.map(|location| location.column))?;
+        value.setattr(
+            "source_line",
+            location.map(|location| location.source_line.clone()),
+        )?;
+        Ok(())
+    });
+    match attached {
+        Ok(()) => error,
+        Err(setattr_error) => setattr_error,
     }
 }
+
+fn javascript_error(message: String, details: JavaScriptErrorDetails) -> PyErr {
+    let error = BelgieJavaScriptError::new_err(message);
+    let document = error_document(&details);
+    let attached = Python::attach(|py| -> PyResult<()> {
+        let value = error.value(py);
+        value.setattr(
+            "frames",
+            PyJsValue::from_json(document["frames"].clone()).to_py(py)?,
+        )?;
+        // A fresh document per call, so callers may edit what they get before shipping it.
+        let to_json = PyCFunction::new_closure(py, None, None, move |args, _kwargs| {
+            PyJsValue::from_json(document.clone()).to_py(args.py())
+        })?;
+        value.setattr("to_json", to_json)?;
+        value.setattr("name", details.name)?;
+        value.setattr("message", details.message)?;
+        value.setattr("stack", details.stack)?;
+        value.setattr("filename", details.filename)?;
+        value.setattr("line", details.line)?;
+        value.setattr("column", details.column)?;
+        value.setattr("source_line", details.source_line)?;
+        value.setattr("request_id", details.request_id)?;
+        Ok(())
+    });
+    match attached {
+        Ok(()) => error,
+        Err(setattr_error) => setattr_error,
+    }
+}
+
+/// `{name, message, stack, frames}` as `to_json()` returns it, for log pipelines.
+fn error_document(details: &JavaScriptErrorDetails) -> serde_json::Value {
+    let frames = details
+        .frames
+        .iter()
+        .map(|frame| {
+            serde_json::json!({
+                "file": frame.file,
+                "line": frame.line,
+                "column": frame.column,
+                "function": frame.function,
+            })
+        })
+        .collect::<Vec<_>>();
+    serde_json::json!({
+        "name": details.name,
+        "message": details.message,
+        "stack": details.stack,
+        "frames": frames,
+    })
+}
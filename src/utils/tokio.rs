@@ -26,3 +26,11 @@ where
 {
     run_outside_runtime(|| pyo3_async_runtimes::tokio::get_runtime().block_on(make_future()))
 }
+
+/// Configures the Tokio runtime that drives awaitables returned to Python before anything can
+/// start it, instead of relying on pyo3-async-runtimes' lazily built default.
+pub(crate) fn init_async_bridge() {
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.enable_all().thread_name("belgie-tokio");
+    pyo3_async_runtimes::tokio::init(builder);
+}